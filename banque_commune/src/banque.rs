@@ -0,0 +1,704 @@
+use crate::cloture::{self, RapportFiscal};
+use crate::compte::{CompteBancaire, Encaissement, NatureOperation, Transaction, TypeCompte};
+use crate::evenement::{Evenement, EvenementDate};
+use crate::frais::{self, Grille, LigneFrais};
+use crate::tr;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+// Durée de conservation par défaut des clés d'idempotence
+pub const RETENTION_CLES_PAR_DEFAUT_HEURES: i64 = 24;
+
+// Frais prélevés quand un chèque ou un virement entrant est rejeté
+pub const FRAIS_REJET: f64 = 20.0;
+
+// Erreurs possibles lors d'une opération sur la banque
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ErreurBanque {
+    CompteIntrouvable(u32),
+    MontantInvalide,
+    SoldeInsuffisant,
+    NomVide,
+    NomDejaUtilise(String),
+    MemeCompte,
+    CleDejaUtilisee(String),
+    EncaissementIntrouvable(u32),
+    PeriodeRapprochee(NaiveDate),
+    RapprochementIncomplet { lignes: usize, transactions: usize },
+    MoisDejaFacture(NaiveDate),
+    MoisNonTermine(NaiveDate),
+    FraisNonPreleves(NaiveDate),
+    ExerciceNonTermine(i32),
+    ExerciceDejaClos(i32),
+    ExerciceClos(i32),
+    ExerciceNonClos(i32),
+    AccesRefuse,
+}
+
+impl fmt::Display for ErreurBanque {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            ErreurBanque::CompteIntrouvable(id) => tr!("erreur.compte_introuvable", id = id),
+            ErreurBanque::MontantInvalide => tr!("commun.montant_invalide").to_string(),
+            ErreurBanque::SoldeInsuffisant => tr!("erreur.solde_insuffisant").to_string(),
+            ErreurBanque::NomVide => tr!("creer.nom_vide").to_string(),
+            ErreurBanque::NomDejaUtilise(nom) => tr!("erreur.nom_deja_utilise", nom = nom),
+            ErreurBanque::MemeCompte => tr!("erreur.meme_compte").to_string(),
+            ErreurBanque::CleDejaUtilisee(cle) => tr!("erreur.cle_deja_utilisee", cle = cle),
+            ErreurBanque::EncaissementIntrouvable(numero) => tr!("erreur.encaissement_introuvable", numero = numero),
+            ErreurBanque::PeriodeRapprochee(date) => {
+                tr!("erreur.periode_rapprochee", date = date.format("%d/%m/%Y"))
+            }
+            ErreurBanque::RapprochementIncomplet { lignes, transactions } => {
+                tr!("erreur.rapprochement_incomplet", lignes = lignes, transactions = transactions)
+            }
+            ErreurBanque::MoisDejaFacture(mois) => tr!("erreur.mois_deja_facture", mois = mois.format("%m/%Y")),
+            ErreurBanque::MoisNonTermine(mois) => tr!("erreur.mois_non_termine", mois = mois.format("%m/%Y")),
+            ErreurBanque::FraisNonPreleves(mois) => tr!("erreur.frais_non_preleves", mois = mois.format("%m/%Y")),
+            ErreurBanque::ExerciceNonTermine(annee) => tr!("erreur.exercice_non_termine", annee = annee),
+            ErreurBanque::ExerciceDejaClos(annee) => tr!("erreur.exercice_deja_clos", annee = annee),
+            ErreurBanque::ExerciceClos(annee) => tr!("erreur.exercice_clos", annee = annee),
+            ErreurBanque::ExerciceNonClos(annee) => tr!("erreur.exercice_non_clos", annee = annee),
+            ErreurBanque::AccesRefuse => tr!("erreur.acces_refuse").to_string(),
+        };
+        f.write_str(&message)
+    }
+}
+
+// Un montant d'opération doit être strictement positif et fini
+fn montant_valide(montant: f64) -> bool {
+    montant > 0.0 && montant.is_finite()
+}
+
+// Opération sur les soldes pouvant être rejouée sans risque grâce à une clé d'idempotence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Operation {
+    Depot { id: u32, montant: f64 },
+    Retrait { id: u32, montant: f64 },
+    Transfert { source: u32, destination: u32, montant: f64 },
+}
+
+// Résultat d'une opération réussie : les soldes après l'opération
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "lowercase")]
+pub enum ResultatOperation {
+    Depot { id: u32, solde: f64 },
+    Retrait { id: u32, solde: f64 },
+    Transfert { source: u32, destination: u32, solde_source: f64, solde_destination: f64 },
+}
+
+// Résultat d'un appel à Banque::executer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Execution {
+    pub resultat: Result<ResultatOperation, ErreurBanque>,
+    // true si la clé était connue : le résultat d'origine est renvoyé sans refaire l'opération
+    pub rejouee: bool,
+}
+
+// Clé d'idempotence mémorisée avec l'opération et son résultat d'origine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CleIdempotence {
+    date: DateTime<Local>,
+    operation: Operation,
+    resultat: Result<ResultatOperation, ErreurBanque>,
+}
+
+// Ensemble des comptes, identifiés par un numéro stable (il ne change pas quand un compte est supprimé).
+// Chaque modification produit un événement qui est appliqué puis mis en attente d'écriture dans le journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Banque {
+    pub comptes: Vec<CompteBancaire>,
+    prochain_id: u32,
+    // Les clés d'idempotence font partie de l'état : elles sont reconstruites depuis le journal
+    cles: HashMap<String, CleIdempotence>,
+    retention_cles_heures: i64,
+    prochain_encaissement: u32,
+    // Dernier mois dont les frais ont été prélevés
+    #[serde(default)]
+    dernier_mois_facture: Option<NaiveDate>,
+    // Années clôturées : leurs transactions sont verrouillées
+    #[serde(default)]
+    exercices_clos: BTreeSet<i32>,
+    // Grille tarifaire, fournie à chaque lancement (elle ne fait pas partie de l'état)
+    #[serde(skip)]
+    tarifs: Grille,
+    // Jeton des administrateurs, fourni à chaque lancement : sans lui, aucun exercice ne peut être rouvert
+    #[serde(skip)]
+    jeton_admin: Option<String>,
+    // Numéro du dernier événement appliqué
+    dernier_evenement: u64,
+    // Événements produits depuis le dernier enregistrement du journal
+    #[serde(skip)]
+    nouveaux: Vec<EvenementDate>,
+}
+
+impl Default for Banque {
+    fn default() -> Banque {
+        Banque::new()
+    }
+}
+
+impl Banque {
+    pub fn new() -> Banque {
+        Banque {
+            comptes: Vec::new(),
+            prochain_id: 1,
+            cles: HashMap::new(),
+            retention_cles_heures: RETENTION_CLES_PAR_DEFAUT_HEURES,
+            prochain_encaissement: 1,
+            dernier_mois_facture: None,
+            exercices_clos: BTreeSet::new(),
+            tarifs: Grille::default(),
+            jeton_admin: None,
+            dernier_evenement: 0,
+            nouveaux: Vec::new(),
+        }
+    }
+
+    // Reconstruit une banque en rejouant des événements depuis une banque vide
+    pub fn reconstruire<'a>(evenements: impl IntoIterator<Item = &'a EvenementDate>) -> Banque {
+        let mut banque = Banque::new();
+        for evenement in evenements {
+            banque.appliquer(evenement);
+        }
+        banque
+    }
+
+    // Change la durée pendant laquelle une clé d'idempotence est conservée
+    pub fn definir_retention_cles(&mut self, heures: i64) {
+        self.retention_cles_heures = heures;
+    }
+
+    // Change la grille tarifaire utilisée pour les frais
+    pub fn definir_tarifs(&mut self, tarifs: Grille) {
+        self.tarifs = tarifs;
+    }
+
+    pub fn tarifs(&self) -> &Grille {
+        &self.tarifs
+    }
+
+    // Remplace l'état par celui d'une banque rechargée, en gardant la configuration fournie au lancement
+    pub fn remplacer_etat(&mut self, etat: Banque) {
+        let retention_cles_heures = self.retention_cles_heures;
+        let tarifs = std::mem::take(&mut self.tarifs);
+        let jeton_admin = self.jeton_admin.take();
+        *self = etat;
+        self.retention_cles_heures = retention_cles_heures;
+        self.tarifs = tarifs;
+        self.jeton_admin = jeton_admin;
+    }
+
+    // Définit le jeton demandé pour rouvrir un exercice clôturé
+    pub fn definir_jeton_admin(&mut self, jeton: Option<String>) {
+        self.jeton_admin = jeton;
+    }
+
+    // Banque de démonstration avec les trois comptes historiques du TP
+    pub fn demo() -> Banque {
+        let mut banque = Banque::new();
+        for (nom, solde) in [("Alice Dupont", 1000.0), ("Bob Martin", 500.0), ("Claire Durand", 750.0)] {
+            banque
+                .ouvrir_compte(nom, solde)
+                .expect("Les comptes de démonstration sont valides");
+        }
+        banque
+    }
+
+    // Numéro du dernier événement appliqué
+    pub fn dernier_evenement(&self) -> u64 {
+        self.dernier_evenement
+    }
+
+    // Événements pas encore écrits dans le journal
+    pub fn nouveaux_evenements(&self) -> &[EvenementDate] {
+        &self.nouveaux
+    }
+
+    // À appeler une fois les nouveaux événements écrits dans le journal
+    pub fn marquer_enregistres(&mut self) {
+        self.nouveaux.clear();
+    }
+
+    // Date et numérote un événement, l'applique et le garde pour le journal
+    fn emettre(&mut self, evenement: Evenement) {
+        let evenement = EvenementDate {
+            numero: self.dernier_evenement + 1,
+            date: Local::now(),
+            evenement,
+        };
+        self.appliquer(&evenement);
+        self.nouveaux.push(evenement);
+    }
+
+    // Applique un événement à l'état. Les vérifications ont eu lieu avant son émission :
+    // un événement qui ne correspond plus à rien (journal modifié à la main) est ignoré.
+    pub fn appliquer(&mut self, evenement: &EvenementDate) {
+        let date = evenement.date;
+        self.dernier_evenement = evenement.numero;
+        match &evenement.evenement {
+            Evenement::CompteOuvert { id, nom, solde } => {
+                self.comptes.push(CompteBancaire::ouvert_le(*id, nom.clone(), *solde, date));
+                self.prochain_id = self.prochain_id.max(id + 1);
+            }
+            Evenement::Depot { id, montant } => {
+                if let Ok(compte) = self.compte_mut(*id) {
+                    compte.crediter(NatureOperation::Depot, *montant, "Dépôt", date);
+                }
+            }
+            Evenement::Retrait { id, montant } => {
+                if let Ok(compte) = self.compte_mut(*id) {
+                    compte.debiter(NatureOperation::Retrait, *montant, "Retrait", date);
+                }
+            }
+            Evenement::Transfert { source, destination, montant } => {
+                if let (Ok(source), Ok(destination)) = (self.position(*source), self.position(*destination)) {
+                    let libelle_debit = format!("Transfert vers {}", self.comptes[destination].obtenir_nom());
+                    let libelle_credit = format!("Transfert de {}", self.comptes[source].obtenir_nom());
+                    if self.comptes[source].debiter(NatureOperation::TransfertEmis, *montant, &libelle_debit, date) {
+                        let destination = &mut self.comptes[destination];
+                        destination.crediter(NatureOperation::TransfertRecu, *montant, &libelle_credit, date);
+                    }
+                }
+            }
+            Evenement::CompteRenomme { id, nom } => {
+                if let Ok(position) = self.position(*id) {
+                    self.comptes[position] = self.comptes[position].renommer(nom.clone());
+                }
+            }
+            Evenement::CompteFerme { id } => {
+                if let Ok(position) = self.position(*id) {
+                    self.comptes.remove(position);
+                }
+            }
+            Evenement::EncaissementDepose { id, numero, libelle, montant, date_valeur } => {
+                if let Ok(compte) = self.compte_mut(*id) {
+                    let encaissement = Encaissement {
+                        numero: *numero,
+                        libelle: libelle.clone(),
+                        montant: *montant,
+                        date_valeur: *date_valeur,
+                        date_depot: date.date_naive(),
+                    };
+                    compte.ajouter_encaissement(encaissement, date);
+                }
+                self.prochain_encaissement = self.prochain_encaissement.max(numero + 1);
+            }
+            Evenement::EncaissementCompense { numero } => {
+                self.comptes.iter_mut().find_map(|compte| compte.compenser(*numero));
+            }
+            Evenement::EncaissementRejete { numero, frais } => {
+                self.comptes.iter_mut().find_map(|compte| compte.rejeter(*numero, *frais, date));
+            }
+            Evenement::RapprochementValide { id, jusqu_au } => {
+                if let Ok(compte) = self.compte_mut(*id) {
+                    compte.marquer_rapproche(*jusqu_au);
+                }
+            }
+            Evenement::TypeCompteChange { id, type_compte } => {
+                if let Ok(compte) = self.compte_mut(*id) {
+                    compte.changer_type(*type_compte);
+                }
+            }
+            Evenement::FraisPreleves { id, libelle, montant, mois } => {
+                if let Ok(compte) = self.compte_mut(*id) {
+                    compte.prelever_frais(*montant, libelle, *mois, date);
+                }
+            }
+            Evenement::MoisFacture { mois } => {
+                self.dernier_mois_facture = Some(*mois);
+            }
+            Evenement::InteretsVerses { id, montant, annee } => {
+                if let Ok(compte) = self.compte_mut(*id) {
+                    compte.verser_interets(*montant, *annee, date);
+                }
+            }
+            Evenement::ExerciceCloture { annee } => {
+                self.exercices_clos.insert(*annee);
+            }
+            Evenement::ExerciceRouvert { annee } => {
+                self.exercices_clos.remove(annee);
+            }
+            Evenement::CleEnregistree { cle, operation, resultat } => {
+                self.cles.insert(
+                    cle.clone(),
+                    CleIdempotence {
+                        date,
+                        operation: operation.clone(),
+                        resultat: resultat.clone(),
+                    },
+                );
+            }
+        }
+    }
+
+    // Position du compte dans la liste à partir de son identifiant
+    pub fn position(&self, id: u32) -> Result<usize, ErreurBanque> {
+        self.comptes
+            .iter()
+            .position(|compte| compte.obtenir_id() == id)
+            .ok_or(ErreurBanque::CompteIntrouvable(id))
+    }
+
+    pub fn compte(&self, id: u32) -> Result<&CompteBancaire, ErreurBanque> {
+        Ok(&self.comptes[self.position(id)?])
+    }
+
+    fn compte_mut(&mut self, id: u32) -> Result<&mut CompteBancaire, ErreurBanque> {
+        let position = self.position(id)?;
+        Ok(&mut self.comptes[position])
+    }
+
+    // Vérifie qu'un nom est utilisable pour un compte (non vide et pas déjà pris)
+    fn verifier_nom(&self, nom: &str) -> Result<(), ErreurBanque> {
+        if nom.trim().is_empty() {
+            return Err(ErreurBanque::NomVide);
+        }
+        if self.comptes.iter().any(|compte| compte.obtenir_nom() == nom) {
+            return Err(ErreurBanque::NomDejaUtilise(nom.to_string()));
+        }
+        Ok(())
+    }
+
+    // Crée un compte et retourne son identifiant
+    pub fn ouvrir_compte(&mut self, nom: &str, solde: f64) -> Result<u32, ErreurBanque> {
+        self.verifier_nom(nom)?;
+        if solde < 0.0 || !solde.is_finite() {
+            return Err(ErreurBanque::MontantInvalide);
+        }
+
+        let id = self.prochain_id;
+        self.emettre(Evenement::CompteOuvert {
+            id,
+            nom: nom.to_string(),
+            solde,
+        });
+        Ok(id)
+    }
+
+    // Supprime un compte et le retourne
+    pub fn fermer_compte(&mut self, id: u32) -> Result<CompteBancaire, ErreurBanque> {
+        let compte = self.compte(id)?.clone();
+        self.emettre(Evenement::CompteFerme { id });
+        Ok(compte)
+    }
+
+    pub fn renommer(&mut self, id: u32, nouveau_nom: &str) -> Result<(), ErreurBanque> {
+        self.verifier_nom(nouveau_nom)?;
+        self.position(id)?;
+        self.emettre(Evenement::CompteRenomme {
+            id,
+            nom: nouveau_nom.to_string(),
+        });
+        Ok(())
+    }
+
+    // Dépôt sur un compte, retourne le nouveau solde
+    pub fn deposer(&mut self, id: u32, montant: f64) -> Result<f64, ErreurBanque> {
+        self.position(id)?;
+        if !montant_valide(montant) {
+            return Err(ErreurBanque::MontantInvalide);
+        }
+        self.emettre(Evenement::Depot { id, montant });
+        Ok(self.compte(id)?.obtenir_solde())
+    }
+
+    // Retrait sur un compte, retourne le nouveau solde
+    pub fn retirer(&mut self, id: u32, montant: f64) -> Result<f64, ErreurBanque> {
+        let solde = self.compte(id)?.obtenir_solde();
+        if !montant_valide(montant) {
+            return Err(ErreurBanque::MontantInvalide);
+        }
+        if montant > solde {
+            return Err(ErreurBanque::SoldeInsuffisant);
+        }
+        self.emettre(Evenement::Retrait { id, montant });
+        Ok(self.compte(id)?.obtenir_solde())
+    }
+
+    // Transfert entre deux comptes (utilisé par le menu, l'interface plein écran et l'API)
+    pub fn transferer(&mut self, source: u32, destination: u32, montant: f64) -> Result<(), ErreurBanque> {
+        if source == destination {
+            return Err(ErreurBanque::MemeCompte);
+        }
+        let solde_source = self.compte(source)?.obtenir_solde();
+        self.position(destination)?;
+        if !montant_valide(montant) {
+            return Err(ErreurBanque::MontantInvalide);
+        }
+        if montant > solde_source {
+            return Err(ErreurBanque::SoldeInsuffisant);
+        }
+        self.emettre(Evenement::Transfert { source, destination, montant });
+        Ok(())
+    }
+
+    // Dépôt d'un chèque ou virement entrant, disponible seulement à sa date de valeur.
+    // Retourne le numéro de l'encaissement.
+    pub fn deposer_en_attente(
+        &mut self,
+        id: u32,
+        montant: f64,
+        date_valeur: NaiveDate,
+        libelle: &str,
+    ) -> Result<u32, ErreurBanque> {
+        if !montant_valide(montant) {
+            return Err(ErreurBanque::MontantInvalide);
+        }
+        self.position(id)?;
+        let numero = self.prochain_encaissement;
+        self.emettre(Evenement::EncaissementDepose {
+            id,
+            numero,
+            libelle: libelle.to_string(),
+            montant,
+            date_valeur,
+        });
+        Ok(numero)
+    }
+
+    // Vrai si des encaissements ont atteint leur date de valeur et attendent d'être rendus disponibles
+    pub fn encaissements_a_compenser(&self, date: NaiveDate) -> bool {
+        self.comptes
+            .iter()
+            .flat_map(|compte| compte.en_attente())
+            .any(|encaissement| encaissement.date_valeur <= date)
+    }
+
+    // Rend disponibles tous les encaissements dont la date de valeur est atteinte
+    pub fn compenser_encaissements(&mut self, date: NaiveDate) -> Vec<Encaissement> {
+        let arrives: Vec<Encaissement> = self
+            .comptes
+            .iter()
+            .flat_map(|compte| compte.en_attente())
+            .filter(|encaissement| encaissement.date_valeur <= date)
+            .cloned()
+            .collect();
+        for encaissement in &arrives {
+            self.emettre(Evenement::EncaissementCompense {
+                numero: encaissement.numero,
+            });
+        }
+        arrives
+    }
+
+    // Rejette un encaissement impayé (chèque sans provision...) et prélève les frais de rejet
+    pub fn rejeter_encaissement(&mut self, numero: u32) -> Result<Encaissement, ErreurBanque> {
+        let encaissement = self
+            .comptes
+            .iter()
+            .flat_map(|compte| compte.en_attente())
+            .find(|encaissement| encaissement.numero == numero)
+            .cloned()
+            .ok_or(ErreurBanque::EncaissementIntrouvable(numero))?;
+        if self.exercice_clos(encaissement.date_depot.year()) {
+            return Err(ErreurBanque::ExerciceClos(encaissement.date_depot.year()));
+        }
+        // Un encaissement comptabilisé dans une période rapprochée ne peut plus être contre-passé
+        let compte = self
+            .comptes
+            .iter()
+            .find(|compte| compte.en_attente().contains(&encaissement))
+            .expect("L'encaissement appartient à un compte");
+        if let Some(date) = compte.rapproche_jusqu_au()
+            && encaissement.date_depot <= date
+        {
+            return Err(ErreurBanque::PeriodeRapprochee(date));
+        }
+        self.emettre(Evenement::EncaissementRejete {
+            numero,
+            frais: FRAIS_REJET,
+        });
+        Ok(encaissement)
+    }
+
+    // Verrouille les transactions d'un compte jusqu'à une date (voir Rapprochement::valider).
+    // La date de rapprochement ne peut qu'avancer.
+    pub fn marquer_rapproche(&mut self, id: u32, jusqu_au: NaiveDate) -> Result<(), ErreurBanque> {
+        if let Some(date) = self.compte(id)?.rapproche_jusqu_au()
+            && jusqu_au <= date
+        {
+            return Err(ErreurBanque::PeriodeRapprochee(date));
+        }
+        self.emettre(Evenement::RapprochementValide { id, jusqu_au });
+        Ok(())
+    }
+
+    pub fn changer_type_compte(&mut self, id: u32, type_compte: TypeCompte) -> Result<(), ErreurBanque> {
+        self.position(id)?;
+        self.emettre(Evenement::TypeCompteChange { id, type_compte });
+        Ok(())
+    }
+
+    // Prélève sur chaque compte les frais d'un mois terminé, selon la grille tarifaire de son type.
+    // Chaque frais est une ligne séparée de l'historique ; un mois ne peut être facturé qu'une fois.
+    pub fn prelever_frais(&mut self, mois: NaiveDate) -> Result<Vec<LigneFrais>, ErreurBanque> {
+        let mois = frais::debut_du_mois(mois);
+        if self.dernier_mois_facture.is_some_and(|dernier| mois <= dernier) {
+            return Err(ErreurBanque::MoisDejaFacture(mois));
+        }
+        if frais::fin_du_mois(mois) >= Local::now().date_naive() {
+            return Err(ErreurBanque::MoisNonTermine(mois));
+        }
+
+        let lignes: Vec<LigneFrais> = self
+            .comptes
+            .iter()
+            .flat_map(|compte| {
+                let tarif = self.tarifs.tarif(compte.type_compte());
+                frais::calculer_frais(compte, tarif, mois)
+                    .into_iter()
+                    .map(|(libelle, montant)| LigneFrais {
+                        id: compte.obtenir_id(),
+                        nom: compte.obtenir_nom().to_string(),
+                        libelle,
+                        montant,
+                    })
+            })
+            .collect();
+        for ligne in &lignes {
+            self.emettre(Evenement::FraisPreleves {
+                id: ligne.id,
+                libelle: ligne.libelle.clone(),
+                montant: ligne.montant,
+                mois,
+            });
+        }
+        self.emettre(Evenement::MoisFacture { mois });
+        Ok(lignes)
+    }
+
+    // Frais prélevés pour un mois, d'après l'historique des comptes
+    pub fn recapitulatif_frais(&self, mois: NaiveDate) -> Vec<LigneFrais> {
+        let mois = frais::debut_du_mois(mois);
+        self.comptes
+            .iter()
+            .flat_map(|compte| {
+                compte
+                    .historique()
+                    .iter()
+                    .filter(move |transaction| transaction.mois_frais == Some(mois))
+                    .map(|transaction| LigneFrais {
+                        id: compte.obtenir_id(),
+                        nom: compte.obtenir_nom().to_string(),
+                        libelle: transaction.libelle.clone(),
+                        montant: -transaction.montant,
+                    })
+            })
+            .collect()
+    }
+
+    pub fn exercice_clos(&self, annee: i32) -> bool {
+        self.exercices_clos.contains(&annee)
+    }
+
+    pub fn exercices_clos(&self) -> &BTreeSet<i32> {
+        &self.exercices_clos
+    }
+
+    // Une transaction est verrouillée si elle est rapprochée ou si son exercice est clôturé
+    pub fn est_verrouillee(&self, compte: &CompteBancaire, transaction: &Transaction) -> bool {
+        compte.est_verrouillee(transaction) || self.exercice_clos(transaction.date.year())
+    }
+
+    // Clôture une année terminée dont les frais de décembre ont été prélevés : verse les intérêts
+    // créditeurs de l'année puis verrouille ses transactions. Retourne le récapitulatif fiscal.
+    pub fn cloturer_exercice(&mut self, annee: i32) -> Result<RapportFiscal, ErreurBanque> {
+        if self.exercice_clos(annee) {
+            return Err(ErreurBanque::ExerciceDejaClos(annee));
+        }
+        if annee >= Local::now().year() {
+            return Err(ErreurBanque::ExerciceNonTermine(annee));
+        }
+        let decembre = NaiveDate::from_ymd_opt(annee, 12, 1).ok_or(ErreurBanque::ExerciceNonTermine(annee))?;
+        if self.dernier_mois_facture.is_none_or(|dernier| dernier < decembre) {
+            return Err(ErreurBanque::FraisNonPreleves(decembre));
+        }
+
+        // Après une réouverture, seule la différence avec les intérêts déjà versés est versée
+        let versements: Vec<(u32, f64)> = self
+            .comptes
+            .iter()
+            .map(|compte| {
+                let tarif = self.tarifs.tarif(compte.type_compte());
+                let du = cloture::calculer_interets(compte, tarif, annee);
+                (compte.obtenir_id(), frais::arrondir(du - cloture::interets_verses(compte, annee)))
+            })
+            .filter(|(_, montant)| montant.abs() >= 0.005)
+            .collect();
+        for (id, montant) in versements {
+            self.emettre(Evenement::InteretsVerses { id, montant, annee });
+        }
+        self.emettre(Evenement::ExerciceCloture { annee });
+        Ok(cloture::rapport_fiscal(&self.comptes, annee))
+    }
+
+    // Rouvre un exercice clôturé, avec le jeton des administrateurs
+    pub fn rouvrir_exercice(&mut self, annee: i32, jeton: &str) -> Result<(), ErreurBanque> {
+        if self.jeton_admin.as_deref() != Some(jeton) {
+            return Err(ErreurBanque::AccesRefuse);
+        }
+        if !self.exercice_clos(annee) {
+            return Err(ErreurBanque::ExerciceNonClos(annee));
+        }
+        self.emettre(Evenement::ExerciceRouvert { annee });
+        Ok(())
+    }
+
+    // Exécute une opération. Si la clé d'idempotence a déjà été vue, le résultat d'origine
+    // est renvoyé sans appliquer l'opération une seconde fois.
+    pub fn executer(&mut self, operation: Operation, cle: Option<&str>) -> Execution {
+        self.purger_cles(Local::now());
+
+        if let Some(cle) = cle
+            && let Some(deja_vue) = self.cles.get(cle)
+        {
+            if deja_vue.operation != operation {
+                return Execution {
+                    resultat: Err(ErreurBanque::CleDejaUtilisee(cle.to_string())),
+                    rejouee: false,
+                };
+            }
+            return Execution {
+                resultat: deja_vue.resultat.clone(),
+                rejouee: true,
+            };
+        }
+
+        let resultat = match operation {
+            Operation::Depot { id, montant } => self
+                .deposer(id, montant)
+                .map(|solde| ResultatOperation::Depot { id, solde }),
+            Operation::Retrait { id, montant } => self
+                .retirer(id, montant)
+                .map(|solde| ResultatOperation::Retrait { id, solde }),
+            Operation::Transfert { source, destination, montant } => {
+                self.transferer(source, destination, montant).and_then(|()| {
+                    Ok(ResultatOperation::Transfert {
+                        source,
+                        destination,
+                        solde_source: self.compte(source)?.obtenir_solde(),
+                        solde_destination: self.compte(destination)?.obtenir_solde(),
+                    })
+                })
+            }
+        };
+
+        if let Some(cle) = cle {
+            self.emettre(Evenement::CleEnregistree {
+                cle: cle.to_string(),
+                operation,
+                resultat: resultat.clone(),
+            });
+        }
+        Execution { resultat, rejouee: false }
+    }
+
+    // Oublie les clés plus anciennes que la durée de rétention
+    fn purger_cles(&mut self, maintenant: DateTime<Local>) {
+        let limite = maintenant - Duration::hours(self.retention_cles_heures);
+        self.cles.retain(|_, cle| cle.date >= limite);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::compte::{CompteBancaire, LIBELLE_FRAIS_REJET, TypeCompte};
+use crate::frais::{self, Tarif};
+use crate::{langue, tr};
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeMap;
+
+fn premier_jour(annee: i32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(annee, 1, 1)
+}
+
+fn dernier_jour(annee: i32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(annee, 12, 31)
+}
+
+// Intérêts créditeurs dus pour une année : somme des soldes positifs de fin de journée × taux / 365,
+// à partir de l'ouverture du compte
+pub fn calculer_interets(compte: &CompteBancaire, tarif: &Tarif, annee: i32) -> f64 {
+    let (Some(debut), Some(fin)) = (premier_jour(annee), dernier_jour(annee)) else {
+        return 0.0;
+    };
+    if tarif.taux_remuneration <= 0.0 {
+        return 0.0;
+    }
+    let debut = debut.max(compte.date_ouverture().date_naive());
+    let solde_cumule: f64 = debut
+        .iter_days()
+        .take_while(|jour| *jour <= fin)
+        .map(|jour| compte.solde_au(jour).max(0.0))
+        .sum();
+    frais::arrondir(solde_cumule * tarif.taux_remuneration / 365.0)
+}
+
+// Intérêts déjà versés au titre d'une année
+pub fn interets_verses(compte: &CompteBancaire, annee: i32) -> f64 {
+    compte
+        .historique()
+        .iter()
+        .filter(|transaction| transaction.exercice_interets == Some(annee))
+        .fold(0.0, |total, transaction| total + transaction.montant)
+}
+
+// Frais payés au titre d'une année : frais mensuels facturés pour ses mois et frais de rejet
+pub fn frais_payes(compte: &CompteBancaire, annee: i32) -> f64 {
+    compte
+        .historique()
+        .iter()
+        .filter(|transaction| match transaction.mois_frais {
+            Some(mois) => mois.year() == annee,
+            None => transaction.libelle == LIBELLE_FRAIS_REJET && transaction.date.year() == annee,
+        })
+        .fold(0.0, |total, transaction| total - transaction.montant)
+}
+
+// Ligne du récapitulatif fiscal pour un compte
+#[derive(Debug, Clone, PartialEq)]
+pub struct LigneFiscale {
+    pub id: u32,
+    pub type_compte: TypeCompte,
+    // Solde comptable au 31 décembre
+    pub solde_fin: f64,
+    pub interets: f64,
+    pub frais: f64,
+}
+
+// Comptes d'un même titulaire
+#[derive(Debug, Clone, PartialEq)]
+pub struct Titulaire {
+    pub nom: String,
+    pub comptes: Vec<LigneFiscale>,
+}
+
+impl Titulaire {
+    pub fn interets(&self) -> f64 {
+        self.comptes.iter().map(|ligne| ligne.interets).sum()
+    }
+
+    pub fn frais(&self) -> f64 {
+        self.comptes.iter().map(|ligne| ligne.frais).sum()
+    }
+}
+
+// Récapitulatif fiscal annuel : par titulaire, intérêts perçus, frais payés et soldes de fin d'année
+#[derive(Debug, Clone, PartialEq)]
+pub struct RapportFiscal {
+    pub annee: i32,
+    pub titulaires: Vec<Titulaire>,
+}
+
+// Établit le récapitulatif d'une année pour les comptes ouverts au 31 décembre, titulaires par ordre alphabétique
+pub fn rapport_fiscal(comptes: &[CompteBancaire], annee: i32) -> RapportFiscal {
+    let mut titulaires: BTreeMap<&str, Vec<LigneFiscale>> = BTreeMap::new();
+    if let Some(fin) = dernier_jour(annee) {
+        for compte in comptes.iter().filter(|compte| compte.date_ouverture().date_naive() <= fin) {
+            titulaires.entry(compte.obtenir_nom()).or_default().push(LigneFiscale {
+                id: compte.obtenir_id(),
+                type_compte: compte.type_compte(),
+                solde_fin: compte.solde_au(fin),
+                interets: interets_verses(compte, annee),
+                frais: frais_payes(compte, annee),
+            });
+        }
+    }
+    RapportFiscal {
+        annee,
+        titulaires: titulaires
+            .into_iter()
+            .map(|(nom, comptes)| Titulaire {
+                nom: nom.to_string(),
+                comptes,
+            })
+            .collect(),
+    }
+}
+
+impl RapportFiscal {
+    pub fn en_texte(&self) -> String {
+        let mut texte = format!("{}\n", tr!("fiscal.titre", annee = self.annee));
+        if self.titulaires.is_empty() {
+            texte.push_str(&format!("{}\n", tr!("fiscal.aucun_compte")));
+        }
+        for titulaire in &self.titulaires {
+            texte.push_str(&format!("\n{}\n", titulaire.nom));
+            for ligne in &titulaire.comptes {
+                let ligne = tr!(
+                    "fiscal.compte",
+                    id = ligne.id,
+                    type_compte = ligne.type_compte,
+                    solde = langue::montant(ligne.solde_fin),
+                    interets = langue::montant(ligne.interets),
+                    frais = langue::montant(ligne.frais)
+                );
+                texte.push_str(&format!("  {}\n", ligne));
+            }
+            let total = tr!(
+                "fiscal.total",
+                interets = langue::montant(titulaire.interets()),
+                frais = langue::montant(titulaire.frais())
+            );
+            texte.push_str(&format!("  {}\n", total));
+        }
+        texte
+    }
+
+    // Une ligne par compte, séparée par des points-virgules comme les relevés
+    pub fn en_csv(&self) -> String {
+        let mut ecrivain = csv::WriterBuilder::new().delimiter(b';').from_writer(Vec::new());
+        let mut ecrire = |champs: &[String]| ecrivain.write_record(champs).expect("Écriture en mémoire");
+        ecrire(&["annee", "titulaire", "compte", "type", "solde_31_12", "interets", "frais"].map(String::from));
+        for titulaire in &self.titulaires {
+            for ligne in &titulaire.comptes {
+                ecrire(&[
+                    self.annee.to_string(),
+                    titulaire.nom.clone(),
+                    ligne.id.to_string(),
+                    ligne.type_compte.to_string(),
+                    format!("{:.2}", ligne.solde_fin),
+                    format!("{:.2}", ligne.interets),
+                    format!("{:.2}", ligne.frais),
+                ]);
+            }
+        }
+        let octets = ecrivain.into_inner().expect("Écriture en mémoire");
+        String::from_utf8(octets).expect("Le CSV est en UTF-8")
+    }
+}
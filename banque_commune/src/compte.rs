@@ -0,0 +1,351 @@
+use chrono::{DateTime, Local, NaiveDate};
+use crate::{langue, tr};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Write};
+
+// Au-dessus de ce solde, un compte est signalé « Compte VIP »
+pub const SEUIL_VIP: f64 = 1000.0;
+
+// Libellé de la ligne de frais prélevée quand un encaissement est rejeté
+pub const LIBELLE_FRAIS_REJET: &str = "Frais de rejet";
+
+// Type de compte, qui détermine les frais appliqués (voir frais.rs)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TypeCompte {
+    #[default]
+    Courant,
+    Epargne,
+    Professionnel,
+}
+
+impl TypeCompte {
+    pub const TOUS: [TypeCompte; 3] = [TypeCompte::Courant, TypeCompte::Epargne, TypeCompte::Professionnel];
+}
+
+impl fmt::Display for TypeCompte {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeCompte::Courant => f.write_str(tr!("type.courant")),
+            TypeCompte::Epargne => f.write_str(tr!("type.epargne")),
+            TypeCompte::Professionnel => f.write_str(tr!("type.professionnel")),
+        }
+    }
+}
+
+// Nature d'une ligne de l'historique, indépendante de son libellé (les frais par opération en dépendent)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NatureOperation {
+    Depot,
+    Retrait,
+    TransfertEmis,
+    TransfertRecu,
+    // Solde initial, encaissement, rejet, frais, intérêts
+    #[default]
+    Autre,
+}
+
+// Une opération passée sur un compte (montant positif = crédit, négatif = débit)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub date: DateTime<Local>,
+    pub libelle: String,
+    pub montant: f64,
+    #[serde(default)]
+    pub nature: NatureOperation,
+    // Pour une ligne de frais : le mois facturé (premier jour du mois)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mois_frais: Option<NaiveDate>,
+    // Pour un versement d'intérêts créditeurs : l'exercice rémunéré
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exercice_interets: Option<i32>,
+    // Pour un encaissement et sa contre-passation : le numéro de l'encaissement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encaissement: Option<u32>,
+}
+
+// Fonds déposés (chèque, virement entrant) qui ne deviennent disponibles qu'à leur date de valeur
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Encaissement {
+    pub numero: u32,
+    pub libelle: String,
+    pub montant: f64,
+    pub date_valeur: NaiveDate,
+    // Jour où l'encaissement a été comptabilisé
+    #[serde(default)]
+    pub date_depot: NaiveDate,
+}
+
+// Structure pour représenter un compte bancaire
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompteBancaire {
+    id: u32,
+    nom: String,
+    // Solde disponible : seuls ces fonds peuvent être retirés
+    solde: f64,
+    historique: Vec<Transaction>,
+    // Encaissements comptabilisés mais pas encore disponibles
+    #[serde(default)]
+    en_attente: Vec<Encaissement>,
+    // Date jusqu'à laquelle le compte a été rapproché du relevé de la banque :
+    // les transactions jusqu'à cette date sont verrouillées
+    #[serde(default)]
+    rapproche_jusqu_au: Option<NaiveDate>,
+    #[serde(default)]
+    type_compte: TypeCompte,
+    #[serde(default)]
+    date_ouverture: DateTime<Local>,
+}
+
+impl CompteBancaire {
+    // Méthode pour créer un nouveau compte
+    pub fn nouveau(id: u32, nom: String, solde: f64) -> CompteBancaire {
+        CompteBancaire::ouvert_le(id, nom, solde, Local::now())
+    }
+
+    // Crée un compte ouvert à une date donnée (rejeu du journal des événements)
+    pub(crate) fn ouvert_le(id: u32, nom: String, solde: f64, date: DateTime<Local>) -> CompteBancaire {
+        let mut compte = CompteBancaire {
+            id,
+            nom,
+            solde,
+            historique: Vec::new(),
+            en_attente: Vec::new(),
+            rapproche_jusqu_au: None,
+            type_compte: TypeCompte::default(),
+            date_ouverture: date,
+        };
+        if solde != 0.0 {
+            compte.enregistrer(NatureOperation::Autre, "Solde initial", solde, date);
+        }
+        compte
+    }
+
+    // Ajoute une ligne à l'historique du compte
+    fn enregistrer(&mut self, nature: NatureOperation, libelle: &str, montant: f64, date: DateTime<Local>) {
+        self.historique.push(Transaction {
+            date,
+            libelle: libelle.to_string(),
+            montant,
+            nature,
+            mois_frais: None,
+            exercice_interets: None,
+            encaissement: None,
+        });
+    }
+
+    // Ajoute une ligne d'encaissement (ou de contre-passation) à l'historique
+    fn enregistrer_encaissement(&mut self, numero: u32, libelle: &str, montant: f64, date: DateTime<Local>) {
+        self.enregistrer(NatureOperation::Autre, libelle, montant, date);
+        if let Some(transaction) = self.historique.last_mut() {
+            transaction.encaissement = Some(numero);
+        }
+    }
+
+    // Débite le compte si le solde le permet, avec la nature et le libellé donnés
+    pub(crate) fn debiter(
+        &mut self,
+        nature: NatureOperation,
+        montant: f64,
+        libelle: &str,
+        date: DateTime<Local>,
+    ) -> bool {
+        if montant <= self.solde && montant > 0.0 {
+            self.solde -= montant;
+            self.enregistrer(nature, libelle, -montant, date);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Crédite le compte d'un montant strictement positif, avec la nature et le libellé donnés
+    pub(crate) fn crediter(
+        &mut self,
+        nature: NatureOperation,
+        montant: f64,
+        libelle: &str,
+        date: DateTime<Local>,
+    ) -> bool {
+        if montant > 0.0 {
+            self.solde += montant;
+            self.enregistrer(nature, libelle, montant, date);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Méthode pour effectuer un retrait
+    pub fn retrait(&mut self, montant: f64) -> bool {
+        self.debiter(NatureOperation::Retrait, montant, "Retrait", Local::now())
+    }
+
+    // Points bonus: Méthode pour effectuer un dépôt (empêche les montants négatifs)
+    pub fn depot(&mut self, montant: f64) -> bool {
+        self.crediter(NatureOperation::Depot, montant, "Dépôt", Local::now())
+    }
+
+    // Points bonus: Méthode renommer qui renvoie un nouveau compte avec le nom changé
+    pub fn renommer(&self, nouveau_nom: String) -> CompteBancaire {
+        CompteBancaire {
+            id: self.id,
+            nom: nouveau_nom,
+            solde: self.solde,
+            historique: self.historique.clone(),
+            en_attente: self.en_attente.clone(),
+            rapproche_jusqu_au: self.rapproche_jusqu_au,
+            type_compte: self.type_compte,
+            date_ouverture: self.date_ouverture,
+        }
+    }
+
+    // Comptabilise un encaissement : il apparaît dans l'historique mais n'est pas encore disponible
+    pub(crate) fn ajouter_encaissement(&mut self, encaissement: Encaissement, date: DateTime<Local>) {
+        self.enregistrer_encaissement(encaissement.numero, &encaissement.libelle, encaissement.montant, date);
+        self.en_attente.push(encaissement);
+    }
+
+    // Rend disponible un encaissement en attente et le retourne
+    pub(crate) fn compenser(&mut self, numero: u32) -> Option<Encaissement> {
+        let position = self.en_attente.iter().position(|encaissement| encaissement.numero == numero)?;
+        let encaissement = self.en_attente.remove(position);
+        self.solde += encaissement.montant;
+        Some(encaissement)
+    }
+
+    // Annule un encaissement impayé : il est contre-passé et les frais de rejet sont prélevés
+    pub(crate) fn rejeter(&mut self, numero: u32, frais: f64, date: DateTime<Local>) -> Option<Encaissement> {
+        let position = self.en_attente.iter().position(|encaissement| encaissement.numero == numero)?;
+        let encaissement = self.en_attente.remove(position);
+        let libelle = format!("Rejet: {}", encaissement.libelle);
+        self.enregistrer_encaissement(numero, &libelle, -encaissement.montant, date);
+        if frais > 0.0 {
+            self.solde -= frais;
+            self.enregistrer(NatureOperation::Autre, LIBELLE_FRAIS_REJET, -frais, date);
+        }
+        Some(encaissement)
+    }
+
+    // Prélève des frais, même si le solde devient négatif : ils forment une ligne à part de l'historique
+    pub(crate) fn prelever_frais(&mut self, montant: f64, libelle: &str, mois: NaiveDate, date: DateTime<Local>) {
+        self.solde -= montant;
+        self.historique.push(Transaction {
+            date,
+            libelle: libelle.to_string(),
+            montant: -montant,
+            nature: NatureOperation::Autre,
+            mois_frais: Some(mois),
+            exercice_interets: None,
+            encaissement: None,
+        });
+    }
+
+    // Verse les intérêts créditeurs d'un exercice (négatifs pour une régularisation après réouverture)
+    pub(crate) fn verser_interets(&mut self, montant: f64, annee: i32, date: DateTime<Local>) {
+        self.solde += montant;
+        self.historique.push(Transaction {
+            date,
+            libelle: format!("Intérêts créditeurs {}", annee),
+            montant,
+            nature: NatureOperation::Autre,
+            mois_frais: None,
+            exercice_interets: Some(annee),
+            encaissement: None,
+        });
+    }
+
+    pub(crate) fn changer_type(&mut self, type_compte: TypeCompte) {
+        self.type_compte = type_compte;
+    }
+
+    // Méthode pour obtenir le type du compte
+    pub fn type_compte(&self) -> TypeCompte {
+        self.type_compte
+    }
+
+    // Méthode pour obtenir la date d'ouverture du compte
+    pub fn date_ouverture(&self) -> DateTime<Local> {
+        self.date_ouverture
+    }
+
+    // Solde comptable en fin de journée, d'après l'historique
+    pub fn solde_au(&self, date: NaiveDate) -> f64 {
+        self.historique
+            .iter()
+            .filter(|transaction| transaction.date.date_naive() <= date)
+            .map(|transaction| transaction.montant)
+            .sum()
+    }
+
+    // Enregistre la date jusqu'à laquelle le compte est rapproché
+    pub(crate) fn marquer_rapproche(&mut self, jusqu_au: NaiveDate) {
+        self.rapproche_jusqu_au = Some(jusqu_au);
+    }
+
+    // Méthode pour obtenir la date du dernier rapprochement
+    pub fn rapproche_jusqu_au(&self) -> Option<NaiveDate> {
+        self.rapproche_jusqu_au
+    }
+
+    // Une transaction rapprochée ne peut plus être remise en cause
+    pub fn est_verrouillee(&self, transaction: &Transaction) -> bool {
+        self.rapproche_jusqu_au
+            .is_some_and(|date| transaction.date.date_naive() <= date)
+    }
+
+    // Méthode pour obtenir le solde (disponible)
+    pub fn obtenir_solde(&self) -> f64 {
+        self.solde
+    }
+
+    // Méthode pour obtenir le solde comptable : disponible + encaissements en attente
+    pub fn obtenir_solde_comptable(&self) -> f64 {
+        self.solde + self.en_attente.iter().map(|encaissement| encaissement.montant).sum::<f64>()
+    }
+
+    // Méthode pour obtenir les encaissements en attente
+    pub fn en_attente(&self) -> &[Encaissement] {
+        &self.en_attente
+    }
+
+    // Méthode pour obtenir le nom
+    pub fn obtenir_nom(&self) -> &str {
+        &self.nom
+    }
+
+    // Méthode pour obtenir l'identifiant du compte
+    pub fn obtenir_id(&self) -> u32 {
+        self.id
+    }
+
+    // Méthode pour obtenir l'historique des opérations (de la plus ancienne à la plus récente)
+    pub fn historique(&self) -> &[Transaction] {
+        &self.historique
+    }
+
+    // Méthode pour afficher les informations du compte
+    pub fn afficher_infos(&self, sortie: &mut impl Write) -> io::Result<()> {
+        if self.en_attente.is_empty() {
+            writeln!(sortie, "{}", tr!("compte.infos", nom = self.nom, solde = langue::montant(self.solde)))
+        } else {
+            let infos = tr!(
+                "compte.infos_comptable",
+                nom = self.nom,
+                solde = langue::montant(self.solde),
+                comptable = langue::montant(self.obtenir_solde_comptable())
+            );
+            writeln!(sortie, "{}", infos)
+        }
+    }
+
+    // Méthode pour vérifier si le compte a le statut VIP
+    pub fn est_vip(&self) -> bool {
+        self.solde > SEUIL_VIP
+    }
+
+    // Nouvelle méthode pour vérifier si le compte est en découvert
+    pub fn est_en_decouvert(&self) -> bool {
+        self.solde < 0.0
+    }
+}
@@ -0,0 +1,44 @@
+use crate::banque::{ErreurBanque, Operation, ResultatOperation};
+use crate::compte::TypeCompte;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// Fait survenu dans la banque. L'état des comptes n'est jamais modifié directement :
+// il est obtenu en rejouant ces événements dans l'ordre.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Evenement {
+    CompteOuvert { id: u32, nom: String, solde: f64 },
+    Depot { id: u32, montant: f64 },
+    Retrait { id: u32, montant: f64 },
+    Transfert { source: u32, destination: u32, montant: f64 },
+    CompteRenomme { id: u32, nom: String },
+    CompteFerme { id: u32 },
+    EncaissementDepose { id: u32, numero: u32, libelle: String, montant: f64, date_valeur: NaiveDate },
+    EncaissementCompense { numero: u32 },
+    EncaissementRejete { numero: u32, frais: f64 },
+    RapprochementValide { id: u32, jusqu_au: NaiveDate },
+    TypeCompteChange { id: u32, type_compte: TypeCompte },
+    // Une ligne de frais, puis la fin de la facturation du mois (premier jour du mois)
+    FraisPreleves { id: u32, libelle: String, montant: f64, mois: NaiveDate },
+    MoisFacture { mois: NaiveDate },
+    // Intérêts créditeurs d'un exercice, puis sa clôture ; la réouverture est réservée à l'administrateur
+    InteretsVerses { id: u32, montant: f64, annee: i32 },
+    ExerciceCloture { annee: i32 },
+    ExerciceRouvert { annee: i32 },
+    // Une clé d'idempotence et le résultat d'origine de son opération (réussie ou non)
+    CleEnregistree {
+        cle: String,
+        operation: Operation,
+        resultat: Result<ResultatOperation, ErreurBanque>,
+    },
+}
+
+// Événement numéroté et daté, tel qu'il est écrit dans le journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvenementDate {
+    pub numero: u64,
+    pub date: DateTime<Local>,
+    #[serde(flatten)]
+    pub evenement: Evenement,
+}
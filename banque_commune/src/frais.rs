@@ -0,0 +1,168 @@
+use crate::compte::{CompteBancaire, NatureOperation, SEUIL_VIP, TypeCompte};
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::{fs, io};
+
+// Tarification d'un type de compte
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tarif {
+    // Frais de tenue de compte prélevés chaque mois
+    pub frais_mensuels: f64,
+    // Retraits et transferts émis gratuits chaque mois, les suivants sont facturés
+    pub operations_gratuites: u32,
+    pub frais_par_operation: f64,
+    // Taux annuel des intérêts débiteurs, calculés jour par jour sur le découvert
+    pub taux_decouvert: f64,
+    // Pas de frais de tenue de compte ni d'opérations si le solde de fin de mois dépasse ce seuil
+    pub exoneration_au_dessus_de: Option<f64>,
+    // Taux annuel de rémunération du solde créditeur, versée à la clôture de l'exercice
+    #[serde(default)]
+    pub taux_remuneration: f64,
+}
+
+// Grille tarifaire : un tarif par type de compte. Chargée depuis un fichier JSON (option --tarifs),
+// les types absents du fichier gardent leur tarif par défaut.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Grille {
+    #[serde(default = "tarif_courant")]
+    pub courant: Tarif,
+    #[serde(default = "tarif_epargne")]
+    pub epargne: Tarif,
+    #[serde(default = "tarif_professionnel")]
+    pub professionnel: Tarif,
+}
+
+fn tarif_courant() -> Tarif {
+    Tarif {
+        frais_mensuels: 2.0,
+        operations_gratuites: 5,
+        frais_par_operation: 0.5,
+        taux_decouvert: 0.08,
+        // Les comptes VIP ne paient pas de frais
+        exoneration_au_dessus_de: Some(SEUIL_VIP),
+        taux_remuneration: 0.0,
+    }
+}
+
+fn tarif_epargne() -> Tarif {
+    Tarif {
+        frais_mensuels: 0.0,
+        operations_gratuites: 2,
+        frais_par_operation: 1.0,
+        taux_decouvert: 0.0,
+        exoneration_au_dessus_de: None,
+        taux_remuneration: 0.02,
+    }
+}
+
+fn tarif_professionnel() -> Tarif {
+    Tarif {
+        frais_mensuels: 8.0,
+        operations_gratuites: 20,
+        frais_par_operation: 0.3,
+        taux_decouvert: 0.1,
+        exoneration_au_dessus_de: None,
+        taux_remuneration: 0.0,
+    }
+}
+
+impl Default for Grille {
+    fn default() -> Grille {
+        Grille {
+            courant: tarif_courant(),
+            epargne: tarif_epargne(),
+            professionnel: tarif_professionnel(),
+        }
+    }
+}
+
+impl Grille {
+    pub fn charger(chemin: &Path) -> io::Result<Grille> {
+        let contenu = fs::read_to_string(chemin)?;
+        serde_json::from_str(&contenu).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn tarif(&self, type_compte: TypeCompte) -> &Tarif {
+        match type_compte {
+            TypeCompte::Courant => &self.courant,
+            TypeCompte::Epargne => &self.epargne,
+            TypeCompte::Professionnel => &self.professionnel,
+        }
+    }
+}
+
+// Ligne de frais d'un compte pour un mois
+#[derive(Debug, Clone, PartialEq)]
+pub struct LigneFrais {
+    pub id: u32,
+    pub nom: String,
+    pub libelle: String,
+    pub montant: f64,
+}
+
+pub fn debut_du_mois(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("Le premier du mois existe toujours")
+}
+
+pub fn fin_du_mois(date: NaiveDate) -> NaiveDate {
+    let suivant = debut_du_mois(date) + Months::new(1);
+    suivant.pred_opt().expect("La veille d'un premier du mois existe")
+}
+
+pub(crate) fn arrondir(montant: f64) -> f64 {
+    (montant * 100.0).round() / 100.0
+}
+
+// Les opérations comptées dans le quota : retraits et transferts émis
+fn est_operation_payante(nature: NatureOperation) -> bool {
+    matches!(nature, NatureOperation::Retrait | NatureOperation::TransfertEmis)
+}
+
+// Calcule les frais d'un compte pour le mois contenant `mois` : (libellé, montant positif)
+pub fn calculer_frais(compte: &CompteBancaire, tarif: &Tarif, mois: NaiveDate) -> Vec<(String, f64)> {
+    let debut = debut_du_mois(mois);
+    let fin = fin_du_mois(mois);
+    let periode = debut.format("%m/%Y");
+    let mut frais = Vec::new();
+    // Un compte ouvert après la fin du mois n'a rien à payer pour ce mois
+    if compte.date_ouverture().date_naive() > fin {
+        return frais;
+    }
+
+    let exonere = tarif
+        .exoneration_au_dessus_de
+        .is_some_and(|seuil| compte.solde_au(fin) > seuil);
+    if !exonere {
+        if tarif.frais_mensuels > 0.0 {
+            frais.push((format!("Frais de tenue de compte {}", periode), tarif.frais_mensuels));
+        }
+
+        let operations = compte
+            .historique()
+            .iter()
+            .filter(|transaction| (debut..=fin).contains(&transaction.date.date_naive()))
+            .filter(|transaction| est_operation_payante(transaction.nature))
+            .count() as u32;
+        if operations > tarif.operations_gratuites && tarif.frais_par_operation > 0.0 {
+            let payantes = operations - tarif.operations_gratuites;
+            frais.push((
+                format!("Frais d'opérations ({} au-delà de {}) {}", payantes, tarif.operations_gratuites, periode),
+                arrondir(payantes as f64 * tarif.frais_par_operation),
+            ));
+        }
+    }
+
+    // Intérêts débiteurs : somme des découverts de fin de journée × taux / 365
+    let decouvert_cumule: f64 = debut
+        .iter_days()
+        .take_while(|jour| *jour <= fin)
+        .map(|jour| (-compte.solde_au(jour)).max(0.0))
+        .sum();
+    let interets = arrondir(decouvert_cumule * tarif.taux_decouvert / 365.0);
+    if interets > 0.0 {
+        frais.push((format!("Intérêts débiteurs {}", periode), interets));
+    }
+
+    frais
+}
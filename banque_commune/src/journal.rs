@@ -0,0 +1,289 @@
+use crate::banque::Banque;
+use crate::evenement::EvenementDate;
+use crate::notification::Notifieur;
+use crate::tr;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+// Nombre d'événements écrits entre deux instantanés
+pub const INTERVALLE_INSTANTANES: u64 = 100;
+
+// État complet de la banque à un point du journal : au démarrage, seuls les événements
+// écrits après cette position sont rejoués
+#[derive(Serialize, Deserialize)]
+struct Instantane {
+    position_journal: u64,
+    banque: Banque,
+}
+
+// Erreur d'enregistrement dans le journal
+#[derive(Debug)]
+pub enum ErreurJournal {
+    Io(io::Error),
+    // Un autre processus a complété le journal depuis notre dernière lecture : les versions
+    // (numéro du dernier événement) ne correspondent plus
+    Conflit { version_attendue: u64, version_trouvee: u64 },
+    LectureSeule,
+}
+
+impl fmt::Display for ErreurJournal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErreurJournal::Io(e) => write!(f, "{}", e),
+            ErreurJournal::Conflit { version_attendue, version_trouvee } => f.write_str(&tr!(
+                "journal.conflit",
+                trouvee = version_trouvee,
+                attendue = version_attendue
+            )),
+            ErreurJournal::LectureSeule => f.write_str(tr!("journal.lecture_seule")),
+        }
+    }
+}
+
+impl From<io::Error> for ErreurJournal {
+    fn from(e: io::Error) -> ErreurJournal {
+        ErreurJournal::Io(e)
+    }
+}
+
+// Journal des événements (un événement JSON par ligne, uniquement complété) et son dernier instantané.
+// Plusieurs processus peuvent partager le même journal : les lectures prennent un verrou partagé, les
+// écritures un verrou exclusif, et une écriture est refusée si le journal a été complété depuis
+// notre dernière lecture.
+pub struct Journal {
+    chemin: PathBuf,
+    instantane: PathBuf,
+    // Numéro du dernier événement couvert par l'instantané
+    dernier_instantane: u64,
+    // Taille du journal déjà appliquée à la banque
+    position: u64,
+    lecture_seule: bool,
+    // Notifications envoyées pour les événements que nous enregistrons
+    notifieur: Option<Notifieur>,
+}
+
+fn donnees_invalides(e: serde_json::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+impl Journal {
+    // L'instantané est rangé à côté du journal : banque.journal -> banque.instantane
+    pub fn new(chemin: &Path) -> Journal {
+        Journal {
+            chemin: chemin.to_path_buf(),
+            instantane: chemin.with_extension("instantane"),
+            dernier_instantane: 0,
+            position: 0,
+            lecture_seule: false,
+            notifieur: None,
+        }
+    }
+
+    // En lecture seule, le journal n'est jamais verrouillé en écriture et les enregistrements sont refusés
+    pub fn definir_lecture_seule(&mut self, lecture_seule: bool) {
+        self.lecture_seule = lecture_seule;
+    }
+
+    // Examine chaque écriture pour envoyer les notifications de ses règles. Le notifieur doit avoir
+    // observé la banque chargée, pour ne pas alerter sur ce qui l'a déjà été.
+    pub fn definir_notifieur(&mut self, notifieur: Notifieur) {
+        self.notifieur = Some(notifieur);
+    }
+
+    pub fn est_lecture_seule(&self) -> bool {
+        self.lecture_seule
+    }
+
+    pub fn chemin(&self) -> &Path {
+        &self.chemin
+    }
+
+    pub fn existe(&self) -> bool {
+        self.chemin.exists()
+    }
+
+    // Ouvre le journal pour le lire, sous verrou partagé (libéré à la fermeture du fichier)
+    fn ouvrir_en_lecture(&self) -> io::Result<File> {
+        let fichier = File::open(&self.chemin)?;
+        fichier.lock_shared()?;
+        Ok(fichier)
+    }
+
+    // Ouvre le journal pour le compléter, sous verrou exclusif
+    fn ouvrir_en_ecriture(&self) -> Result<File, ErreurJournal> {
+        if self.lecture_seule {
+            return Err(ErreurJournal::LectureSeule);
+        }
+        let fichier = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.chemin)?;
+        fichier.lock()?;
+        Ok(fichier)
+    }
+
+    // Charge la banque : le dernier instantané, puis les événements écrits depuis
+    pub fn charger(&mut self) -> io::Result<Banque> {
+        let mut fichier = self.ouvrir_en_lecture()?;
+        let (mut banque, position) = match fs::read_to_string(&self.instantane) {
+            Ok(contenu) => {
+                let instantane: Instantane = serde_json::from_str(&contenu).map_err(donnees_invalides)?;
+                (instantane.banque, instantane.position_journal)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (Banque::new(), 0),
+            Err(e) => return Err(e),
+        };
+        self.dernier_instantane = banque.dernier_evenement();
+        self.position = position;
+        self.rattraper(&mut fichier, &mut banque)?;
+        Ok(banque)
+    }
+
+    // Applique à la banque les événements écrits par les autres processus depuis notre dernière lecture
+    pub fn rafraichir(&mut self, banque: &mut Banque) -> io::Result<()> {
+        if !self.existe() {
+            return Ok(());
+        }
+        let mut fichier = self.ouvrir_en_lecture()?;
+        self.rattraper(&mut fichier, banque)
+    }
+
+    // Abandonne les modifications pas encore enregistrées et recharge l'état du journal,
+    // en gardant la configuration de la banque (tarifs, jeton, rétention des clés)
+    pub fn recharger(&mut self, banque: &mut Banque) -> io::Result<()> {
+        let etat = self.charger()?;
+        banque.remplacer_etat(etat);
+        Ok(())
+    }
+
+    fn rattraper(&mut self, fichier: &mut File, banque: &mut Banque) -> io::Result<()> {
+        fichier.seek(SeekFrom::Start(self.position))?;
+        for evenement in lire_evenements(&mut *fichier)? {
+            if evenement.numero > banque.dernier_evenement() {
+                banque.appliquer(&evenement);
+            }
+        }
+        self.position = fichier.metadata()?.len();
+        // Les écritures des autres processus ont été notifiées par eux
+        if let Some(notifieur) = &mut self.notifieur {
+            notifieur.observer(banque);
+        }
+        Ok(())
+    }
+
+    // Tous les événements du journal, du plus ancien au plus récent
+    pub fn evenements(&self) -> io::Result<Vec<EvenementDate>> {
+        lire_evenements(self.ouvrir_en_lecture()?)
+    }
+
+    // État de la banque tel qu'il était à l'instant donné
+    pub fn banque_au(&self, instant: DateTime<Local>) -> io::Result<Banque> {
+        let evenements = self.evenements()?;
+        Ok(Banque::reconstruire(
+            evenements.iter().take_while(|evenement| evenement.date <= instant),
+        ))
+    }
+
+    // Ajoute au journal les événements produits depuis le dernier enregistrement,
+    // et prend un instantané quand assez d'événements se sont accumulés.
+    // Refusé si un autre processus a complété le journal entre-temps.
+    pub fn enregistrer(&mut self, banque: &mut Banque) -> Result<(), ErreurJournal> {
+        let Some(premier) = banque.nouveaux_evenements().first() else {
+            return Ok(());
+        };
+        let version_attendue = premier.numero - 1;
+
+        let mut fichier = self.ouvrir_en_ecriture()?;
+        if fichier.metadata()?.len() != self.position {
+            fichier.seek(SeekFrom::Start(self.position))?;
+            let version_trouvee = lire_evenements(&mut fichier)?
+                .last()
+                .map_or(version_attendue, |evenement| evenement.numero);
+            return Err(ErreurJournal::Conflit {
+                version_attendue,
+                version_trouvee,
+            });
+        }
+        self.terminer_ecriture(&mut fichier, banque)
+    }
+
+    // Exécute une modification sous verrou exclusif, sur la banque mise à jour des écritures des autres
+    // processus : aucun conflit n'est possible. La banque ne doit pas avoir d'événements en attente.
+    pub fn modifier<T>(
+        &mut self,
+        banque: &mut Banque,
+        modification: impl FnOnce(&mut Banque) -> T,
+    ) -> Result<T, ErreurJournal> {
+        let mut fichier = self.ouvrir_en_ecriture()?;
+        self.rattraper(&mut fichier, banque)?;
+        let resultat = modification(banque);
+        self.terminer_ecriture(&mut fichier, banque)?;
+        Ok(resultat)
+    }
+
+    fn terminer_ecriture(&mut self, fichier: &mut File, banque: &mut Banque) -> Result<(), ErreurJournal> {
+        if banque.nouveaux_evenements().is_empty() {
+            return Ok(());
+        }
+        self.position = ecrire_evenements(fichier, banque.nouveaux_evenements())?;
+        if let Some(notifieur) = &mut self.notifieur {
+            notifieur.examiner(banque, banque.nouveaux_evenements());
+        }
+        banque.marquer_enregistres();
+
+        if banque.dernier_evenement() - self.dernier_instantane >= INTERVALLE_INSTANTANES {
+            self.prendre_instantane(banque, self.position)?;
+        }
+        Ok(())
+    }
+
+    // Écrit des événements à la fin du journal (par exemple ceux d'un import) et retourne
+    // la nouvelle taille du journal
+    pub fn ajouter(&self, evenements: &[EvenementDate]) -> Result<u64, ErreurJournal> {
+        let mut fichier = self.ouvrir_en_ecriture()?;
+        Ok(ecrire_evenements(&mut fichier, evenements)?)
+    }
+
+    // Écrit l'instantané dans un fichier temporaire puis le renomme, pour ne jamais laisser un
+    // instantané à moitié écrit (appelé sous le verrou exclusif du journal)
+    fn prendre_instantane(&mut self, banque: &Banque, position_journal: u64) -> io::Result<()> {
+        let instantane = Instantane {
+            position_journal,
+            banque: banque.clone(),
+        };
+        let contenu = serde_json::to_string(&instantane).map_err(io::Error::other)?;
+        let temporaire = self.instantane.with_extension("instantane.tmp");
+        fs::write(&temporaire, contenu)?;
+        fs::rename(&temporaire, &self.instantane)?;
+        self.dernier_instantane = banque.dernier_evenement();
+        Ok(())
+    }
+}
+
+fn ecrire_evenements(fichier: &mut File, evenements: &[EvenementDate]) -> io::Result<u64> {
+    let mut lignes = String::new();
+    for evenement in evenements {
+        lignes.push_str(&serde_json::to_string(evenement).map_err(io::Error::other)?);
+        lignes.push('\n');
+    }
+    fichier.write_all(lignes.as_bytes())?;
+    fichier.sync_data()?;
+    Ok(fichier.metadata()?.len())
+}
+
+fn lire_evenements(fichier: impl Read) -> io::Result<Vec<EvenementDate>> {
+    let mut evenements = Vec::new();
+    for ligne in BufReader::new(fichier).lines() {
+        let ligne = ligne?;
+        if ligne.trim().is_empty() {
+            continue;
+        }
+        evenements.push(serde_json::from_str(&ligne).map_err(donnees_invalides)?);
+    }
+    Ok(evenements)
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// Catalogue des messages : un fichier par langue dans locales/, une ligne « clé = texte » par message.
+// Les paramètres s'écrivent {nom} dans le texte, \n y représente un retour à la ligne. Le texte commence
+// après l'espace qui suit le =, les espaces suivantes sont conservées (indentation).
+const FRANCAIS: &str = include_str!("../locales/fr.txt");
+const ANGLAIS: &str = include_str!("../locales/en.txt");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Langue {
+    Francais,
+    Anglais,
+}
+
+// Langue de l'interface, choisie au démarrage (français par défaut)
+static LANGUE: AtomicU8 = AtomicU8::new(0);
+
+pub fn definir_langue(langue: Langue) {
+    LANGUE.store(langue as u8, Ordering::Relaxed);
+}
+
+pub fn langue() -> Langue {
+    match LANGUE.load(Ordering::Relaxed) {
+        1 => Langue::Anglais,
+        _ => Langue::Francais,
+    }
+}
+
+impl Langue {
+    // Reconnaît « fr », « en » ou une locale comme « en_US.UTF-8 »
+    pub fn depuis_code(code: &str) -> Option<Langue> {
+        let code = code.trim().to_lowercase();
+        if code.starts_with("fr") {
+            Some(Langue::Francais)
+        } else if code.starts_with("en") {
+            Some(Langue::Anglais)
+        } else {
+            None
+        }
+    }
+
+    // Langue des variables d'environnement LC_ALL, LC_MESSAGES puis LANG, français si aucune n'est reconnue
+    pub fn depuis_environnement() -> Langue {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|variable| std::env::var(variable).ok())
+            .find(|valeur| !valeur.is_empty())
+            .and_then(|valeur| Langue::depuis_code(&valeur))
+            .unwrap_or(Langue::Francais)
+    }
+
+    pub fn catalogue(self) -> &'static HashMap<&'static str, String> {
+        static CATALOGUES: [OnceLock<HashMap<&'static str, String>>; 2] = [OnceLock::new(), OnceLock::new()];
+        let source = match self {
+            Langue::Francais => FRANCAIS,
+            Langue::Anglais => ANGLAIS,
+        };
+        CATALOGUES[self as usize].get_or_init(|| lire_catalogue(source))
+    }
+
+    // Texte d'un message, celui du catalogue français s'il manque dans cette langue
+    pub fn message(self, cle: &'static str) -> &'static str {
+        self.catalogue()
+            .get(cle)
+            .or_else(|| Langue::Francais.catalogue().get(cle))
+            .map_or(cle, String::as_str)
+    }
+
+    // Texte d'un message dont les paramètres {nom} sont remplacés par leur valeur
+    pub fn traduire(self, cle: &'static str, parametres: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut texte = String::new();
+        let mut reste = self.message(cle);
+        while let Some(debut) = reste.find('{') {
+            texte.push_str(&reste[..debut]);
+            let parametre = reste[debut + 1..]
+                .find('}')
+                .and_then(|fin| parametres.iter().find(|(nom, _)| *nom == &reste[debut + 1..debut + 1 + fin]));
+            match parametre {
+                Some((nom, valeur)) => {
+                    texte.push_str(&valeur.to_string());
+                    reste = &reste[debut + nom.len() + 2..];
+                }
+                None => {
+                    texte.push('{');
+                    reste = &reste[debut + 1..];
+                }
+            }
+        }
+        texte.push_str(reste);
+        texte
+    }
+
+    // Montant au format de la langue : « 1 000,50 € » ou « €1,000.50 »
+    pub fn formater_montant(self, montant: f64) -> String {
+        let centimes = (montant.abs() * 100.0).round() as u64;
+        let signe = if montant < 0.0 && centimes > 0 { "-" } else { "" };
+        let (milliers, decimale) = match self {
+            Langue::Francais => (' ', ','),
+            Langue::Anglais => (',', '.'),
+        };
+        let chiffres = (centimes / 100).to_string();
+        let mut entiers = String::new();
+        for (index, chiffre) in chiffres.chars().enumerate() {
+            if index > 0 && (chiffres.len() - index).is_multiple_of(3) {
+                entiers.push(milliers);
+            }
+            entiers.push(chiffre);
+        }
+        match self {
+            Langue::Francais => format!("{}{}{}{:02} €", signe, entiers, decimale, centimes % 100),
+            Langue::Anglais => format!("{}€{}{}{:02}", signe, entiers, decimale, centimes % 100),
+        }
+    }
+
+    // Lit un montant saisi avec l'un ou l'autre séparateur décimal (voir saisie::lire_montant). Une virgule
+    // seule suivie de trois chiffres sépare les milliers en anglais.
+    pub fn lire_montant(self, texte: &str) -> Option<f64> {
+        saisie::lire_montant(texte, self == Langue::Anglais)
+    }
+}
+
+// Montant au format de la langue de l'interface
+pub fn montant(valeur: f64) -> String {
+    langue().formater_montant(valeur)
+}
+
+// Montant précédé de son signe, pour les historiques : « +50,00 € », « -20,00 € »
+pub fn montant_signe(valeur: f64) -> String {
+    let texte = montant(valeur);
+    if texte.starts_with('-') { texte } else { format!("+{}", texte) }
+}
+
+// Montant saisi dans la langue de l'interface
+pub fn lire_montant(texte: &str) -> Option<f64> {
+    langue().lire_montant(texte)
+}
+
+// Messages des saisies (réponse invalide, oui/non, erreurs) dans la langue de l'interface. {min} et {max} sont
+// laissés tels quels : la console les remplace par les bornes attendues.
+pub fn messages_saisie() -> saisie::Messages {
+    saisie::Messages {
+        nombre_invalide: crate::tr!("commun.nombre_hors_limites").to_string(),
+        montant_invalide: crate::tr!("commun.montant_invalide").to_string(),
+        oui_non_invalide: crate::tr!("commun.oui_non_invalide").to_string(),
+        oui: crate::tr!("commun.oui").to_string(),
+        non: crate::tr!("commun.non").to_string(),
+        virgule_des_milliers: langue() == Langue::Anglais,
+        fin_de_saisie: crate::tr!("commun.fin_de_saisie").to_string(),
+        trop_d_essais: crate::tr!("commun.trop_d_essais").to_string(),
+    }
+}
+
+fn lire_catalogue(source: &'static str) -> HashMap<&'static str, String> {
+    source
+        .lines()
+        .map(str::trim_end)
+        .filter(|ligne| !ligne.trim().is_empty() && !ligne.starts_with('#'))
+        .filter_map(|ligne| ligne.split_once('='))
+        // Seule l'espace qui suit le = est retirée : le texte peut commencer par une indentation
+        .map(|(cle, texte)| (cle.trim(), texte.strip_prefix(' ').unwrap_or(texte).replace("\\n", "\n")))
+        .collect()
+}
+
+// Message du catalogue dans la langue de l'interface : tr!("cle") ou tr!("cle", nom = valeur, ...)
+#[macro_export]
+macro_rules! tr {
+    ($cle:literal) => {
+        $crate::langue::langue().message($cle)
+    };
+    ($cle:literal, $($nom:ident = $valeur:expr),+ $(,)?) => {
+        $crate::langue::langue().traduire($cle, &[$((stringify!($nom), &$valeur as &dyn std::fmt::Display)),+])
+    };
+}
@@ -0,0 +1,10 @@
+// Modèle bancaire commun au tp1 et au tp2 : comptes, banque, journal des événements, frais, clôture,
+// notifications et traductions
+pub mod banque;
+pub mod cloture;
+pub mod compte;
+pub mod evenement;
+pub mod frais;
+pub mod journal;
+pub mod langue;
+pub mod notification;
@@ -0,0 +1,335 @@
+use crate::banque::Banque;
+use crate::compte::{CompteBancaire, Transaction};
+use crate::evenement::{Evenement, EvenementDate};
+use crate::{langue, tr};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Délai de connexion et d'écriture vers un serveur de journalisation
+const DELAI_TCP: Duration = Duration::from_secs(2);
+
+// Règle de déclenchement d'une notification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "regle", rename_all = "snake_case")]
+pub enum Regle {
+    // Le solde disponible d'un compte (de tous les comptes si aucun n'est précisé) passe sous le seuil
+    SoldeSous {
+        seuil: f64,
+        #[serde(default)]
+        compte: Option<u32>,
+    },
+    // Transfert d'au moins ce montant
+    GrosTransfert { montant: f64 },
+    // Opération programmée en échec : un encaissement en attente (chèque, virement) rejeté
+    OperationEchouee,
+}
+
+// Notification prête à être envoyée. Deux notifications de même clé ne sont envoyées qu'une fois.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub cle: String,
+    pub date: DateTime<Local>,
+    pub message: String,
+}
+
+// Destination des notifications : la console, un fichier, un serveur de journalisation...
+// D'autres destinations peuvent être branchées avec Notifieur::ajouter_destination.
+pub trait Destination: Send {
+    // Nom affiché quand un envoi échoue
+    fn decrire(&self) -> String;
+    fn envoyer(&mut self, notification: &Notification) -> io::Result<()>;
+}
+
+// Affiche les notifications sur la sortie standard
+pub struct DestinationConsole;
+
+impl Destination for DestinationConsole {
+    fn decrire(&self) -> String {
+        "console".to_string()
+    }
+
+    fn envoyer(&mut self, notification: &Notification) -> io::Result<()> {
+        println!("🔔 {}", notification.message);
+        Ok(())
+    }
+}
+
+// Ajoute les notifications à la fin d'un fichier, une par ligne précédée de sa date
+pub struct DestinationFichier {
+    chemin: PathBuf,
+}
+
+impl DestinationFichier {
+    pub fn new(chemin: &Path) -> DestinationFichier {
+        DestinationFichier {
+            chemin: chemin.to_path_buf(),
+        }
+    }
+}
+
+impl Destination for DestinationFichier {
+    fn decrire(&self) -> String {
+        self.chemin.display().to_string()
+    }
+
+    fn envoyer(&mut self, notification: &Notification) -> io::Result<()> {
+        let mut fichier = OpenOptions::new().create(true).append(true).open(&self.chemin)?;
+        writeln!(fichier, "[{}] {}", notification.date.to_rfc3339(), notification.message)
+    }
+}
+
+// Envoie les notifications à un serveur de journalisation (tp4) : une ligne par message, terminée
+// par un retour à la ligne. La connexion est gardée ouverte et rétablie si elle a été coupée.
+pub struct DestinationTcp {
+    adresse: String,
+    connexion: Option<TcpStream>,
+}
+
+impl DestinationTcp {
+    pub fn new(adresse: &str) -> DestinationTcp {
+        DestinationTcp {
+            adresse: adresse.to_string(),
+            connexion: None,
+        }
+    }
+
+    fn connecter(&self) -> io::Result<TcpStream> {
+        let mut derniere_erreur = io::Error::new(io::ErrorKind::NotFound, self.adresse.clone());
+        for adresse in self.adresse.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&adresse, DELAI_TCP) {
+                Ok(connexion) => {
+                    connexion.set_write_timeout(Some(DELAI_TCP))?;
+                    return Ok(connexion);
+                }
+                Err(e) => derniere_erreur = e,
+            }
+        }
+        Err(derniere_erreur)
+    }
+}
+
+impl Destination for DestinationTcp {
+    fn decrire(&self) -> String {
+        format!("tcp://{}", self.adresse)
+    }
+
+    fn envoyer(&mut self, notification: &Notification) -> io::Result<()> {
+        // Le protocole est ligne par ligne : le message ne doit pas contenir de retour à la ligne
+        let ligne = format!("tp2: {}\n", notification.message.replace('\n', " "));
+        if let Some(connexion) = &mut self.connexion
+            && connexion.write_all(ligne.as_bytes()).is_ok()
+        {
+            return Ok(());
+        }
+        // Première notification, ou serveur redémarré depuis la précédente
+        self.connexion = None;
+        let mut connexion = self.connecter()?;
+        connexion.write_all(ligne.as_bytes())?;
+        self.connexion = Some(connexion);
+        Ok(())
+    }
+}
+
+// Destination telle qu'elle est décrite dans le fichier de configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "destination", rename_all = "snake_case")]
+pub enum ConfigurationDestination {
+    Console,
+    Fichier { chemin: PathBuf },
+    Tcp { adresse: String },
+}
+
+// Règles et destinations des notifications, chargées depuis un fichier JSON (option --notifications)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Configuration {
+    #[serde(default)]
+    pub regles: Vec<Regle>,
+    #[serde(default)]
+    pub destinations: Vec<ConfigurationDestination>,
+}
+
+impl Configuration {
+    pub fn charger(chemin: &Path) -> io::Result<Configuration> {
+        let contenu = fs::read_to_string(chemin)?;
+        serde_json::from_str(&contenu).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+// Examine les événements enregistrés dans le journal et envoie les notifications de leurs règles
+pub struct Notifieur {
+    regles: Vec<Regle>,
+    destinations: Vec<Box<dyn Destination>>,
+    // Clés des notifications déjà envoyées. Celle d'un compte sous un seuil est retirée quand le
+    // solde repasse au-dessus : le compte peut alors déclencher une nouvelle alerte.
+    envoyees: HashSet<String>,
+}
+
+impl Notifieur {
+    pub fn new(regles: Vec<Regle>) -> Notifieur {
+        Notifieur {
+            regles,
+            destinations: Vec::new(),
+            envoyees: HashSet::new(),
+        }
+    }
+
+    pub fn depuis_configuration(configuration: Configuration) -> Notifieur {
+        let mut notifieur = Notifieur::new(configuration.regles);
+        for destination in configuration.destinations {
+            notifieur.ajouter_destination(match destination {
+                ConfigurationDestination::Console => Box::new(DestinationConsole),
+                ConfigurationDestination::Fichier { chemin } => Box::new(DestinationFichier::new(&chemin)),
+                ConfigurationDestination::Tcp { adresse } => Box::new(DestinationTcp::new(&adresse)),
+            });
+        }
+        notifieur
+    }
+
+    pub fn ajouter_destination(&mut self, destination: Box<dyn Destination>) {
+        self.destinations.push(destination);
+    }
+
+    // Prend connaissance de l'état de la banque sans rien envoyer : au chargement, ou après les
+    // écritures d'un autre processus (qui a envoyé ses propres notifications)
+    pub fn observer(&mut self, banque: &Banque) {
+        for notification in self.suivre_soldes(banque, None) {
+            self.envoyees.insert(notification.cle);
+        }
+    }
+
+    // Envoie les notifications déclenchées par des événements qui viennent d'être enregistrés
+    // et retourne celles qui n'avaient pas déjà été envoyées
+    pub fn examiner(&mut self, banque: &Banque, evenements: &[EvenementDate]) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+        for evenement in evenements {
+            for regle in &self.regles {
+                notifications.extend(notifier_evenement(regle, banque, evenement));
+            }
+        }
+        let concernes: HashSet<u32> = evenements
+            .iter()
+            .flat_map(|evenement| comptes_concernes(banque, evenement))
+            .collect();
+        notifications.extend(self.suivre_soldes(banque, Some(&concernes)));
+
+        notifications.retain(|notification| self.envoyees.insert(notification.cle.clone()));
+        for notification in &notifications {
+            for destination in &mut self.destinations {
+                if let Err(e) = destination.envoyer(notification) {
+                    eprintln!("{}", tr!("notification.envoi_impossible", destination = destination.decrire(), erreur = e));
+                }
+            }
+        }
+        notifications
+    }
+
+    // Réarme les comptes revenus au-dessus de leur seuil et retourne les alertes des comptes sous leur
+    // seuil, parmi ceux concernés par les derniers événements (tous si aucun n'est précisé)
+    fn suivre_soldes(&mut self, banque: &Banque, concernes: Option<&HashSet<u32>>) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+        for regle in &self.regles {
+            let Regle::SoldeSous { seuil, compte } = regle else {
+                continue;
+            };
+            let comptes = banque.comptes.iter().filter(|c| compte.is_none_or(|id| c.obtenir_id() == id));
+            for compte in comptes {
+                let cle = format!("solde:{}:{}", compte.obtenir_id(), seuil);
+                if compte.obtenir_solde() >= *seuil {
+                    self.envoyees.remove(&cle);
+                } else if concernes.is_none_or(|concernes| concernes.contains(&compte.obtenir_id())) {
+                    let message = tr!(
+                        "notification.solde_sous",
+                        nom = compte.obtenir_nom(),
+                        id = compte.obtenir_id(),
+                        seuil = langue::montant(*seuil),
+                        solde = langue::montant(compte.obtenir_solde())
+                    );
+                    notifications.push(Notification {
+                        cle,
+                        date: Local::now(),
+                        message,
+                    });
+                }
+            }
+        }
+        notifications
+    }
+}
+
+fn nom_du_compte(banque: &Banque, id: u32) -> String {
+    banque
+        .compte(id)
+        .map_or_else(|_| format!("n°{}", id), |compte| compte.obtenir_nom().to_string())
+}
+
+// Encaissement rejeté et son compte, retrouvés par son numéro : la première ligne de l'historique qui le porte
+// est le dépôt (la contre-passation vient après)
+fn rejet(banque: &Banque, numero: u32) -> Option<(&CompteBancaire, &Transaction)> {
+    banque.comptes.iter().find_map(|compte| {
+        compte
+            .historique()
+            .iter()
+            .find(|transaction| transaction.encaissement == Some(numero))
+            .map(|transaction| (compte, transaction))
+    })
+}
+
+// Comptes dont le solde disponible a pu changer avec cet événement
+fn comptes_concernes(banque: &Banque, evenement: &EvenementDate) -> Vec<u32> {
+    match &evenement.evenement {
+        Evenement::CompteOuvert { id, .. }
+        | Evenement::Depot { id, .. }
+        | Evenement::Retrait { id, .. }
+        | Evenement::FraisPreleves { id, .. }
+        | Evenement::InteretsVerses { id, .. } => vec![*id],
+        Evenement::Transfert { source, destination, .. } => vec![*source, *destination],
+        Evenement::EncaissementRejete { numero, .. } => {
+            rejet(banque, *numero).map(|(compte, _)| compte.obtenir_id()).into_iter().collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+// Notification déclenchée par un événement seul (les seuils de solde dépendent de l'état, voir suivre_soldes)
+fn notifier_evenement(regle: &Regle, banque: &Banque, evenement: &EvenementDate) -> Option<Notification> {
+    let (cle, message) = match (regle, &evenement.evenement) {
+        (Regle::GrosTransfert { montant: seuil }, Evenement::Transfert { source, destination, montant })
+            if montant >= seuil =>
+        {
+            let message = tr!(
+                "notification.gros_transfert",
+                montant = langue::montant(*montant),
+                source = nom_du_compte(banque, *source),
+                destination = nom_du_compte(banque, *destination)
+            );
+            (format!("transfert:{}", evenement.numero), message)
+        }
+        (Regle::OperationEchouee, Evenement::EncaissementRejete { numero, frais }) => {
+            // Le compte peut avoir été fermé depuis : seul le numéro de l'encaissement est alors connu
+            let message = match rejet(banque, *numero) {
+                Some((compte, depot)) => tr!(
+                    "notification.operation_echouee",
+                    numero = numero,
+                    libelle = depot.libelle,
+                    montant = langue::montant(depot.montant),
+                    nom = compte.obtenir_nom(),
+                    frais = langue::montant(*frais)
+                ),
+                None => tr!("notification.encaissement_rejete", numero = numero, frais = langue::montant(*frais)),
+            };
+            (format!("rejet:{}", numero), message)
+        }
+        _ => return None,
+    };
+    Some(Notification {
+        cle,
+        date: evenement.date,
+        message,
+    })
+}
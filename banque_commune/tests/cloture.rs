@@ -0,0 +1,146 @@
+// Clôture annuelle : intérêts créditeurs, récapitulatif fiscal, verrouillage et réouverture
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use banque_commune::banque::{Banque, ErreurBanque};
+use banque_commune::compte::TypeCompte;
+use banque_commune::evenement::{Evenement, EvenementDate};
+
+fn le(jour: u32, mois: u32, annee: i32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(annee, mois, jour, 12, 0, 0).unwrap()
+}
+
+// 2025 : Alice (courant), Bob (courant, frais de février et un chèque rejeté) et Dan (épargne à 2%)
+// ouvrent leurs comptes le 1er janvier ; un chèque d'Alice déposé en décembre est encore en attente.
+// Les frais de décembre sont prélevés en janvier 2026.
+fn banque_de_2025(frais_de_decembre: bool) -> Banque {
+    let mut evenements = vec![
+        (le(1, 1, 2025), Evenement::CompteOuvert { id: 1, nom: "Alice".to_string(), solde: 300.0 }),
+        (le(1, 1, 2025), Evenement::CompteOuvert { id: 2, nom: "Bob".to_string(), solde: 500.0 }),
+        (le(1, 1, 2025), Evenement::CompteOuvert { id: 3, nom: "Dan".to_string(), solde: 1000.0 }),
+        (le(1, 1, 2025), Evenement::TypeCompteChange { id: 3, type_compte: TypeCompte::Epargne }),
+        (
+            le(1, 3, 2025),
+            Evenement::FraisPreleves {
+                id: 2,
+                libelle: "Frais de tenue de compte 02/2025".to_string(),
+                montant: 2.0,
+                mois: NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            },
+        ),
+        (
+            le(10, 6, 2025),
+            Evenement::EncaissementDepose {
+                id: 2,
+                numero: 1,
+                libelle: "Chèque".to_string(),
+                montant: 100.0,
+                date_valeur: NaiveDate::from_ymd_opt(2025, 6, 15).unwrap(),
+            },
+        ),
+        (le(12, 6, 2025), Evenement::EncaissementRejete { numero: 1, frais: 20.0 }),
+        (
+            le(20, 12, 2025),
+            Evenement::EncaissementDepose {
+                id: 1,
+                numero: 2,
+                libelle: "Chèque".to_string(),
+                montant: 50.0,
+                date_valeur: NaiveDate::from_ymd_opt(2099, 1, 1).unwrap(),
+            },
+        ),
+    ];
+    if frais_de_decembre {
+        evenements.push((
+            le(2, 1, 2026),
+            Evenement::MoisFacture { mois: NaiveDate::from_ymd_opt(2025, 12, 1).unwrap() },
+        ));
+    }
+
+    let journal: Vec<EvenementDate> = evenements
+        .into_iter()
+        .enumerate()
+        .map(|(index, (date, evenement))| EvenementDate { numero: index as u64 + 1, date, evenement })
+        .collect();
+    Banque::reconstruire(&journal)
+}
+
+#[test]
+fn la_cloture_verse_les_interets_et_etablit_le_recapitulatif_fiscal() {
+    let mut banque = banque_de_2025(true);
+    let rapport = banque.cloturer_exercice(2025).unwrap();
+
+    let noms: Vec<&str> = rapport.titulaires.iter().map(|titulaire| titulaire.nom.as_str()).collect();
+    assert_eq!(noms, ["Alice", "Bob", "Dan"]);
+
+    // 1000€ toute l'année à 2% ; les comptes courants ne sont pas rémunérés
+    let dan = &rapport.titulaires[2];
+    assert_eq!(dan.interets(), 20.0);
+    assert_eq!(dan.comptes[0].solde_fin, 1000.0);
+    assert_eq!(banque.compte(3).unwrap().obtenir_solde(), 1020.0);
+    assert_eq!(rapport.titulaires[0].interets(), 0.0);
+
+    // Frais de tenue de compte et frais de rejet
+    let bob = &rapport.titulaires[1];
+    assert_eq!(bob.frais(), 22.0);
+    assert_eq!(bob.comptes[0].solde_fin, 478.0);
+
+    assert!(rapport.en_texte().contains("Total: intérêts perçus 20,00 €, frais payés 0,00 €"));
+    let csv = rapport.en_csv();
+    assert!(csv.starts_with("annee;titulaire;compte;type;solde_31_12;interets;frais\n"));
+    assert!(csv.contains("2025;Bob;2;Compte courant;478.00;0.00;22.00\n"));
+    assert!(csv.contains("2025;Dan;3;Compte épargne;1000.00;20.00;0.00\n"));
+}
+
+#[test]
+fn seule_une_annee_terminee_et_facturee_peut_etre_cloturee_une_fois() {
+    let mut banque = banque_de_2025(false);
+    let decembre = NaiveDate::from_ymd_opt(2025, 12, 1).unwrap();
+    assert_eq!(banque.cloturer_exercice(2025), Err(ErreurBanque::FraisNonPreleves(decembre)));
+
+    let mut banque = banque_de_2025(true);
+    let cette_annee = chrono::Datelike::year(&Local::now());
+    assert_eq!(
+        banque.cloturer_exercice(cette_annee),
+        Err(ErreurBanque::ExerciceNonTermine(cette_annee))
+    );
+    banque.cloturer_exercice(2025).unwrap();
+    assert_eq!(banque.cloturer_exercice(2025), Err(ErreurBanque::ExerciceDejaClos(2025)));
+}
+
+#[test]
+fn les_transactions_d_un_exercice_clos_sont_verrouillees() {
+    let mut banque = banque_de_2025(true);
+    banque.cloturer_exercice(2025).unwrap();
+
+    assert_eq!(banque.rejeter_encaissement(2), Err(ErreurBanque::ExerciceClos(2025)));
+    let alice = banque.compte(1).unwrap();
+    assert!(
+        alice
+            .historique()
+            .iter()
+            .all(|transaction| banque.est_verrouillee(alice, transaction))
+    );
+    // Les intérêts versés en 2026 ne sont pas verrouillés
+    let dan = banque.compte(3).unwrap();
+    assert!(!banque.est_verrouillee(dan, dan.historique().last().unwrap()));
+}
+
+#[test]
+fn seul_un_administrateur_peut_rouvrir_un_exercice() {
+    let mut banque = banque_de_2025(true);
+    banque.cloturer_exercice(2025).unwrap();
+
+    // Sans jeton configuré, personne ne peut rouvrir
+    assert_eq!(banque.rouvrir_exercice(2025, ""), Err(ErreurBanque::AccesRefuse));
+    banque.definir_jeton_admin(Some("secret".to_string()));
+    assert_eq!(banque.rouvrir_exercice(2025, "devine"), Err(ErreurBanque::AccesRefuse));
+    assert_eq!(banque.rouvrir_exercice(2024, "secret"), Err(ErreurBanque::ExerciceNonClos(2024)));
+
+    banque.rouvrir_exercice(2025, "secret").unwrap();
+    assert!(!banque.exercice_clos(2025));
+    banque.rejeter_encaissement(2).unwrap();
+
+    // Une nouvelle clôture ne verse pas les intérêts une seconde fois
+    let rapport = banque.cloturer_exercice(2025).unwrap();
+    assert_eq!(rapport.titulaires[2].interets(), 20.0);
+    assert_eq!(banque.compte(3).unwrap().obtenir_solde(), 1020.0);
+}
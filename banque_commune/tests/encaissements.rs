@@ -0,0 +1,74 @@
+// Encaissements avec date de valeur : solde disponible, solde comptable, compensation et rejet
+use chrono::NaiveDate;
+use banque_commune::banque::{Banque, ErreurBanque, FRAIS_REJET};
+
+fn date(jour: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, jour).unwrap()
+}
+
+#[test]
+fn un_cheque_n_est_pas_disponible_avant_sa_date_de_valeur() {
+    let mut banque = Banque::demo();
+    banque.deposer_en_attente(2, 300.0, date(10), "Chèque").unwrap();
+
+    let compte = banque.compte(2).unwrap();
+    assert_eq!(compte.obtenir_solde(), 500.0);
+    assert_eq!(compte.obtenir_solde_comptable(), 800.0);
+
+    // Les retraits n'utilisent que les fonds disponibles
+    assert_eq!(banque.retirer(2, 600.0), Err(ErreurBanque::SoldeInsuffisant));
+    assert!(banque.compenser_encaissements(date(9)).is_empty());
+}
+
+#[test]
+fn le_cheque_est_compense_a_sa_date_de_valeur() {
+    let mut banque = Banque::demo();
+    banque.deposer_en_attente(2, 300.0, date(10), "Chèque").unwrap();
+    assert!(!banque.encaissements_a_compenser(date(9)));
+    assert!(banque.encaissements_a_compenser(date(10)));
+
+    let compenses = banque.compenser_encaissements(date(10));
+    assert_eq!(compenses.len(), 1);
+    assert!(!banque.encaissements_a_compenser(date(10)));
+
+    let compte = banque.compte(2).unwrap();
+    assert_eq!(compte.obtenir_solde(), 800.0);
+    assert_eq!(compte.obtenir_solde_comptable(), 800.0);
+    assert!(compte.en_attente().is_empty());
+    assert_eq!(banque.retirer(2, 600.0), Ok(200.0));
+}
+
+#[test]
+fn un_cheque_rejete_est_contre_passe_avec_des_frais() {
+    let mut banque = Banque::demo();
+    let numero = banque.deposer_en_attente(2, 300.0, date(10), "Chèque").unwrap();
+
+    let rejete = banque.rejeter_encaissement(numero).unwrap();
+    assert_eq!(rejete.montant, 300.0);
+
+    let compte = banque.compte(2).unwrap();
+    assert_eq!(compte.obtenir_solde(), 500.0 - FRAIS_REJET);
+    assert_eq!(compte.obtenir_solde_comptable(), 500.0 - FRAIS_REJET);
+    // L'historique reste cohérent avec le solde comptable
+    let somme: f64 = compte.historique().iter().map(|transaction| transaction.montant).sum();
+    assert_eq!(somme, compte.obtenir_solde_comptable());
+
+    // Un encaissement déjà rejeté (ou compensé) ne peut plus l'être
+    assert_eq!(
+        banque.rejeter_encaissement(numero),
+        Err(ErreurBanque::EncaissementIntrouvable(numero))
+    );
+}
+
+#[test]
+fn montant_invalide_refuse() {
+    let mut banque = Banque::demo();
+    assert_eq!(
+        banque.deposer_en_attente(1, -5.0, date(10), "Chèque"),
+        Err(ErreurBanque::MontantInvalide)
+    );
+    assert_eq!(
+        banque.deposer_en_attente(42, 5.0, date(10), "Chèque"),
+        Err(ErreurBanque::CompteIntrouvable(42))
+    );
+}
@@ -0,0 +1,161 @@
+// Frais mensuels : tenue de compte, opérations au-delà du quota, intérêts débiteurs et exonérations
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use banque_commune::banque::{Banque, ErreurBanque};
+use banque_commune::compte::TypeCompte;
+use banque_commune::evenement::{Evenement, EvenementDate};
+use banque_commune::frais::Grille;
+
+fn le(jour: u32, mois: u32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, mois, jour, 12, 0, 0).unwrap()
+}
+
+fn fevrier() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 2, 1).unwrap()
+}
+
+// Février 2025 : Alice (VIP) et Bob font 7 retraits, Claire est à découvert de 100€ tout le mois,
+// Dan a un compte épargne avec 3 retraits
+fn banque_de_fevrier() -> Banque {
+    banque_de_fevrier_avec(Vec::new())
+}
+
+// La même banque, avec des événements de plus en février
+fn banque_de_fevrier_avec(autres: Vec<Evenement>) -> Banque {
+    let mut evenements = vec![
+        Evenement::CompteOuvert { id: 1, nom: "Alice".to_string(), solde: 2000.0 },
+        Evenement::CompteOuvert { id: 2, nom: "Bob".to_string(), solde: 500.0 },
+        Evenement::CompteOuvert { id: 3, nom: "Claire".to_string(), solde: 0.0 },
+        Evenement::CompteOuvert { id: 4, nom: "Dan".to_string(), solde: 300.0 },
+        Evenement::TypeCompteChange { id: 4, type_compte: TypeCompte::Epargne },
+    ]
+    .into_iter()
+    .map(|evenement| (le(31, 1), evenement))
+    .collect::<Vec<_>>();
+    evenements.push((
+        le(31, 1),
+        Evenement::FraisPreleves {
+            id: 3,
+            libelle: "Frais de rejet".to_string(),
+            montant: 100.0,
+            mois: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        },
+    ));
+    for jour in 1..=7 {
+        evenements.push((le(jour, 2), Evenement::Retrait { id: 1, montant: 10.0 }));
+        evenements.push((le(jour, 2), Evenement::Retrait { id: 2, montant: 10.0 }));
+    }
+    for jour in 1..=3 {
+        evenements.push((le(jour, 2), Evenement::Retrait { id: 4, montant: 10.0 }));
+    }
+    evenements.extend(autres.into_iter().map(|evenement| (le(10, 2), evenement)));
+    // Un retrait de mars ne compte pas pour février, et un compte ouvert en mars ne paie rien
+    evenements.push((le(1, 3), Evenement::Retrait { id: 2, montant: 10.0 }));
+    evenements.push((le(1, 3), Evenement::CompteOuvert { id: 5, nom: "Eve".to_string(), solde: 0.0 }));
+
+    let journal: Vec<EvenementDate> = evenements
+        .into_iter()
+        .enumerate()
+        .map(|(index, (date, evenement))| EvenementDate { numero: index as u64 + 1, date, evenement })
+        .collect();
+    Banque::reconstruire(&journal)
+}
+
+fn frais_de(banque: &Banque, id: u32) -> Vec<(String, f64)> {
+    banque
+        .recapitulatif_frais(fevrier())
+        .into_iter()
+        .filter(|ligne| ligne.id == id)
+        .map(|ligne| (ligne.libelle, ligne.montant))
+        .collect()
+}
+
+#[test]
+fn les_frais_du_mois_sont_preleves_en_lignes_separees() {
+    let mut banque = banque_de_fevrier();
+    let lignes = banque.prelever_frais(fevrier()).unwrap();
+    assert_eq!(banque.recapitulatif_frais(fevrier()), lignes);
+
+    // Compte VIP : exonéré de frais
+    assert!(frais_de(&banque, 1).is_empty());
+    assert!(frais_de(&banque, 5).is_empty());
+
+    assert_eq!(
+        frais_de(&banque, 2),
+        vec![
+            ("Frais de tenue de compte 02/2025".to_string(), 2.0),
+            ("Frais d'opérations (2 au-delà de 5) 02/2025".to_string(), 1.0),
+        ]
+    );
+    assert_eq!(banque.compte(2).unwrap().obtenir_solde(), 500.0 - 80.0 - 3.0);
+
+    // 100€ de découvert pendant 28 jours à 8% par an
+    assert_eq!(
+        frais_de(&banque, 3),
+        vec![
+            ("Frais de tenue de compte 02/2025".to_string(), 2.0),
+            ("Intérêts débiteurs 02/2025".to_string(), 0.61),
+        ]
+    );
+
+    // Compte épargne : pas de frais de tenue, une opération au-delà des deux gratuites
+    assert_eq!(
+        frais_de(&banque, 4),
+        vec![("Frais d'opérations (1 au-delà de 2) 02/2025".to_string(), 1.0)]
+    );
+}
+
+#[test]
+fn seuls_les_retraits_et_les_transferts_emis_sont_comptes() {
+    // Ni un chèque dont le libellé ressemble à un retrait, ni un transfert reçu
+    let mut banque = banque_de_fevrier_avec(vec![
+        Evenement::EncaissementDepose {
+            id: 4,
+            numero: 1,
+            libelle: "Retrait".to_string(),
+            montant: 50.0,
+            date_valeur: NaiveDate::from_ymd_opt(2025, 2, 12).unwrap(),
+        },
+        Evenement::Transfert { source: 2, destination: 4, montant: 5.0 },
+    ]);
+    banque.prelever_frais(fevrier()).unwrap();
+    assert_eq!(
+        frais_de(&banque, 4),
+        vec![("Frais d'opérations (1 au-delà de 2) 02/2025".to_string(), 1.0)]
+    );
+
+    // Un transfert émis compte, quel que soit le nom du destinataire
+    let mut banque = banque_de_fevrier_avec(vec![
+        Evenement::CompteRenomme { id: 2, nom: "Transfert".to_string() },
+        Evenement::Transfert { source: 4, destination: 2, montant: 5.0 },
+    ]);
+    banque.prelever_frais(fevrier()).unwrap();
+    assert_eq!(
+        frais_de(&banque, 4),
+        vec![("Frais d'opérations (2 au-delà de 2) 02/2025".to_string(), 2.0)]
+    );
+}
+
+#[test]
+fn un_mois_n_est_facture_qu_une_fois_et_une_fois_termine() {
+    let mut banque = banque_de_fevrier();
+    banque.prelever_frais(fevrier()).unwrap();
+    assert_eq!(banque.prelever_frais(fevrier()), Err(ErreurBanque::MoisDejaFacture(fevrier())));
+
+    let ce_mois = Local::now().date_naive();
+    assert!(matches!(banque.prelever_frais(ce_mois), Err(ErreurBanque::MoisNonTermine(_))));
+}
+
+#[test]
+fn la_grille_tarifaire_est_configurable() {
+    let mut banque = banque_de_fevrier();
+    let mut grille = Grille::default();
+    grille.courant.frais_mensuels = 0.0;
+    grille.courant.operations_gratuites = 10;
+    grille.courant.taux_decouvert = 0.0;
+    banque.definir_tarifs(grille);
+
+    banque.prelever_frais(fevrier()).unwrap();
+    assert!(frais_de(&banque, 2).is_empty());
+    assert!(frais_de(&banque, 3).is_empty());
+    assert_eq!(frais_de(&banque, 4).len(), 1);
+}
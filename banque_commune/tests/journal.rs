@@ -0,0 +1,189 @@
+// Journal des événements : rechargement, instantanés, état de la banque à une date passée
+// et accès concurrents de plusieurs processus
+use chrono::{DateTime, Local, TimeZone};
+use banque_commune::banque::{Banque, Operation};
+use banque_commune::evenement::{Evenement, EvenementDate};
+use banque_commune::journal::{ErreurJournal, INTERVALLE_INSTANTANES, Journal};
+
+fn soldes(banque: &Banque) -> Vec<(u32, String, f64)> {
+    banque
+        .comptes
+        .iter()
+        .map(|compte| (compte.obtenir_id(), compte.obtenir_nom().to_string(), compte.obtenir_solde()))
+        .collect()
+}
+
+fn le(jour: u32, mois: u32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, mois, jour, 12, 0, 0).unwrap()
+}
+
+fn evenement(numero: u64, date: DateTime<Local>, evenement: Evenement) -> EvenementDate {
+    EvenementDate { numero, date, evenement }
+}
+
+#[test]
+fn la_banque_rechargee_est_identique() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("banque.journal");
+
+    let mut banque = Banque::demo();
+    banque.transferer(1, 2, 150.0).unwrap();
+    banque.renommer(3, "Claire Martin").unwrap();
+    banque.fermer_compte(2).unwrap();
+    let id = banque.ouvrir_compte("Dan", 200.0).unwrap();
+    banque.executer(Operation::Retrait { id, montant: 50.0 }, Some("cle-1"));
+    let mut journal = Journal::new(&chemin);
+    journal.enregistrer(&mut banque).unwrap();
+    assert!(banque.nouveaux_evenements().is_empty());
+
+    let mut rechargee = Journal::new(&chemin).charger().unwrap();
+    assert_eq!(soldes(&rechargee), soldes(&banque));
+    assert_eq!(rechargee.compte(1).unwrap().historique().len(), 2);
+
+    // Les clés d'idempotence sont rejouées elles aussi
+    let rejeu = rechargee.executer(Operation::Retrait { id, montant: 50.0 }, Some("cle-1"));
+    assert!(rejeu.rejouee);
+    assert_eq!(rechargee.compte(id).unwrap().obtenir_solde(), 150.0);
+}
+
+#[test]
+fn un_instantane_est_pris_periodiquement() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("banque.journal");
+    let mut journal = Journal::new(&chemin);
+
+    let mut banque = Banque::demo();
+    for _ in 0..INTERVALLE_INSTANTANES {
+        banque.deposer(1, 1.0).unwrap();
+    }
+    journal.enregistrer(&mut banque).unwrap();
+    assert!(dossier.path().join("banque.instantane").exists());
+
+    // Les événements écrits après l'instantané sont rejoués par-dessus
+    banque.retirer(2, 100.0).unwrap();
+    journal.enregistrer(&mut banque).unwrap();
+
+    let rechargee = Journal::new(&chemin).charger().unwrap();
+    assert_eq!(soldes(&rechargee), soldes(&banque));
+    assert_eq!(rechargee.dernier_evenement(), banque.dernier_evenement());
+}
+
+#[test]
+fn solde_d_alice_au_premier_mars() {
+    let journal = vec![
+        evenement(1, le(1, 2), Evenement::CompteOuvert { id: 1, nom: "Alice".to_string(), solde: 1000.0 }),
+        evenement(2, le(1, 2), Evenement::CompteOuvert { id: 2, nom: "Bob".to_string(), solde: 500.0 }),
+        evenement(3, le(15, 2), Evenement::Retrait { id: 1, montant: 200.0 }),
+        evenement(4, le(28, 2), Evenement::Transfert { source: 2, destination: 1, montant: 100.0 }),
+        evenement(5, le(5, 3), Evenement::Depot { id: 1, montant: 1000.0 }),
+        evenement(6, le(6, 3), Evenement::CompteFerme { id: 2 }),
+    ];
+
+    let premier_mars = Local.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+    let passee = Banque::reconstruire(journal.iter().take_while(|e| e.date <= premier_mars));
+    assert_eq!(passee.compte(1).unwrap().obtenir_solde(), 900.0);
+    assert_eq!(passee.compte(2).unwrap().obtenir_solde(), 400.0);
+
+    let actuelle = Banque::reconstruire(&journal);
+    assert_eq!(actuelle.compte(1).unwrap().obtenir_solde(), 1900.0);
+    assert!(actuelle.compte(2).is_err());
+}
+
+#[test]
+fn le_journal_repond_aux_questions_sur_le_passe() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("banque.journal");
+    let mut journal = Journal::new(&chemin);
+
+    let mut banque = Banque::demo();
+    journal.enregistrer(&mut banque).unwrap();
+    let avant_retrait = Local::now();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    banque.retirer(1, 300.0).unwrap();
+    journal.enregistrer(&mut banque).unwrap();
+
+    let passee = journal.banque_au(avant_retrait).unwrap();
+    assert_eq!(passee.compte(1).unwrap().obtenir_solde(), 1000.0);
+    assert_eq!(journal.banque_au(Local::now()).unwrap().compte(1).unwrap().obtenir_solde(), 700.0);
+}
+
+// Deux utilisateurs (deux Journal sur le même fichier) partent de la même version
+fn deux_utilisateurs(chemin: &std::path::Path) -> ((Journal, Banque), (Journal, Banque)) {
+    let mut banque = Banque::demo();
+    Journal::new(chemin).enregistrer(&mut banque).unwrap();
+    let mut premier = Journal::new(chemin);
+    let mut second = Journal::new(chemin);
+    let banque_premier = premier.charger().unwrap();
+    let banque_second = second.charger().unwrap();
+    ((premier, banque_premier), (second, banque_second))
+}
+
+#[test]
+fn une_ecriture_concurrente_est_refusee_puis_rejouee_sur_l_etat_a_jour() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("banque.journal");
+    let ((mut premier, mut banque_premier), (mut second, mut banque_second)) = deux_utilisateurs(&chemin);
+
+    banque_premier.deposer(1, 100.0).unwrap();
+    premier.enregistrer(&mut banque_premier).unwrap();
+
+    // Le second n'a pas vu le dépôt : son retrait n'écrase rien, il est refusé
+    banque_second.retirer(1, 50.0).unwrap();
+    match second.enregistrer(&mut banque_second) {
+        Err(ErreurJournal::Conflit { version_attendue, version_trouvee }) => {
+            assert_eq!((version_attendue, version_trouvee), (3, 4));
+        }
+        autre => panic!("conflit attendu, obtenu {:?}", autre),
+    }
+
+    // Après rechargement, l'opération peut être refaite sur l'état à jour
+    second.recharger(&mut banque_second).unwrap();
+    assert_eq!(banque_second.compte(1).unwrap().obtenir_solde(), 1100.0);
+    banque_second.retirer(1, 50.0).unwrap();
+    second.enregistrer(&mut banque_second).unwrap();
+
+    let relue = Journal::new(&chemin).charger().unwrap();
+    assert_eq!(relue.compte(1).unwrap().obtenir_solde(), 1050.0);
+}
+
+#[test]
+fn une_modification_sous_verrou_part_toujours_de_l_etat_le_plus_recent() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("banque.journal");
+    let ((mut premier, mut banque_premier), (mut second, mut banque_second)) = deux_utilisateurs(&chemin);
+
+    banque_premier.retirer(2, 400.0).unwrap();
+    premier.enregistrer(&mut banque_premier).unwrap();
+
+    // Le second voit le retrait du premier avant de vérifier le solde
+    let resultat = second.modifier(&mut banque_second, |banque| banque.retirer(2, 200.0)).unwrap();
+    assert!(resultat.is_err());
+    let solde = second.modifier(&mut banque_second, |banque| banque.retirer(2, 100.0)).unwrap();
+    assert_eq!(solde, Ok(0.0));
+
+    premier.rafraichir(&mut banque_premier).unwrap();
+    assert_eq!(soldes(&banque_premier), soldes(&Journal::new(&chemin).charger().unwrap()));
+}
+
+#[test]
+fn la_lecture_seule_ne_prend_jamais_le_verrou_d_ecriture() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("banque.journal");
+    Journal::new(&chemin).enregistrer(&mut Banque::demo()).unwrap();
+    let contenu = std::fs::read_to_string(&chemin).unwrap();
+
+    // Un autre lecteur tient un verrou partagé : une écriture attendrait, la lecture seule non
+    let autre_lecteur = std::fs::File::open(&chemin).unwrap();
+    autre_lecteur.lock_shared().unwrap();
+
+    let mut journal = Journal::new(&chemin);
+    journal.definir_lecture_seule(true);
+    let mut banque = journal.charger().unwrap();
+    banque.retirer(1, 10.0).unwrap();
+    assert!(matches!(journal.enregistrer(&mut banque), Err(ErreurJournal::LectureSeule)));
+    assert!(matches!(
+        journal.modifier(&mut banque, |banque| banque.deposer(1, 10.0)),
+        Err(ErreurJournal::LectureSeule)
+    ));
+    assert_eq!(std::fs::read_to_string(&chemin).unwrap(), contenu);
+}
@@ -0,0 +1,92 @@
+// Catalogues de messages et format des montants en français et en anglais
+use std::collections::BTreeSet;
+use banque_commune::langue::Langue;
+
+#[test]
+fn format_des_montants() {
+    assert_eq!(Langue::Francais.formater_montant(1000.5), "1 000,50 €");
+    assert_eq!(Langue::Anglais.formater_montant(1000.5), "€1,000.50");
+    assert_eq!(Langue::Francais.formater_montant(-1234567.891), "-1 234 567,89 €");
+    assert_eq!(Langue::Anglais.formater_montant(-1234567.891), "-€1,234,567.89");
+    assert_eq!(Langue::Francais.formater_montant(999.999), "1 000,00 €");
+    assert_eq!(Langue::Anglais.formater_montant(-0.001), "€0.00");
+}
+
+#[test]
+fn lecture_des_deux_separateurs() {
+    for langue in [Langue::Francais, Langue::Anglais] {
+        assert_eq!(langue.lire_montant("12,5"), Some(12.5));
+        assert_eq!(langue.lire_montant("12.5"), Some(12.5));
+        assert_eq!(langue.lire_montant(" 1 000,50 € "), Some(1000.5));
+        assert_eq!(langue.lire_montant("€1,000.50"), Some(1000.5));
+        assert_eq!(langue.lire_montant("1.000.000,25"), Some(1000000.25));
+        assert_eq!(langue.lire_montant("-20"), Some(-20.0));
+        assert_eq!(langue.lire_montant("douze"), None);
+        assert_eq!(langue.lire_montant(""), None);
+    }
+    // Une virgule seule suivie de trois chiffres : décimales en français, milliers en anglais
+    assert_eq!(Langue::Francais.lire_montant("1,250"), Some(1.25));
+    assert_eq!(Langue::Anglais.lire_montant("1,250"), Some(1250.0));
+}
+
+#[test]
+fn choix_de_la_langue() {
+    assert_eq!(Langue::depuis_code("en_US.UTF-8"), Some(Langue::Anglais));
+    assert_eq!(Langue::depuis_code("fr_FR.UTF-8"), Some(Langue::Francais));
+    assert_eq!(Langue::depuis_code("EN"), Some(Langue::Anglais));
+    assert_eq!(Langue::depuis_code("C.UTF-8"), None);
+}
+
+// Paramètres {nom} d'un message
+fn parametres(texte: &str) -> BTreeSet<&str> {
+    texte
+        .split('{')
+        .skip(1)
+        .filter_map(|suite| suite.split_once('}').map(|(nom, _)| nom))
+        .collect()
+}
+
+#[test]
+fn les_catalogues_ont_les_memes_messages() {
+    let francais = Langue::Francais.catalogue();
+    let anglais = Langue::Anglais.catalogue();
+
+    assert_eq!(
+        francais.keys().collect::<BTreeSet<_>>(),
+        anglais.keys().collect::<BTreeSet<_>>()
+    );
+    for (cle, texte) in francais {
+        assert_eq!(parametres(texte), parametres(&anglais[cle]), "paramètres de {}", cle);
+    }
+}
+
+// Toutes les clés utilisées dans le code (tr!("cle", ...) et dire!(console, "cle", ...)) sont au catalogue,
+// partagé par le modèle commun, le tp1 et le tp2 (qui vérifie ses propres clés)
+#[test]
+fn les_messages_utilises_existent() {
+    let francais = Langue::Francais.catalogue();
+    let dossiers = ["src", "../tp1/src"];
+    let fichiers = dossiers
+        .iter()
+        .flat_map(|dossier| std::fs::read_dir(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(dossier)).unwrap());
+    for entree in fichiers {
+        let chemin = entree.unwrap().path();
+        let source = std::fs::read_to_string(&chemin).unwrap();
+        let appels = source.match_indices("tr!(").chain(source.match_indices("dire!("));
+        // include_str!( se termine aussi par tr!( : seul un nom de macro complet compte
+        let appels = appels.filter(|(debut, _)| {
+            !source[..*debut].ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        });
+        for (debut, _) in appels {
+            let Some((_, suite)) = source[debut..].split_once('"') else {
+                continue;
+            };
+            let cle = suite.split('"').next().unwrap();
+            if cle == "cle" || cle.contains(' ') {
+                // Exemples dans les commentaires
+                continue;
+            }
+            assert!(francais.contains_key(cle), "{}: clé {} absente du catalogue", chemin.display(), cle);
+        }
+    }
+}
@@ -0,0 +1,242 @@
+// Notifications : règles de déclenchement, déduplication et destinations (fichier, serveur TCP)
+use chrono::{Local, NaiveDate};
+use std::io::{self, BufRead, BufReader};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use banque_commune::banque::Banque;
+use banque_commune::evenement::{Evenement, EvenementDate};
+use banque_commune::journal::Journal;
+use banque_commune::notification::{
+    Configuration, ConfigurationDestination, Destination, DestinationFichier, DestinationTcp, Notification, Notifieur,
+    Regle,
+};
+
+// Destination de test : garde les messages reçus
+#[derive(Clone, Default)]
+struct Collecte(Arc<Mutex<Vec<String>>>);
+
+impl Collecte {
+    fn messages(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl Destination for Collecte {
+    fn decrire(&self) -> String {
+        "collecte".to_string()
+    }
+
+    fn envoyer(&mut self, notification: &Notification) -> io::Result<()> {
+        self.0.lock().unwrap().push(notification.message.clone());
+        Ok(())
+    }
+}
+
+fn notifieur(regles: Vec<Regle>) -> (Notifieur, Collecte) {
+    let collecte = Collecte::default();
+    let mut notifieur = Notifieur::new(regles);
+    notifieur.ajouter_destination(Box::new(collecte.clone()));
+    (notifieur, collecte)
+}
+
+// Examine les événements produits depuis le dernier appel, comme le journal après une écriture
+fn examiner(notifieur: &mut Notifieur, banque: &mut Banque) -> usize {
+    let envoyees = notifieur.examiner(banque, banque.nouveaux_evenements()).len();
+    banque.marquer_enregistres();
+    envoyees
+}
+
+#[test]
+fn un_compte_passant_sous_le_seuil_n_alerte_qu_une_fois() {
+    let (mut notifieur, collecte) = notifieur(vec![Regle::SoldeSous { seuil: 100.0, compte: None }]);
+    let mut banque = Banque::demo();
+    banque.marquer_enregistres();
+    notifieur.observer(&banque);
+
+    banque.retirer(2, 450.0).unwrap();
+    assert_eq!(examiner(&mut notifieur, &mut banque), 1);
+    assert_eq!(collecte.messages(), ["Le solde de Bob Martin (n°2) est passé sous 100,00 € : 50,00 €"]);
+
+    // Toujours sous le seuil : pas de nouvelle alerte
+    banque.retirer(2, 20.0).unwrap();
+    banque.renommer(1, "Alice Martin").unwrap();
+    assert_eq!(examiner(&mut notifieur, &mut banque), 0);
+
+    // Revenu au-dessus puis repassé en dessous : nouvelle alerte
+    banque.deposer(2, 200.0).unwrap();
+    assert_eq!(examiner(&mut notifieur, &mut banque), 0);
+    banque.retirer(2, 150.0).unwrap();
+    assert_eq!(examiner(&mut notifieur, &mut banque), 1);
+    assert_eq!(collecte.messages().len(), 2);
+}
+
+#[test]
+fn un_seuil_peut_ne_viser_qu_un_compte() {
+    let (mut notifieur, collecte) = notifieur(vec![Regle::SoldeSous { seuil: 600.0, compte: Some(3) }]);
+    let mut banque = Banque::demo();
+    banque.marquer_enregistres();
+    // Bob est déjà sous 600 € mais n'est pas visé ; Claire y passe
+    notifieur.observer(&banque);
+    banque.transferer(3, 2, 200.0).unwrap();
+    assert_eq!(examiner(&mut notifieur, &mut banque), 1);
+    assert!(collecte.messages()[0].contains("Claire Durand (n°3)"));
+}
+
+#[test]
+fn les_gros_transferts_sont_signales() {
+    let (mut notifieur, collecte) = notifieur(vec![Regle::GrosTransfert { montant: 500.0 }]);
+    let mut banque = Banque::demo();
+    banque.transferer(1, 2, 499.0).unwrap();
+    banque.transferer(1, 3, 500.0).unwrap();
+    assert_eq!(examiner(&mut notifieur, &mut banque), 1);
+    assert_eq!(collecte.messages(), ["Transfert de 500,00 € de Alice Dupont vers Claire Durand"]);
+}
+
+#[test]
+fn un_encaissement_rejete_est_une_operation_en_echec() {
+    let (mut notifieur, collecte) = notifieur(vec![Regle::OperationEchouee]);
+    let mut banque = Banque::demo();
+    let date_valeur = NaiveDate::from_ymd_opt(2999, 12, 31).unwrap();
+    let numero = banque.deposer_en_attente(2, 300.0, date_valeur, "Chèque").unwrap();
+    assert_eq!(examiner(&mut notifieur, &mut banque), 0);
+
+    banque.rejeter_encaissement(numero).unwrap();
+    assert_eq!(examiner(&mut notifieur, &mut banque), 1);
+    assert_eq!(
+        collecte.messages(),
+        ["Opération en échec : encaissement n°1 (Chèque, 300,00 €) de Bob Martin rejeté, frais de 20,00 €"]
+    );
+}
+
+#[test]
+fn chaque_rejet_est_attribue_au_compte_de_son_encaissement() {
+    // Deux rejets à la même date, dont un encaissement au libellé trompeur
+    let (mut notifieur, collecte) = notifieur(vec![Regle::OperationEchouee]);
+    let date = Local::now();
+    let depot = |id, numero, libelle: &str, montant| Evenement::EncaissementDepose {
+        id,
+        numero,
+        libelle: libelle.to_string(),
+        montant,
+        date_valeur: NaiveDate::from_ymd_opt(2999, 12, 31).unwrap(),
+    };
+    let evenements: Vec<EvenementDate> = [
+        Evenement::CompteOuvert { id: 1, nom: "Alice".to_string(), solde: 100.0 },
+        Evenement::CompteOuvert { id: 2, nom: "Bob".to_string(), solde: 100.0 },
+        depot(1, 1, "Chèque", 300.0),
+        depot(2, 2, "Rejet: X", 50.0),
+        Evenement::EncaissementRejete { numero: 2, frais: 20.0 },
+        Evenement::EncaissementRejete { numero: 1, frais: 20.0 },
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(index, evenement)| EvenementDate { numero: index as u64 + 1, date, evenement })
+    .collect();
+    let banque = Banque::reconstruire(&evenements);
+
+    assert_eq!(notifieur.examiner(&banque, &evenements[4..]).len(), 2);
+    assert_eq!(
+        collecte.messages(),
+        [
+            "Opération en échec : encaissement n°2 (Rejet: X, 50,00 €) de Bob rejeté, frais de 20,00 €",
+            "Opération en échec : encaissement n°1 (Chèque, 300,00 €) de Alice rejeté, frais de 20,00 €",
+        ]
+    );
+}
+
+#[test]
+fn une_meme_alerte_de_deux_regles_n_est_envoyee_qu_une_fois() {
+    let regle = Regle::GrosTransfert { montant: 100.0 };
+    let (mut notifieur, collecte) = notifieur(vec![regle.clone(), regle]);
+    let mut banque = Banque::demo();
+    banque.transferer(1, 2, 100.0).unwrap();
+    let evenements = banque.nouveaux_evenements().to_vec();
+    assert_eq!(notifieur.examiner(&banque, &evenements).len(), 1);
+    // Les mêmes événements examinés une seconde fois ne redéclenchent rien
+    assert!(notifieur.examiner(&banque, &evenements).is_empty());
+    assert_eq!(collecte.messages().len(), 1);
+}
+
+#[test]
+fn le_journal_notifie_ses_propres_ecritures_seulement() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("banque.journal");
+    let alertes = dossier.path().join("alertes.log");
+    let mut banque = Banque::demo();
+    Journal::new(&chemin).enregistrer(&mut banque).unwrap();
+
+    // Premier utilisateur, avec notifications
+    let mut journal = Journal::new(&chemin);
+    let mut premiere = journal.charger().unwrap();
+    let mut notifieur = Notifieur::new(vec![Regle::SoldeSous { seuil: 100.0, compte: None }]);
+    notifieur.ajouter_destination(Box::new(DestinationFichier::new(&alertes)));
+    notifieur.observer(&premiere);
+    journal.definir_notifieur(notifieur);
+
+    // Un autre processus fait passer Bob sous le seuil : c'est à lui de le signaler
+    let mut autre = Journal::new(&chemin);
+    let mut seconde = autre.charger().unwrap();
+    seconde.retirer(2, 450.0).unwrap();
+    autre.enregistrer(&mut seconde).unwrap();
+    journal.modifier(&mut premiere, |banque| banque.deposer(1, 10.0)).unwrap().unwrap();
+    assert!(!alertes.exists());
+
+    journal.modifier(&mut premiere, |banque| banque.retirer(3, 700.0)).unwrap().unwrap();
+    let contenu = std::fs::read_to_string(&alertes).unwrap();
+    assert_eq!(contenu.lines().count(), 1);
+    assert!(contenu.ends_with("Le solde de Claire Durand (n°3) est passé sous 100,00 € : 50,00 €\n"));
+}
+
+#[test]
+fn la_destination_tcp_suit_le_protocole_du_serveur_de_journalisation() {
+    let serveur = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut destination = DestinationTcp::new(&serveur.local_addr().unwrap().to_string());
+    let notification = |message: &str| Notification {
+        cle: message.to_string(),
+        date: chrono::Local::now(),
+        message: message.to_string(),
+    };
+    destination.envoyer(&notification("premier")).unwrap();
+    destination.envoyer(&notification("sur deux\nlignes")).unwrap();
+
+    let (connexion, _) = serveur.accept().unwrap();
+    let mut lignes = BufReader::new(connexion).lines();
+    assert_eq!(lignes.next().unwrap().unwrap(), "tp2: premier");
+    assert_eq!(lignes.next().unwrap().unwrap(), "tp2: sur deux lignes");
+}
+
+#[test]
+fn lecture_de_la_configuration() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("notifications.json");
+    std::fs::write(
+        &chemin,
+        r#"{
+            "regles": [
+                {"regle": "solde_sous", "seuil": 100},
+                {"regle": "solde_sous", "seuil": 0, "compte": 2},
+                {"regle": "gros_transfert", "montant": 1000},
+                {"regle": "operation_echouee"}
+            ],
+            "destinations": [
+                {"destination": "console"},
+                {"destination": "fichier", "chemin": "alertes.log"},
+                {"destination": "tcp", "adresse": "127.0.0.1:10000"}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let configuration = Configuration::charger(&chemin).unwrap();
+    assert_eq!(configuration.regles[1], Regle::SoldeSous { seuil: 0.0, compte: Some(2) });
+    assert_eq!(configuration.regles[3], Regle::OperationEchouee);
+    assert_eq!(
+        configuration.destinations[2],
+        ConfigurationDestination::Tcp {
+            adresse: "127.0.0.1:10000".to_string()
+        }
+    );
+
+    std::fs::write(&chemin, r#"{"regles": [{"regle": "inconnue"}]}"#).unwrap();
+    assert_eq!(Configuration::charger(&chemin).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,87 @@
+// Tests par propriétés : des suites d'opérations aléatoires ne doivent jamais casser les invariants des comptes
+use proptest::collection::vec;
+use proptest::prelude::*;
+use banque_commune::banque::Banque;
+use banque_commune::compte::CompteBancaire;
+
+// Montant valide entre 0,01€ et 1000€, au centime près
+fn montant() -> impl Strategy<Value = f64> {
+    (1u32..=100_000).prop_map(|centimes| centimes as f64 / 100.0)
+}
+
+fn solde() -> impl Strategy<Value = f64> {
+    (0u32..=1_000_000).prop_map(|centimes| centimes as f64 / 100.0)
+}
+
+fn total(banque: &Banque) -> f64 {
+    banque.comptes.iter().map(|compte| compte.obtenir_solde()).sum()
+}
+
+proptest! {
+    #[test]
+    fn les_transferts_conservent_la_somme_des_soldes(
+        soldes in vec(solde(), 2..6),
+        transferts in vec((0usize..6, 0usize..6, montant()), 0..50),
+    ) {
+        let mut banque = Banque::new();
+        for (i, solde) in soldes.iter().enumerate() {
+            banque.ouvrir_compte(&format!("Compte {}", i), *solde).unwrap();
+        }
+        let total_avant = total(&banque);
+
+        let nombre = soldes.len();
+        for (source, destination, montant) in transferts {
+            let source = banque.comptes[source % nombre].obtenir_id();
+            let destination = banque.comptes[destination % nombre].obtenir_id();
+            let _ = banque.transferer(source, destination, montant);
+        }
+
+        prop_assert!((total(&banque) - total_avant).abs() < 1e-6);
+    }
+
+    #[test]
+    fn depots_et_retraits_valides_ne_rendent_jamais_le_compte_negatif(
+        solde_initial in solde(),
+        operations in vec((any::<bool>(), montant()), 0..100),
+    ) {
+        let mut compte = CompteBancaire::nouveau(1, "Test".to_string(), solde_initial);
+        for (est_un_depot, montant) in operations {
+            if est_un_depot {
+                prop_assert!(compte.depot(montant));
+            } else {
+                compte.retrait(montant);
+            }
+            prop_assert!(compte.obtenir_solde() >= 0.0);
+            prop_assert!(!compte.est_en_decouvert());
+        }
+    }
+
+    #[test]
+    fn un_montant_negatif_ou_nul_ne_change_pas_le_solde(
+        solde_initial in solde(),
+        montant in prop_oneof![Just(0.0), Just(f64::NAN), -1_000.0f64..=0.0],
+    ) {
+        let mut compte = CompteBancaire::nouveau(1, "Test".to_string(), solde_initial);
+        prop_assert!(!compte.depot(montant));
+        prop_assert!(!compte.retrait(montant));
+        prop_assert_eq!(compte.obtenir_solde(), solde_initial);
+    }
+
+    #[test]
+    fn renommer_conserve_le_solde(
+        solde_initial in solde(),
+        operations in vec((any::<bool>(), montant()), 0..20),
+        nouveau_nom in "[A-Za-z][A-Za-z ]{0,19}",
+    ) {
+        let mut compte = CompteBancaire::nouveau(1, "Ancien nom".to_string(), solde_initial);
+        for (est_un_depot, montant) in operations {
+            if est_un_depot { compte.depot(montant); } else { compte.retrait(montant); }
+        }
+
+        let renomme = compte.renommer(nouveau_nom.clone());
+        prop_assert_eq!(renomme.obtenir_nom(), nouveau_nom.as_str());
+        prop_assert_eq!(renomme.obtenir_solde(), compte.obtenir_solde());
+        prop_assert_eq!(renomme.obtenir_id(), compte.obtenir_id());
+        prop_assert_eq!(renomme.historique().len(), compte.historique().len());
+    }
+}
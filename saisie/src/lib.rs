@@ -0,0 +1,322 @@
+// Saisies interactives communes aux TP : questions typées (entier dans un intervalle, montant, oui/non,
+// choix dans une liste, texte sur plusieurs lignes), fin de saisie (Ctrl-D, fin du flux), nombre
+// d'essais limité, édition de la ligne et historique dans un terminal.
+use rustyline::DefaultEditor;
+use rustyline::error::ReadlineError;
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// Nombre de réponses invalides acceptées avant d'abandonner une question
+pub const ESSAIS_PAR_DEFAUT: u32 = 3;
+
+#[derive(Debug)]
+pub enum ErreurSaisie {
+    // Plus rien à lire : Ctrl-D, Ctrl-C dans un terminal, ou fin du flux redirigé
+    FinDeSaisie,
+    TropDEssais,
+    Io(io::Error),
+}
+
+// Texte français par défaut ; Messages::erreur le donne dans la langue de la console
+impl fmt::Display for ErreurSaisie {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Messages::default().erreur(self))
+    }
+}
+
+impl From<io::Error> for ErreurSaisie {
+    fn from(e: io::Error) -> ErreurSaisie {
+        ErreurSaisie::Io(e)
+    }
+}
+
+pub type Resultat<T> = Result<T, ErreurSaisie>;
+
+// Source des lignes saisies
+pub trait Lecteur {
+    // Affiche l'invite et lit une ligne (sans son retour à la ligne), None en fin de saisie
+    fn lire_ligne(&mut self, invite: &str, sortie: &mut dyn Write) -> io::Result<Option<String>>;
+}
+
+// Flux ordinaire : entrée redirigée, tampon en mémoire dans les tests
+impl<R: BufRead> Lecteur for R {
+    fn lire_ligne(&mut self, invite: &str, sortie: &mut dyn Write) -> io::Result<Option<String>> {
+        // Sans ce flush, une invite sans retour à la ligne (print!) n'apparaît qu'après la réponse
+        write!(sortie, "{}", invite)?;
+        sortie.flush()?;
+        let mut ligne = String::new();
+        if self.read_line(&mut ligne)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(ligne.trim_end_matches(['\n', '\r']).to_string()))
+    }
+}
+
+// Terminal interactif : édition de la ligne (flèches, Ctrl-A/Ctrl-E...) et historique des réponses
+pub struct Terminal {
+    editeur: DefaultEditor,
+}
+
+impl Terminal {
+    pub fn new() -> io::Result<Terminal> {
+        let editeur = DefaultEditor::new().map_err(io::Error::other)?;
+        Ok(Terminal { editeur })
+    }
+}
+
+impl Lecteur for Terminal {
+    fn lire_ligne(&mut self, invite: &str, sortie: &mut dyn Write) -> io::Result<Option<String>> {
+        // Le texte déjà écrit (question sur sa propre ligne) doit être affiché avant que l'éditeur prenne la main
+        sortie.flush()?;
+        match self.editeur.readline(invite) {
+            Ok(ligne) => {
+                if !ligne.trim().is_empty() {
+                    let _ = self.editeur.add_history_entry(ligne.as_str());
+                }
+                Ok(Some(ligne))
+            }
+            Err(ReadlineError::Eof | ReadlineError::Interrupted) => Ok(None),
+            Err(ReadlineError::Io(e)) => Err(e),
+            Err(e) => Err(io::Error::other(e)),
+        }
+    }
+}
+
+// Entrée standard : le terminal s'il y en a un, le flux redirigé sinon
+pub enum EntreeStandard {
+    Terminal(Box<Terminal>),
+    Flux(io::StdinLock<'static>),
+}
+
+impl Lecteur for EntreeStandard {
+    fn lire_ligne(&mut self, invite: &str, sortie: &mut dyn Write) -> io::Result<Option<String>> {
+        match self {
+            EntreeStandard::Terminal(terminal) => terminal.lire_ligne(invite, sortie),
+            EntreeStandard::Flux(flux) => flux.lire_ligne(invite, sortie),
+        }
+    }
+}
+
+// Messages affichés après une réponse invalide, en français par défaut. {min} et {max} sont
+// remplacés par les bornes de l'intervalle attendu.
+#[derive(Debug, Clone)]
+pub struct Messages {
+    pub nombre_invalide: String,
+    pub montant_invalide: String,
+    pub oui_non_invalide: String,
+    pub oui: String,
+    pub non: String,
+    // Une virgule seule suivie de trois chiffres sépare les milliers (« 1,250 » vaut 1250, comme en anglais)
+    pub virgule_des_milliers: bool,
+    // Textes des erreurs de saisie
+    pub fin_de_saisie: String,
+    pub trop_d_essais: String,
+}
+
+impl Default for Messages {
+    fn default() -> Messages {
+        Messages {
+            nombre_invalide: "Veuillez entrer un nombre entre {min} et {max}.".to_string(),
+            montant_invalide: "Montant invalide!".to_string(),
+            oui_non_invalide: "Répondez par oui ou par non.".to_string(),
+            oui: "oui".to_string(),
+            non: "non".to_string(),
+            virgule_des_milliers: false,
+            fin_de_saisie: "fin de la saisie".to_string(),
+            trop_d_essais: "trop de réponses invalides".to_string(),
+        }
+    }
+}
+
+impl Messages {
+    // Texte d'une erreur de saisie dans la langue de ces messages
+    pub fn erreur(&self, erreur: &ErreurSaisie) -> String {
+        match erreur {
+            ErreurSaisie::FinDeSaisie => self.fin_de_saisie.clone(),
+            ErreurSaisie::TropDEssais => self.trop_d_essais.clone(),
+            ErreurSaisie::Io(e) => e.to_string(),
+        }
+    }
+}
+
+// Entrée et sortie d'un programme interactif : stdin/stdout en temps normal, des tampons en mémoire
+// dans les tests
+pub struct Console<L, W> {
+    entree: L,
+    pub sortie: W,
+    essais: u32,
+    messages: Messages,
+}
+
+impl Console<EntreeStandard, io::Stdout> {
+    pub fn standard() -> Self {
+        let entree = match io::stdin().is_terminal().then(Terminal::new) {
+            Some(Ok(terminal)) => EntreeStandard::Terminal(Box::new(terminal)),
+            _ => EntreeStandard::Flux(io::stdin().lock()),
+        };
+        Console::new(entree, io::stdout())
+    }
+}
+
+impl<L: Lecteur, W: Write> Console<L, W> {
+    pub fn new(entree: L, sortie: W) -> Console<L, W> {
+        Console {
+            entree,
+            sortie,
+            essais: ESSAIS_PAR_DEFAUT,
+            messages: Messages::default(),
+        }
+    }
+
+    // Nombre de réponses invalides acceptées avant d'abandonner une question (au moins une)
+    pub fn avec_essais(mut self, essais: u32) -> Self {
+        self.essais = essais.max(1);
+        self
+    }
+
+    pub fn avec_messages(mut self, messages: Messages) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    pub fn messages(&self) -> &Messages {
+        &self.messages
+    }
+
+    // Écrit un message sur sa propre ligne
+    pub fn afficher(&mut self, message: impl fmt::Display) {
+        let _ = writeln!(self.sortie, "{}", message);
+    }
+
+    // Lit une ligne brute après avoir affiché l'invite
+    pub fn demander(&mut self, invite: &str) -> Resultat<String> {
+        self.entree
+            .lire_ligne(invite, &mut self.sortie)?
+            .ok_or(ErreurSaisie::FinDeSaisie)
+    }
+
+    // Lit une ligne sans invite (la question a été affichée avant), None en fin de saisie
+    pub fn lire(&mut self) -> Option<String> {
+        self.demander("").ok()
+    }
+
+    // Lit une ligne, vide en fin de saisie
+    pub fn lire_ligne(&mut self) -> String {
+        self.lire().unwrap_or_default()
+    }
+
+    // Texte non vide, sans les espaces autour ; une ligne vide repose la question
+    pub fn texte(&mut self, invite: &str) -> Resultat<String> {
+        loop {
+            let ligne = self.demander(invite)?;
+            if !ligne.trim().is_empty() {
+                return Ok(ligne.trim().to_string());
+            }
+        }
+    }
+
+    // Repose la question tant que la réponse n'est pas acceptée par `analyser`, dans la limite des essais
+    pub fn valeur<T>(&mut self, invite: &str, erreur: &str, analyser: impl Fn(&str) -> Option<T>) -> Resultat<T> {
+        for _ in 0..self.essais {
+            let ligne = self.demander(invite)?;
+            match analyser(ligne.trim()) {
+                Some(valeur) => return Ok(valeur),
+                None => self.afficher(erreur),
+            }
+        }
+        Err(ErreurSaisie::TropDEssais)
+    }
+
+    // Nombre entier compris dans l'intervalle
+    pub fn entier<T>(&mut self, invite: &str, plage: RangeInclusive<T>) -> Resultat<T>
+    where
+        T: FromStr + PartialOrd + fmt::Display,
+    {
+        let erreur = self
+            .messages
+            .nombre_invalide
+            .replace("{min}", &plage.start().to_string())
+            .replace("{max}", &plage.end().to_string());
+        self.valeur(invite, &erreur, |texte| texte.parse().ok().filter(|nombre| plage.contains(nombre)))
+    }
+
+    // Montant avec un point ou une virgule décimale (voir lire_montant)
+    pub fn montant(&mut self, invite: &str) -> Resultat<f64> {
+        let erreur = self.messages.montant_invalide.clone();
+        let virgule_des_milliers = self.messages.virgule_des_milliers;
+        self.valeur(invite, &erreur, |texte| lire_montant(texte, virgule_des_milliers))
+    }
+
+    // Réponse oui ou non (la première lettre suffit)
+    pub fn oui_non(&mut self, invite: &str) -> Resultat<bool> {
+        let (oui, non) = (self.messages.oui.to_lowercase(), self.messages.non.to_lowercase());
+        let erreur = self.messages.oui_non_invalide.clone();
+        self.valeur(invite, &erreur, |texte| {
+            let reponse = texte.to_lowercase();
+            let correspond = |mot: &str| !reponse.is_empty() && mot.starts_with(&reponse);
+            match (correspond(&oui), correspond(&non)) {
+                (true, false) => Some(true),
+                (false, true) => Some(false),
+                _ => None,
+            }
+        })
+    }
+
+    // Affiche les options numérotées à partir de 1 et retourne la position de celle choisie
+    pub fn choisir<T: fmt::Display>(&mut self, invite: &str, options: &[T]) -> Resultat<usize> {
+        for (index, option) in options.iter().enumerate() {
+            let _ = writeln!(self.sortie, "{}. {}", index + 1, option);
+        }
+        Ok(self.entier(invite, 1..=options.len())? - 1)
+    }
+
+    // Texte sur plusieurs lignes, jusqu'à une ligne égale à la sentinelle (ou la fin de la saisie,
+    // si au moins une ligne a été lue). Chaque ligne est suivie d'un retour à la ligne.
+    pub fn lignes_jusqu_a(&mut self, invite: &str, sentinelle: &str) -> Resultat<String> {
+        let mut texte = String::new();
+        let mut lues = 0;
+        loop {
+            match self.demander(invite) {
+                Ok(ligne) if ligne.trim() == sentinelle => return Ok(texte),
+                Ok(ligne) => {
+                    texte.push_str(&ligne);
+                    texte.push('\n');
+                    lues += 1;
+                }
+                Err(ErreurSaisie::FinDeSaisie) if lues > 0 => return Ok(texte),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+// Lit un montant saisi avec l'un ou l'autre séparateur décimal (« 12,50 », « 12.50 », « 1 000,50 € »,
+// « 1,000.50 »). Avec `virgule_des_milliers`, une virgule seule suivie de trois chiffres sépare les milliers.
+pub fn lire_montant(texte: &str, virgule_des_milliers: bool) -> Option<f64> {
+    let nettoye: String = texte.chars().filter(|c| !c.is_whitespace() && *c != '€').collect();
+    let virgules = nettoye.matches(',').count();
+    let points = nettoye.matches('.').count();
+    let decimale = match (virgules, points) {
+        (0, 0) => None,
+        (1, 0) => {
+            let milliers = nettoye.split(',').nth(1).is_some_and(|fin| fin.len() == 3);
+            if virgule_des_milliers && milliers { None } else { Some(',') }
+        }
+        (0, 1) => Some('.'),
+        // Le même séparateur répété ne peut séparer que des milliers
+        (_, 0) | (0, _) => None,
+        // Avec les deux, le dernier est le séparateur décimal
+        _ => nettoye.chars().rev().find(|c| *c == ',' || *c == '.'),
+    };
+    let nombre: String = nettoye
+        .chars()
+        .filter_map(|c| match c {
+            ',' | '.' if Some(c) == decimale => Some('.'),
+            ',' | '.' => None,
+            _ => Some(c),
+        })
+        .collect();
+    nombre.parse().ok().filter(|montant: &f64| montant.is_finite())
+}
@@ -0,0 +1,121 @@
+// Questions typées, nombre d'essais et fin de saisie, sur des entrées en mémoire
+use saisie::{Console, ErreurSaisie, Messages, lire_montant};
+
+fn console(entree: &str) -> Console<&[u8], Vec<u8>> {
+    Console::new(entree.as_bytes(), Vec::new())
+}
+
+fn sortie(console: Console<&[u8], Vec<u8>>) -> String {
+    String::from_utf8(console.sortie).unwrap()
+}
+
+#[test]
+fn l_invite_est_ecrite_avant_la_lecture() {
+    let mut console = console("Alice\n");
+    assert_eq!(console.texte("Nom : ").unwrap(), "Alice");
+    assert_eq!(sortie(console), "Nom : ");
+}
+
+#[test]
+fn la_fin_de_saisie_arrete_la_question() {
+    let mut console = console("");
+    assert!(matches!(console.texte("Nom : "), Err(ErreurSaisie::FinDeSaisie)));
+    assert_eq!(console.lire(), None);
+    assert_eq!(console.lire_ligne(), "");
+
+    // Une ligne vide repose la question, sans boucler indéfiniment en fin de saisie
+    let mut console = self::console("\n  \n");
+    assert!(matches!(console.texte("Nom : "), Err(ErreurSaisie::FinDeSaisie)));
+}
+
+#[test]
+fn entier_dans_un_intervalle() {
+    let mut console = console("zéro\n0\n4\n");
+    assert_eq!(console.entier("Choix : ", 1..=5).unwrap(), 4);
+    let texte = sortie(console);
+    assert_eq!(texte.matches("Veuillez entrer un nombre entre 1 et 5.").count(), 2);
+}
+
+#[test]
+fn trop_d_essais() {
+    let mut console = console("a\nb\nc\n1\n");
+    assert!(matches!(console.entier("Choix : ", 1..=3), Err(ErreurSaisie::TropDEssais)));
+    // La réponse suivante reste à lire
+    assert_eq!(console.lire().as_deref(), Some("1"));
+
+    let mut console = self::console("a\nb\n").avec_essais(1);
+    assert!(matches!(console.entier::<u8>("Choix : ", 1..=3), Err(ErreurSaisie::TropDEssais)));
+
+    // Le texte de l'erreur vient des messages de la console
+    assert_eq!(ErreurSaisie::TropDEssais.to_string(), "trop de réponses invalides");
+    let messages = Messages {
+        trop_d_essais: "too many invalid answers".to_string(),
+        ..Messages::default()
+    };
+    let console = console.avec_messages(messages);
+    assert_eq!(console.messages().erreur(&ErreurSaisie::TropDEssais), "too many invalid answers");
+}
+
+#[test]
+fn montants() {
+    let mut console = console("beaucoup\n12,50\n");
+    assert_eq!(console.montant("Montant : ").unwrap(), 12.5);
+    assert!(sortie(console).contains("Montant invalide!"));
+
+    assert_eq!(lire_montant("12.5", false), Some(12.5));
+    assert_eq!(lire_montant(" 1 000,50 € ", false), Some(1000.5));
+    assert_eq!(lire_montant("1.000,50", false), Some(1000.5));
+    assert_eq!(lire_montant("1,000.50", false), Some(1000.5));
+    assert_eq!(lire_montant("-3", false), Some(-3.0));
+    assert_eq!(lire_montant("", false), None);
+    assert_eq!(lire_montant("inf", false), None);
+
+    // Une virgule suivie de trois chiffres : décimale en français, milliers en anglais
+    assert_eq!(lire_montant("1,250", false), Some(1.25));
+    assert_eq!(lire_montant("1,250", true), Some(1250.0));
+    let messages = Messages {
+        virgule_des_milliers: true,
+        ..Messages::default()
+    };
+    let mut console = self::console("1,250\n").avec_messages(messages);
+    assert_eq!(console.montant("Amount: ").unwrap(), 1250.0);
+}
+
+#[test]
+fn oui_ou_non() {
+    let mut console = console("OUI\nn\npeut-être\no\n");
+    assert!(console.oui_non("Confirmer ? ").unwrap());
+    assert!(!console.oui_non("Confirmer ? ").unwrap());
+    assert!(console.oui_non("Confirmer ? ").unwrap());
+    assert!(sortie(console).contains("Répondez par oui ou par non."));
+
+    let messages = Messages {
+        oui: "yes".to_string(),
+        non: "no".to_string(),
+        ..Messages::default()
+    };
+    let mut console = self::console("y\n").avec_messages(messages);
+    assert!(console.oui_non("Confirm? ").unwrap());
+}
+
+#[test]
+fn choix_dans_une_liste() {
+    let mut console = console("3\n2\n");
+    assert_eq!(console.choisir("Compte : ", &["Alice", "Bob"]).unwrap(), 1);
+    let texte = sortie(console);
+    assert!(texte.starts_with("1. Alice\n2. Bob\nCompte : "));
+    assert!(texte.contains("Veuillez entrer un nombre entre 1 et 2."));
+}
+
+#[test]
+fn texte_sur_plusieurs_lignes() {
+    let mut console = console("première\n\nderniere\nFIN\nsuite\n");
+    assert_eq!(console.lignes_jusqu_a("> ", "FIN").unwrap(), "première\n\nderniere\n");
+    assert_eq!(console.lire().as_deref(), Some("suite"));
+
+    // La fin de saisie termine le texte commencé
+    let mut console = self::console("une ligne");
+    assert_eq!(console.lignes_jusqu_a("> ", "FIN").unwrap(), "une ligne\n");
+    let mut console = self::console("");
+    assert!(matches!(console.lignes_jusqu_a("> ", "FIN"), Err(ErreurSaisie::FinDeSaisie)));
+}
@@ -1,4 +1,4 @@
-use std::io;
+use saisie::{Console, ErreurSaisie};
 
 fn main() {
     let nom = "Kevin";
@@ -77,26 +77,19 @@ fn main() {
         println!("{}.{}", i + 1, option);
     }
 
-    println!("Veuillez saisir un numéro de votre choix:");
-
-    let mut choix = String::new();
-    io::stdin()
-        .read_line(&mut choix)
-        .expect("Attention erreur de lecture");
-
-    let choix: usize = match choix.trim().parse() {
-        Ok(num) => num,
+    // La console de la crate saisie affiche l'invite, lit la réponse et la redemande si elle n'est
+    // pas un numéro du menu (3 essais), ou s'arrête si l'entrée est terminée (Ctrl-D)
+    let mut console = Console::standard();
+    match console.entier("Veuillez saisir un numéro de votre choix: ", 1..=options.len()) {
+        Ok(choix) => {
+            println!("Vous avez sélectionné : {}", options[choix - 1]);
+            // ici on peut exécuter une action selon choix dans options
+        }
+        Err(ErreurSaisie::TropDEssais) => println!(" choix hors système !! limite système "),
         Err(_) => {
-            println!("Veuillez saisir un numero valide");
+            println!("Aucun choix saisi");
             return;
         }
-    };
-
-    if choix == 0 || choix > options.len() {
-        println!(" choix hors système !! limite système ");
-    } else {
-        println!("Vous avez sélectionné : {}", options[choix - 1]);
-        // ici on peut exécuter une action selon choix dans options
     }
 
     // Les tableaux
@@ -1,134 +1,179 @@
-use std::io;
+use banque_commune::banque::{Banque, ErreurBanque};
+use banque_commune::journal::Journal;
+use banque_commune::langue::{self, Langue};
+use banque_commune::tr;
+use saisie::{Console, Lecteur};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+// Les comptes sont ceux du tp2 : même modèle, même journal (banque.journal par défaut)
+const FICHIER_DONNEES_PAR_DEFAUT: &str = "banque.journal";
+
+// Options de la ligne de commande : [--donnees journal] [--langue fr|en], la langue de LANG sinon
+fn lire_options() -> PathBuf {
+    langue::definir_langue(Langue::depuis_environnement());
+    let mut donnees = PathBuf::from(FICHIER_DONNEES_PAR_DEFAUT);
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--donnees" => {
+                if let Some(chemin) = args.next() {
+                    donnees = PathBuf::from(chemin);
+                }
+            }
+            "--langue" => match args.next().as_deref().and_then(Langue::depuis_code) {
+                Some(choisie) => langue::definir_langue(choisie),
+                None => eprintln!("{}", tr!("options.langue_invalide")),
+            },
+            _ => {}
+        }
+    }
+    donnees
+}
+
+// Reconstruit la banque depuis le journal, ou crée la banque de démonstration au premier lancement
+fn charger_banque(journal: &mut Journal) -> Banque {
+    if journal.existe() {
+        return journal.charger().unwrap_or_else(|e| {
+            eprintln!("{}", tr!("commun.lecture_impossible", chemin = journal.chemin().display(), erreur = e));
+            std::process::exit(1);
+        });
+    }
+    let mut banque = Banque::demo();
+    if let Err(e) = journal.enregistrer(&mut banque) {
+        println!("{}", tr!("journal.enregistrement_impossible", chemin = journal.chemin().display(), erreur = e));
+    }
+    banque
+}
 
 fn main() {
-    println!("=== Système de Gestion de Comptes Bancaires ===\n");
+    let donnees = lire_options();
+    println!("{}\n", tr!("titre"));
 
-    let mut noms = [
-        "Alice Dupont".to_string(),
-        "Bob Martin".to_string(),
-        "Claire Durand".to_string(),
-    ];
-    
-    let mut soldes = vec![1000.0, 500.0, 750.0];
+    let mut journal = Journal::new(&donnees);
+    let mut banque = charger_banque(&mut journal);
 
-    let options = ["Afficher solde", "Retrait", "Liste comptes", "Quitter"];
+    let mut console = Console::standard().avec_messages(langue::messages_saisie());
+    let options = [tr!("menu.afficher_solde"), tr!("menu.retrait"), tr!("menu.liste"), tr!("menu.quitter")];
 
     loop {
+        // Les comptes ont pu être modifiés par le tp2 ou un autre tp1 depuis le dernier choix
+        if let Err(e) = journal.rafraichir(&mut banque) {
+            println!("{}", tr!("journal.relecture_impossible", chemin = journal.chemin().display(), erreur = e));
+        }
+
         // Afficher le menu
-        println!("\n--- MENU ---");
+        println!("{}", tr!("menu.titre"));
         for (index, option) in options.iter().enumerate() {
             println!("{}. {}", index + 1, option);
         }
-        
-        print!("\nChoisissez une option (1-4): ");
-        
-        // Lire l'entrée utilisateur
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).expect("Erreur de lecture");
-        
+
+        // Lire l'entrée utilisateur (fin de l'entrée = quitter)
+        let Ok(input) = console.demander(&format!("{} ", tr!("menu.choisir_option", nombre = options.len()))) else {
+            println!("{}", tr!("menu.au_revoir"));
+            break;
+        };
+
         let choix: u32 = match input.trim().parse() {
             Ok(num) => num,
             Err(_) => {
-                println!("Veuillez entrer un nombre valide!");
+                println!("{}", tr!("menu.nombre_invalide"));
                 continue;
             }
         };
 
         // Traiter le choix
         match choix {
-            1 => afficher_solde(&noms, &soldes),
-            2 => effectuer_retrait(&noms, &mut soldes),
-            3 => lister_comptes(&noms, &soldes),
+            1 => afficher_solde(&mut console, &banque),
+            2 => effectuer_retrait(&mut console, &mut banque, &mut journal),
+            3 => lister_comptes(&banque),
             4 => {
-                println!("Au revoir!");
+                println!("{}", tr!("menu.au_revoir"));
                 break;
             }
-            _ => println!("Option invalide, veuillez choisir entre 1 et 4."),
+            _ => println!("{}", tr!("menu.option_invalide", nombre = options.len())),
         }
     }
 }
 
-fn afficher_solde(noms: &[String], soldes: &[f64]) {
-    println!("\n--- Afficher Solde ---");
-    
-    if noms.is_empty() {
-        println!("Aucun compte disponible.");
+// Demande un compte par son numéro dans la liste et retourne son identifiant. Un numéro invalide est
+// redemandé ; None si l'utilisateur abandonne (fin de saisie, trop d'essais).
+fn choisir_compte(console: &mut Console<impl Lecteur, impl Write>, banque: &Banque) -> Option<u32> {
+    let nombre = banque.comptes.len();
+    let choix = console.valeur("", tr!("commun.choix_invalide"), |texte| {
+        texte.parse::<usize>().ok().filter(|num| (1..=nombre).contains(num))
+    });
+    choix.ok().map(|num| banque.comptes[num - 1].obtenir_id())
+}
+
+fn afficher_solde(console: &mut Console<impl Lecteur, impl Write>, banque: &Banque) {
+    println!("{}", tr!("solde.titre"));
+
+    if banque.comptes.is_empty() {
+        println!("{}", tr!("commun.aucun_compte"));
         return;
     }
 
-    println!("Choisissez un compte:");
-    for (index, nom) in noms.iter().enumerate() {
-        println!("{}. {}", index + 1, nom);
+    println!("{}", tr!("commun.choisir_compte"));
+    for (index, compte) in banque.comptes.iter().enumerate() {
+        println!("{}", tr!("commun.compte", numero = index + 1, nom = compte.obtenir_nom()));
     }
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).expect("Erreur de lecture");
-    
-    let choix: usize = match input.trim().parse::<usize>() {
-        Ok(num) if num > 0 && num <= noms.len() => num - 1,
-        _ => {
-            println!("Choix invalide!");
-            return;
-        }
+    let Some(id) = choisir_compte(console, banque) else {
+        return;
     };
-
-    println!("Solde de {}: {:.2}€", noms[choix], soldes[choix]);
+    let compte = banque.compte(id).expect("Le compte vient d'être choisi");
+    println!("{}", tr!("solde.de", nom = compte.obtenir_nom(), solde = langue::montant(compte.obtenir_solde())));
 }
 
-fn effectuer_retrait(noms: &[String], soldes: &mut [f64]) {
-    println!("\n--- Retrait ---");
-    
-    if noms.is_empty() {
-        println!("Aucun compte disponible.");
+fn effectuer_retrait(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque, journal: &mut Journal) {
+    println!("{}", tr!("retrait.titre"));
+
+    if banque.comptes.is_empty() {
+        println!("{}", tr!("commun.aucun_compte"));
         return;
     }
 
-    println!("Choisissez un compte:");
-    for (index, nom) in noms.iter().enumerate() {
-        println!("{}. {} (Solde: {:.2}€)", index + 1, nom, soldes[index]);
+    println!("{}", tr!("commun.choisir_compte"));
+    for (index, compte) in banque.comptes.iter().enumerate() {
+        let solde = langue::montant(compte.obtenir_solde());
+        println!("{}", tr!("commun.compte_et_solde", numero = index + 1, nom = compte.obtenir_nom(), solde = solde));
     }
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).expect("Erreur de lecture");
-    
-    let choix: usize = match input.trim().parse::<usize>() {
-        Ok(num) if num > 0 && num <= noms.len() => num - 1,
-        _ => {
-            println!("Choix invalide!");
-            return;
-        }
+    let Some(id) = choisir_compte(console, banque) else {
+        return;
     };
 
-    println!("Montant à retirer:");
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).expect("Erreur de lecture");
-    
-    let montant: f64 = match input.trim().parse() {
-        Ok(num) => num,
-        Err(_) => {
-            println!("Montant invalide!");
-            return;
-        }
+    println!("{}", tr!("retrait.montant"));
+    // « 12,50 » comme « 12.50 », redemandé s'il est illisible
+    let Ok(montant) = console.valeur("", tr!("commun.montant_invalide"), langue::lire_montant) else {
+        return;
     };
 
-    if montant <= soldes[choix] && montant > 0.0 {
-        soldes[choix] -= montant;
-        println!("Retrait de {:.2}€ effectué avec succès!", montant);
-        println!("Nouveau solde: {:.2}€", soldes[choix]);
-    } else {
-        println!("Retrait impossible (solde insuffisant ou montant invalide).");
+    // Le retrait est validé par la banque et enregistré sous le verrou du journal, sur l'état le plus récent
+    match journal.modifier(banque, |banque| banque.retirer(id, montant)) {
+        Ok(Ok(solde)) => {
+            println!("{}", tr!("retrait.effectue", montant = langue::montant(montant)));
+            println!("{}", tr!("commun.nouveau_solde", solde = langue::montant(solde)));
+        }
+        Ok(Err(ErreurBanque::SoldeInsuffisant | ErreurBanque::MontantInvalide)) => {
+            println!("{}", tr!("retrait.impossible"))
+        }
+        Ok(Err(e)) => println!("{}", e),
+        Err(e) => println!("{}", tr!("journal.enregistrement_impossible", chemin = journal.chemin().display(), erreur = e)),
     }
 }
 
-fn lister_comptes(noms: &[String], soldes: &[f64]) {
-    println!("\n--- Liste des Comptes ---");
-    
-    if noms.is_empty() {
-        println!("Aucun compte enregistré.");
+fn lister_comptes(banque: &Banque) {
+    println!("{}", tr!("liste.titre"));
+
+    if banque.comptes.is_empty() {
+        println!("{}", tr!("liste.aucun"));
         return;
     }
 
-    for (index, nom) in noms.iter().enumerate() {
-        println!("{}. {} - Solde: {:.2}€", index + 1, nom, soldes[index]);
+    for (index, compte) in banque.comptes.iter().enumerate() {
+        print!("{}. ", index + 1);
+        let _ = compte.afficher_infos(&mut io::stdout());
     }
-}
\ No newline at end of file
+}
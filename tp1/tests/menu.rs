@@ -0,0 +1,39 @@
+// Les quatre opérations du menu, en lançant le programme sur un journal temporaire
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+fn lancer(journal: &Path, saisie: &str) -> String {
+    let mut programme = Command::new(env!("CARGO_BIN_EXE_tp1"))
+        .args(["--langue", "fr", "--donnees"])
+        .arg(journal)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    programme.stdin.take().unwrap().write_all(saisie.as_bytes()).unwrap();
+    let sortie = programme.wait_with_output().unwrap();
+    assert!(sortie.status.success());
+    String::from_utf8(sortie.stdout).unwrap()
+}
+
+#[test]
+fn solde_retrait_liste_et_quitter() {
+    let dossier = tempfile::tempdir().unwrap();
+    let journal = dossier.path().join("banque.journal");
+
+    // Solde de Bob, retrait de 100,50 € chez Alice, liste des comptes, puis quitter
+    let sortie = lancer(&journal, "1\n2\n2\n1\n100,5\n3\n4\n");
+    assert!(sortie.contains("Solde de Bob Martin: 500,00 €"));
+    assert!(sortie.contains("Retrait de 100,50 € effectué avec succès!"));
+    assert!(sortie.contains("Nouveau solde: 899,50 €"));
+    assert!(sortie.contains("1. Alice Dupont - Solde: 899,50 €"));
+    assert!(sortie.ends_with("Au revoir!\n"));
+
+    // Le retrait a été enregistré dans le journal ; un retrait trop grand est refusé
+    let sortie = lancer(&journal, "2\n1\n5000\n3\n");
+    assert!(sortie.contains("Retrait impossible (solde insuffisant ou montant invalide)."));
+    assert!(sortie.contains("1. Alice Dupont - Solde: 899,50 €"));
+    // La fin de la saisie quitte aussi
+    assert!(sortie.ends_with("Au revoir!\n"));
+}
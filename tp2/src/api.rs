@@ -0,0 +1,358 @@
+use crate::compte::CompteBancaire;
+use crate::banque::{Banque, ErreurBanque, Execution, Operation};
+use crate::journal::{ErreurJournal, Journal};
+use crate::tr;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+pub const ADRESSE_PAR_DEFAUT: &str = "127.0.0.1:10002";
+
+// En-tête HTTP portant la clé d'idempotence fournie par le client
+const EN_TETE_IDEMPOTENCE: &str = "Idempotency-Key";
+// En-tête ajouté à la réponse quand le résultat d'origine est renvoyé
+const EN_TETE_REJEU: &str = "Idempotent-Replayed";
+
+struct Serveur {
+    // Le Mutex sérialise les requêtes concurrentes : une seule opération modifie la banque à la fois.
+    // Il n'est pris que dans les tâches bloquantes (voir bloquant).
+    banque: Mutex<Banque>,
+    // Toujours verrouillé après la banque
+    journal: Mutex<Journal>,
+}
+
+type Etat = Arc<Serveur>;
+
+impl Serveur {
+    // Verrouille la banque puis le journal. Si une requête a paniqué en les tenant, l'état en mémoire
+    // n'est plus sûr : la banque est rechargée depuis le journal, qui fait foi.
+    fn verrouiller(&self) -> Result<(MutexGuard<'_, Banque>, MutexGuard<'_, Journal>), ErreurJournal> {
+        let banque = self.banque.lock();
+        let journal = self.journal.lock();
+        let empoisonne = banque.is_err() || journal.is_err();
+        let mut banque = banque.unwrap_or_else(PoisonError::into_inner);
+        let mut journal = journal.unwrap_or_else(PoisonError::into_inner);
+        if empoisonne {
+            journal.recharger(&mut banque)?;
+            self.banque.clear_poison();
+            self.journal.clear_poison();
+        }
+        Ok((banque, journal))
+    }
+
+    // Accès exclusif à la banque pour la consulter, à jour des écritures des autres processus (lues sous
+    // le verrou partagé du journal). Le verrou exclusif n'est pris que pour enregistrer les encaissements
+    // arrivés à date de valeur, s'il y en a. Une erreur du journal est renvoyée plutôt que des données
+    // périmées.
+    fn banque(&self) -> Result<MutexGuard<'_, Banque>, ErreurJournal> {
+        let (mut banque, mut journal) = self.verrouiller()?;
+        let aujourd_hui = Local::now().date_naive();
+        let mut resultat = journal.rafraichir(&mut banque).map_err(ErreurJournal::from);
+        if resultat.is_ok() && !journal.est_lecture_seule() && banque.encaissements_a_compenser(aujourd_hui) {
+            resultat = journal.modifier(&mut banque, |banque| {
+                banque.compenser_encaissements(aujourd_hui);
+            });
+        }
+        if let Err(e) = &resultat {
+            eprintln!("{}", tr!("serveur.mise_a_jour_impossible", chemin = journal.chemin().display(), erreur = e));
+        }
+        resultat.map(|_| banque)
+    }
+
+    // Modifie la banque sous le verrou exclusif du journal : la modification s'applique toujours
+    // à l'état le plus récent, même si un autre processus vient d'écrire
+    fn modifier<T>(&self, modification: impl FnOnce(&mut Banque) -> T) -> Result<T, ErreurJournal> {
+        let (mut banque, mut journal) = self.verrouiller()?;
+        journal.modifier(&mut banque, |banque| {
+            banque.compenser_encaissements(Local::now().date_naive());
+            modification(banque)
+        })
+    }
+}
+
+// Traite une requête (verrous, lecture et écriture du journal) sur un thread réservé aux tâches
+// bloquantes : l'attente du verrou d'un autre processus n'arrête pas les threads de tokio. Une panique
+// du traitement donne une erreur 500.
+async fn bloquant<T: Send + 'static>(
+    etat: Etat,
+    traitement: impl FnOnce(&Serveur) -> Result<T, ErreurApi> + Send + 'static,
+) -> Result<T, ErreurApi> {
+    tokio::task::spawn_blocking(move || traitement(&etat))
+        .await
+        .unwrap_or(Err(ErreurApi::Interne))
+}
+
+#[derive(Serialize)]
+struct CompteJson {
+    id: u32,
+    nom: String,
+    solde: f64,
+    solde_comptable: f64,
+    en_decouvert: bool,
+}
+
+impl From<&CompteBancaire> for CompteJson {
+    fn from(compte: &CompteBancaire) -> CompteJson {
+        CompteJson {
+            id: compte.obtenir_id(),
+            nom: compte.obtenir_nom().to_string(),
+            solde: compte.obtenir_solde(),
+            solde_comptable: compte.obtenir_solde_comptable(),
+            en_decouvert: compte.est_en_decouvert(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TransactionJson {
+    date: String,
+    libelle: String,
+    montant: f64,
+}
+
+#[derive(Deserialize)]
+struct NouveauCompte {
+    nom: String,
+    #[serde(default)]
+    solde: f64,
+}
+
+// Paramètre facultatif ?au=<date RFC 3339> : état de la banque à cet instant
+#[derive(Deserialize)]
+struct Instant {
+    au: Option<DateTime<Local>>,
+}
+
+#[derive(Deserialize)]
+struct OperationJson {
+    montant: f64,
+}
+
+#[derive(Deserialize)]
+struct Transfert {
+    source: u32,
+    destination: u32,
+    montant: f64,
+}
+
+// Chaque erreur métier est traduite en code HTTP avec un corps {"erreur": "..."}
+fn reponse_erreur(erreur: ErreurBanque) -> Response {
+    let statut = match erreur {
+        ErreurBanque::CompteIntrouvable(_) | ErreurBanque::EncaissementIntrouvable(_) => StatusCode::NOT_FOUND,
+        ErreurBanque::NomDejaUtilise(_)
+        | ErreurBanque::CleDejaUtilisee(_)
+        | ErreurBanque::PeriodeRapprochee(_)
+        | ErreurBanque::RapprochementIncomplet { .. }
+        | ErreurBanque::MoisDejaFacture(_)
+        | ErreurBanque::MoisNonTermine(_)
+        | ErreurBanque::FraisNonPreleves(_)
+        | ErreurBanque::ExerciceNonTermine(_)
+        | ErreurBanque::ExerciceDejaClos(_)
+        | ErreurBanque::ExerciceClos(_)
+        | ErreurBanque::ExerciceNonClos(_) => StatusCode::CONFLICT,
+        ErreurBanque::AccesRefuse => StatusCode::FORBIDDEN,
+        ErreurBanque::SoldeInsuffisant => StatusCode::UNPROCESSABLE_ENTITY,
+        ErreurBanque::MontantInvalide | ErreurBanque::NomVide | ErreurBanque::MemeCompte => {
+            StatusCode::BAD_REQUEST
+        }
+    };
+    (statut, Json(serde_json::json!({ "erreur": erreur.to_string() }))).into_response()
+}
+
+// Erreur d'une requête : erreur métier, impossibilité de lire ou d'écrire le journal, ou panique du
+// traitement
+enum ErreurApi {
+    Banque(ErreurBanque),
+    Journal(ErreurJournal),
+    Interne,
+}
+
+impl From<ErreurBanque> for ErreurApi {
+    fn from(erreur: ErreurBanque) -> ErreurApi {
+        ErreurApi::Banque(erreur)
+    }
+}
+
+impl From<ErreurJournal> for ErreurApi {
+    fn from(erreur: ErreurJournal) -> ErreurApi {
+        ErreurApi::Journal(erreur)
+    }
+}
+
+impl IntoResponse for ErreurApi {
+    fn into_response(self) -> Response {
+        match self {
+            ErreurApi::Banque(erreur) => reponse_erreur(erreur),
+            ErreurApi::Journal(erreur) => {
+                let statut = match erreur {
+                    ErreurJournal::LectureSeule => StatusCode::FORBIDDEN,
+                    ErreurJournal::Conflit { .. } => StatusCode::CONFLICT,
+                    ErreurJournal::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                (statut, Json(serde_json::json!({ "erreur": erreur.to_string() }))).into_response()
+            }
+            ErreurApi::Interne => {
+                let corps = serde_json::json!({ "erreur": tr!("serveur.erreur_interne") });
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(corps)).into_response()
+            }
+        }
+    }
+}
+
+// Réponse d'une opération idempotente : le résultat, marqué s'il s'agit d'un rejeu
+fn reponse_execution(execution: Execution) -> Response {
+    let mut reponse = match execution.resultat {
+        Ok(resultat) => Json(resultat).into_response(),
+        Err(erreur) => reponse_erreur(erreur),
+    };
+    if execution.rejouee {
+        reponse.headers_mut().insert(EN_TETE_REJEU, "true".parse().unwrap());
+    }
+    reponse
+}
+
+fn cle_idempotence(en_tetes: &HeaderMap) -> Option<&str> {
+    en_tetes.get(EN_TETE_IDEMPOTENCE).and_then(|valeur| valeur.to_str().ok())
+}
+
+// Démarre le serveur HTTP (bloquant) sur l'adresse donnée
+pub fn lancer(banque: Banque, journal: Journal, adresse: &str) -> io::Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(adresse).await?;
+        println!("{}", tr!("serveur.ecoute", adresse = adresse));
+        axum::serve(listener, routes(banque, journal)).await
+    })
+}
+
+// Exécute une opération sur les soldes et l'enregistre dans le journal
+async fn executer(etat: Etat, operation: Operation, en_tetes: &HeaderMap) -> Result<Response, ErreurApi> {
+    let cle = cle_idempotence(en_tetes).map(str::to_string);
+    bloquant(etat, move |serveur| {
+        let execution = serveur.modifier(|banque| banque.executer(operation, cle.as_deref()))?;
+        Ok(reponse_execution(execution))
+    })
+    .await
+}
+
+// Routes de l'API sur la banque et son journal
+pub fn routes(banque: Banque, journal: Journal) -> Router {
+    let etat = Arc::new(Serveur {
+        banque: Mutex::new(banque),
+        journal: Mutex::new(journal),
+    });
+    Router::new()
+        .route("/comptes", get(lister).post(creer))
+        .route("/comptes/{id}", get(consulter).delete(fermer))
+        .route("/comptes/{id}/depot", post(deposer))
+        .route("/comptes/{id}/retrait", post(retirer))
+        .route("/comptes/{id}/transactions", get(transactions))
+        .route("/transferts", post(transferer))
+        .with_state(etat)
+}
+
+async fn lister(
+    State(etat): State<Etat>,
+    Query(instant): Query<Instant>,
+) -> Result<Json<Vec<CompteJson>>, ErreurApi> {
+    bloquant(etat, move |serveur| {
+        let Some(au) = instant.au else {
+            let banque = serveur.banque()?;
+            return Ok(Json(banque.comptes.iter().map(CompteJson::from).collect()));
+        };
+        // Seul le journal est relu
+        let (banque, journal) = serveur.verrouiller()?;
+        drop(banque);
+        let passee = journal.banque_au(au).map_err(ErreurJournal::from)?;
+        Ok(Json(passee.comptes.iter().map(CompteJson::from).collect()))
+    })
+    .await
+}
+
+async fn creer(
+    State(etat): State<Etat>,
+    Json(corps): Json<NouveauCompte>,
+) -> Result<(StatusCode, Json<CompteJson>), ErreurApi> {
+    bloquant(etat, move |serveur| {
+        let compte = serveur.modifier(|banque| {
+            let id = banque.ouvrir_compte(corps.nom.trim(), corps.solde)?;
+            banque.compte(id).map(CompteJson::from)
+        })??;
+        Ok((StatusCode::CREATED, Json(compte)))
+    })
+    .await
+}
+
+async fn consulter(State(etat): State<Etat>, Path(id): Path<u32>) -> Result<Json<CompteJson>, ErreurApi> {
+    bloquant(etat, move |serveur| {
+        let banque = serveur.banque()?;
+        Ok(Json(CompteJson::from(banque.compte(id)?)))
+    })
+    .await
+}
+
+async fn fermer(State(etat): State<Etat>, Path(id): Path<u32>) -> Result<Json<CompteJson>, ErreurApi> {
+    bloquant(etat, move |serveur| {
+        let compte = serveur.modifier(|banque| banque.fermer_compte(id))??;
+        Ok(Json(CompteJson::from(&compte)))
+    })
+    .await
+}
+
+async fn deposer(
+    State(etat): State<Etat>,
+    Path(id): Path<u32>,
+    en_tetes: HeaderMap,
+    Json(corps): Json<OperationJson>,
+) -> Result<Response, ErreurApi> {
+    executer(etat, Operation::Depot { id, montant: corps.montant }, &en_tetes).await
+}
+
+async fn retirer(
+    State(etat): State<Etat>,
+    Path(id): Path<u32>,
+    en_tetes: HeaderMap,
+    Json(corps): Json<OperationJson>,
+) -> Result<Response, ErreurApi> {
+    executer(etat, Operation::Retrait { id, montant: corps.montant }, &en_tetes).await
+}
+
+async fn transferer(
+    State(etat): State<Etat>,
+    en_tetes: HeaderMap,
+    Json(corps): Json<Transfert>,
+) -> Result<Response, ErreurApi> {
+    let operation = Operation::Transfert {
+        source: corps.source,
+        destination: corps.destination,
+        montant: corps.montant,
+    };
+    executer(etat, operation, &en_tetes).await
+}
+
+async fn transactions(
+    State(etat): State<Etat>,
+    Path(id): Path<u32>,
+) -> Result<Json<Vec<TransactionJson>>, ErreurApi> {
+    bloquant(etat, move |serveur| {
+        let banque = serveur.banque()?;
+        let historique = banque
+            .compte(id)?
+            .historique()
+            .iter()
+            .map(|transaction| TransactionJson {
+                date: transaction.date.to_rfc3339(),
+                libelle: transaction.libelle.clone(),
+                montant: transaction.montant,
+            })
+            .collect();
+        Ok(Json(historique))
+    })
+    .await
+}
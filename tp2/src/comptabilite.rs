@@ -0,0 +1,775 @@
+use crate::banque::Banque;
+use crate::compte::{Encaissement, TypeCompte};
+use crate::evenement::{Evenement, EvenementDate};
+use crate::frais;
+use crate::journal::Journal;
+use crate::tr;
+use chrono::{Datelike, Days, Local, NaiveDate, TimeZone};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::{fmt, fs, io};
+
+// Export de la banque en comptabilité en partie double (Beancount, Ledger-cli) à partir du journal
+// des événements, et import d'un export Beancount.
+//
+// Plan comptable :
+//   Assets:Tp2:C<id>            solde disponible du compte <id>
+//   Assets:Tp2:Attente:C<id>    encaissements en attente du compte <id>
+//   Equity:Ouverture            contrepartie des soldes initiaux
+//   Equity:Externe              argent entré ou sorti de la banque (dépôts, retraits, chèques)
+//   Equity:Cloture              solde restant d'un compte supprimé
+//   Expenses:Frais              frais prélevés par la banque
+//   Income:Interets             intérêts créditeurs versés à la clôture de l'exercice
+
+const DEVISE: &str = "EUR";
+const OUVERTURE: &str = "Equity:Ouverture";
+const EXTERNE: &str = "Equity:Externe";
+const CLOTURE: &str = "Equity:Cloture";
+const FRAIS: &str = "Expenses:Frais";
+const INTERETS: &str = "Income:Interets";
+const PREFIXE_COMPTE: &str = "Assets:Tp2:C";
+const PREFIXE_ATTENTE: &str = "Assets:Tp2:Attente:C";
+
+fn compte(id: u32) -> String {
+    format!("{}{}", PREFIXE_COMPTE, id)
+}
+
+fn attente(id: u32) -> String {
+    format!("{}{}", PREFIXE_ATTENTE, id)
+}
+
+// Valeur d'une métadonnée ou d'une directive personnalisée
+#[derive(Debug, Clone)]
+enum Valeur {
+    Texte(String),
+    Compte(String),
+    Date(NaiveDate),
+}
+
+#[derive(Debug)]
+enum Directive {
+    Ouverture { date: NaiveDate, compte: String, nom: Option<String> },
+    Cloture { date: NaiveDate, compte: String },
+    Ecriture {
+        date: NaiveDate,
+        libelle: String,
+        meta: Vec<(&'static str, Valeur)>,
+        // Le montant d'une seule ligne peut être omis : il équilibre l'écriture
+        postes: Vec<(String, Option<f64>)>,
+    },
+    Assertion { date: NaiveDate, compte: String, montant: f64 },
+    Personnalisee { date: NaiveDate, nom: &'static str, valeurs: Vec<Valeur> },
+}
+
+// Montant au centime près quand c'est possible, sinon avec toutes ses décimales
+fn montant(valeur: f64) -> String {
+    if (valeur * 100.0).round() / 100.0 == valeur {
+        format!("{:.2}", valeur)
+    } else {
+        format!("{}", valeur)
+    }
+}
+
+fn chaine(texte: &str) -> String {
+    format!("\"{}\"", texte.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn trouver_encaissement(banque: &Banque, numero: u32) -> Option<(u32, Encaissement)> {
+    banque.comptes.iter().find_map(|compte| {
+        compte
+            .en_attente()
+            .iter()
+            .find(|encaissement| encaissement.numero == numero)
+            .map(|encaissement| (compte.obtenir_id(), encaissement.clone()))
+    })
+}
+
+fn nom_de(banque: &Banque, id: u32) -> String {
+    banque
+        .compte(id)
+        .map(|compte| compte.obtenir_nom().to_string())
+        .unwrap_or_else(|_| format!("compte {}", id))
+}
+
+// Soldes de tous les comptes ouverts à une date
+fn assertions(banque: &Banque, date: NaiveDate, attentes: &BTreeSet<u32>, sortie: &mut Vec<Directive>) {
+    for c in &banque.comptes {
+        let id = c.obtenir_id();
+        sortie.push(Directive::Assertion {
+            date,
+            compte: compte(id),
+            montant: c.obtenir_solde(),
+        });
+        if attentes.contains(&id) {
+            sortie.push(Directive::Assertion {
+                date,
+                compte: attente(id),
+                montant: c.obtenir_solde_comptable() - c.obtenir_solde(),
+            });
+        }
+    }
+}
+
+// Traduit les événements en directives comptables, en rejouant la banque au fur et à mesure
+// pour connaître les noms et les soldes
+fn directives(evenements: &[EvenementDate]) -> Vec<Directive> {
+    let mut sortie = Vec::new();
+    let Some(premier) = evenements.first() else {
+        return sortie;
+    };
+    let debut = premier.date.date_naive();
+    for c in [OUVERTURE, EXTERNE, CLOTURE, FRAIS, INTERETS] {
+        sortie.push(Directive::Ouverture {
+            date: debut,
+            compte: c.to_string(),
+            nom: None,
+        });
+    }
+
+    let mut banque = Banque::new();
+    // Comptes ayant déjà reçu un encaissement (leur compte d'attente est ouvert)
+    let mut attentes = BTreeSet::new();
+    let mut precedent = debut;
+    for evenement in evenements {
+        let date = evenement.date.date_naive();
+        // Soldes de fin de mois, vérifiés au premier jour du mois suivant
+        if frais::debut_du_mois(date) > frais::debut_du_mois(precedent) {
+            assertions(&banque, frais::debut_du_mois(date), &attentes, &mut sortie);
+        }
+        precedent = date;
+
+        let identifiant = vec![("tp2-id", Valeur::Texte(evenement.numero.to_string()))];
+        let ecriture = |libelle: String, meta: Vec<(&'static str, Valeur)>, postes: Vec<(String, Option<f64>)>| {
+            let mut tout = identifiant.clone();
+            tout.extend(meta);
+            Directive::Ecriture {
+                date,
+                libelle,
+                meta: tout,
+                postes,
+            }
+        };
+
+        match &evenement.evenement {
+            Evenement::CompteOuvert { id, nom, solde } => {
+                sortie.push(Directive::Ouverture {
+                    date,
+                    compte: compte(*id),
+                    nom: Some(nom.clone()),
+                });
+                if *solde != 0.0 {
+                    sortie.push(ecriture(
+                        "Solde initial".to_string(),
+                        Vec::new(),
+                        vec![(compte(*id), Some(*solde)), (OUVERTURE.to_string(), None)],
+                    ));
+                }
+            }
+            Evenement::Depot { id, montant } => sortie.push(ecriture(
+                "Dépôt".to_string(),
+                Vec::new(),
+                vec![(compte(*id), Some(*montant)), (EXTERNE.to_string(), None)],
+            )),
+            Evenement::Retrait { id, montant } => sortie.push(ecriture(
+                "Retrait".to_string(),
+                Vec::new(),
+                vec![(compte(*id), Some(-montant)), (EXTERNE.to_string(), None)],
+            )),
+            Evenement::Transfert { source, destination, montant } => sortie.push(ecriture(
+                format!(
+                    "Transfert de {} vers {}",
+                    nom_de(&banque, *source),
+                    nom_de(&banque, *destination)
+                ),
+                Vec::new(),
+                vec![(compte(*source), Some(-montant)), (compte(*destination), Some(*montant))],
+            )),
+            Evenement::CompteRenomme { id, nom } => sortie.push(Directive::Personnalisee {
+                date,
+                nom: "tp2-renommage",
+                valeurs: vec![Valeur::Compte(compte(*id)), Valeur::Texte(nom.clone())],
+            }),
+            Evenement::CompteFerme { id } => {
+                if let Ok(c) = banque.compte(*id) {
+                    let en_attente = c.obtenir_solde_comptable() - c.obtenir_solde();
+                    let mut postes = Vec::new();
+                    if c.obtenir_solde() != 0.0 {
+                        postes.push((compte(*id), Some(-c.obtenir_solde())));
+                    }
+                    if en_attente != 0.0 {
+                        postes.push((attente(*id), Some(-en_attente)));
+                    }
+                    if !postes.is_empty() {
+                        postes.push((CLOTURE.to_string(), None));
+                        sortie.push(ecriture("Clôture du compte".to_string(), Vec::new(), postes));
+                    }
+                }
+                sortie.push(Directive::Cloture { date, compte: compte(*id) });
+                if attentes.remove(id) {
+                    sortie.push(Directive::Cloture { date, compte: attente(*id) });
+                }
+            }
+            Evenement::EncaissementDepose { id, numero, libelle, montant, date_valeur } => {
+                if attentes.insert(*id) {
+                    sortie.push(Directive::Ouverture {
+                        date,
+                        compte: attente(*id),
+                        nom: None,
+                    });
+                }
+                sortie.push(ecriture(
+                    libelle.clone(),
+                    vec![
+                        ("encaissement", Valeur::Texte(numero.to_string())),
+                        ("date-valeur", Valeur::Date(*date_valeur)),
+                    ],
+                    vec![(attente(*id), Some(*montant)), (EXTERNE.to_string(), None)],
+                ));
+            }
+            Evenement::EncaissementCompense { numero } => {
+                if let Some((id, encaissement)) = trouver_encaissement(&banque, *numero) {
+                    sortie.push(ecriture(
+                        format!("Compensation: {}", encaissement.libelle),
+                        vec![("encaissement", Valeur::Texte(numero.to_string()))],
+                        vec![
+                            (compte(id), Some(encaissement.montant)),
+                            (attente(id), Some(-encaissement.montant)),
+                        ],
+                    ));
+                }
+            }
+            Evenement::EncaissementRejete { numero, frais } => {
+                if let Some((id, encaissement)) = trouver_encaissement(&banque, *numero) {
+                    let mut postes = vec![
+                        (attente(id), Some(-encaissement.montant)),
+                        (EXTERNE.to_string(), Some(encaissement.montant)),
+                    ];
+                    if *frais > 0.0 {
+                        postes.push((compte(id), Some(-frais)));
+                        postes.push((FRAIS.to_string(), Some(*frais)));
+                    }
+                    sortie.push(ecriture(
+                        format!("Rejet: {}", encaissement.libelle),
+                        vec![("encaissement", Valeur::Texte(numero.to_string()))],
+                        postes,
+                    ));
+                }
+            }
+            Evenement::RapprochementValide { id, jusqu_au } => sortie.push(Directive::Personnalisee {
+                date,
+                nom: "tp2-rapprochement",
+                valeurs: vec![Valeur::Compte(compte(*id)), Valeur::Date(*jusqu_au)],
+            }),
+            Evenement::TypeCompteChange { id, type_compte } => sortie.push(Directive::Personnalisee {
+                date,
+                nom: "tp2-type",
+                valeurs: vec![Valeur::Compte(compte(*id)), Valeur::Texte(format!("{:?}", type_compte))],
+            }),
+            Evenement::FraisPreleves { id, libelle, montant, mois } => sortie.push(ecriture(
+                libelle.clone(),
+                vec![("mois-frais", Valeur::Date(*mois))],
+                vec![(compte(*id), Some(-montant)), (FRAIS.to_string(), None)],
+            )),
+            Evenement::MoisFacture { mois } => sortie.push(Directive::Personnalisee {
+                date,
+                nom: "tp2-mois-facture",
+                valeurs: vec![Valeur::Date(*mois)],
+            }),
+            Evenement::InteretsVerses { id, montant, annee } => sortie.push(ecriture(
+                format!("Intérêts créditeurs {}", annee),
+                vec![("exercice", Valeur::Texte(annee.to_string()))],
+                vec![(compte(*id), Some(*montant)), (INTERETS.to_string(), None)],
+            )),
+            Evenement::ExerciceCloture { annee } => sortie.push(Directive::Personnalisee {
+                date,
+                nom: "tp2-cloture",
+                valeurs: vec![Valeur::Texte(annee.to_string())],
+            }),
+            Evenement::ExerciceRouvert { annee } => sortie.push(Directive::Personnalisee {
+                date,
+                nom: "tp2-reouverture",
+                valeurs: vec![Valeur::Texte(annee.to_string())],
+            }),
+            // Détail technique de l'API, sans effet sur les comptes
+            Evenement::CleEnregistree { .. } => {}
+        }
+
+        banque.appliquer(evenement);
+    }
+
+    // Soldes à la fin de l'export
+    assertions(&banque, precedent + Days::new(1), &attentes, &mut sortie);
+    sortie
+}
+
+fn valeur_beancount(valeur: &Valeur) -> String {
+    match valeur {
+        Valeur::Texte(texte) => chaine(texte),
+        Valeur::Compte(compte) => compte.clone(),
+        Valeur::Date(date) => date.to_string(),
+    }
+}
+
+fn valeur_ledger(valeur: &Valeur) -> String {
+    match valeur {
+        Valeur::Texte(texte) => texte.clone(),
+        Valeur::Compte(compte) => compte.clone(),
+        Valeur::Date(date) => date.to_string(),
+    }
+}
+
+// Fichier Beancount de tous les comptes et de toutes les opérations du journal
+pub fn exporter_beancount(evenements: &[EvenementDate]) -> String {
+    let mut texte = String::new();
+    texte.push_str("option \"title\" \"Banque tp2\"\n");
+    texte.push_str(&format!("option \"operating_currency\" \"{}\"\n", DEVISE));
+
+    for directive in directives(evenements) {
+        match directive {
+            Directive::Ouverture { date, compte, nom } => {
+                texte.push_str(&format!("\n{} open {} {}\n", date, compte, DEVISE));
+                if let Some(nom) = nom {
+                    texte.push_str(&format!("  nom: {}\n", chaine(&nom)));
+                }
+            }
+            Directive::Cloture { date, compte } => texte.push_str(&format!("\n{} close {}\n", date, compte)),
+            Directive::Ecriture { date, libelle, meta, postes } => {
+                texte.push_str(&format!("\n{} * {}\n", date, chaine(&libelle)));
+                for (cle, valeur) in meta {
+                    texte.push_str(&format!("  {}: {}\n", cle, valeur_beancount(&valeur)));
+                }
+                for (compte, valeur) in postes {
+                    match valeur {
+                        Some(valeur) => {
+                            texte.push_str(&format!("  {:<32} {:>12} {}\n", compte, montant(valeur), DEVISE))
+                        }
+                        None => texte.push_str(&format!("  {}\n", compte)),
+                    }
+                }
+            }
+            Directive::Assertion { date, compte, montant } => {
+                texte.push_str(&format!("\n{} balance {} {:.2} {}\n", date, compte, montant, DEVISE));
+            }
+            Directive::Personnalisee { date, nom, valeurs } => {
+                let valeurs: Vec<String> = valeurs.iter().map(valeur_beancount).collect();
+                texte.push_str(&format!("\n{} custom {} {}\n", date, chaine(nom), valeurs.join(" ")));
+            }
+        }
+    }
+    texte
+}
+
+// Journal Ledger-cli équivalent : déclarations de comptes, écritures et contrôles de soldes
+pub fn exporter_ledger(evenements: &[EvenementDate]) -> String {
+    let mut texte = String::from("; Banque tp2\n");
+    let mut controle: Option<NaiveDate> = None;
+
+    for directive in directives(evenements) {
+        // Les assertions d'une même date sont regroupées dans une écriture de contrôle
+        if let Directive::Assertion { date, compte, montant } = &directive {
+            if controle != Some(*date) {
+                texte.push_str(&format!("\n{} * Contrôle des soldes\n", date));
+                controle = Some(*date);
+            }
+            texte.push_str(&format!("    {:<32} 0 {} = {:.2} {}\n", compte, DEVISE, montant, DEVISE));
+            continue;
+        }
+        controle = None;
+
+        match directive {
+            Directive::Ouverture { date, compte, nom } => {
+                texte.push_str(&format!("\n; {} ouverture\naccount {}\n", date, compte));
+                if let Some(nom) = nom {
+                    texte.push_str(&format!("    note {}\n", nom));
+                }
+            }
+            Directive::Cloture { date, compte } => texte.push_str(&format!("\n; {} clôture de {}\n", date, compte)),
+            Directive::Ecriture { date, libelle, meta, postes } => {
+                texte.push_str(&format!("\n{} * {}\n", date, libelle));
+                for (cle, valeur) in meta {
+                    texte.push_str(&format!("    ; {}: {}\n", cle, valeur_ledger(&valeur)));
+                }
+                for (compte, valeur) in postes {
+                    match valeur {
+                        Some(valeur) => {
+                            texte.push_str(&format!("    {:<32} {:>12} {}\n", compte, montant(valeur), DEVISE))
+                        }
+                        None => texte.push_str(&format!("    {}\n", compte)),
+                    }
+                }
+            }
+            Directive::Personnalisee { date, nom, valeurs } => {
+                let valeurs: Vec<String> = valeurs.iter().map(valeur_ledger).collect();
+                texte.push_str(&format!("\n; {} {} {}\n", date, nom, valeurs.join(" ")));
+            }
+            Directive::Assertion { .. } => unreachable!(),
+        }
+    }
+    texte
+}
+
+// Exporte tout le journal dans un fichier : Ledger-cli pour l'extension .ledger, Beancount sinon
+pub fn exporter_fichier(journal: &Journal, destination: &Path) -> io::Result<()> {
+    let evenements = journal.evenements()?;
+    let texte = if destination.extension().is_some_and(|extension| extension == "ledger") {
+        exporter_ledger(&evenements)
+    } else {
+        exporter_beancount(&evenements)
+    };
+    fs::write(destination, texte)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErreurImport {
+    pub ligne: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ErreurImport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", tr!("import.ligne", ligne = self.ligne, message = self.message))
+    }
+}
+
+// Mot d'une ligne Beancount : chaîne entre guillemets ou mot nu
+#[derive(Debug, Clone, PartialEq)]
+enum Jeton {
+    Chaine(String),
+    Mot(String),
+}
+
+impl Jeton {
+    fn texte(&self) -> &str {
+        match self {
+            Jeton::Chaine(texte) | Jeton::Mot(texte) => texte,
+        }
+    }
+}
+
+fn decouper(ligne: &str) -> Result<Vec<Jeton>, String> {
+    let mut jetons = Vec::new();
+    let mut caracteres = ligne.chars().peekable();
+    while let Some(&c) = caracteres.peek() {
+        if c.is_whitespace() {
+            caracteres.next();
+        } else if c == ';' {
+            break;
+        } else if c == '"' {
+            caracteres.next();
+            let mut texte = String::new();
+            loop {
+                match caracteres.next() {
+                    Some('\\') => texte.extend(caracteres.next()),
+                    Some('"') => break,
+                    Some(c) => texte.push(c),
+                    None => return Err(tr!("import.guillemet_non_ferme").to_string()),
+                }
+            }
+            jetons.push(Jeton::Chaine(texte));
+        } else {
+            let mut mot = String::new();
+            while let Some(&c) = caracteres.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                mot.push(c);
+                caracteres.next();
+            }
+            jetons.push(Jeton::Mot(mot));
+        }
+    }
+    Ok(jetons)
+}
+
+// Directive datée avec ses lignes indentées (métadonnées et postes)
+struct Entree {
+    ligne: usize,
+    date: NaiveDate,
+    genre: String,
+    jetons: Vec<Jeton>,
+    meta: HashMap<String, Jeton>,
+    postes: Vec<(String, Option<f64>)>,
+}
+
+fn lire_entrees(texte: &str) -> Result<Vec<Entree>, ErreurImport> {
+    let mut entrees: Vec<Entree> = Vec::new();
+    for (index, ligne) in texte.lines().enumerate() {
+        let numero = index + 1;
+        let erreur = |message: String| ErreurImport { ligne: numero, message };
+        let contenu = ligne.trim();
+        if contenu.is_empty() || contenu.starts_with(';') {
+            continue;
+        }
+        let jetons = decouper(contenu).map_err(erreur)?;
+
+        if ligne.starts_with(char::is_whitespace) {
+            let Some(entree) = entrees.last_mut() else {
+                return Err(erreur(tr!("import.ligne_indentee").to_string()));
+            };
+            match jetons.as_slice() {
+                [Jeton::Mot(cle), valeur, ..] if cle.ends_with(':') && cle.starts_with(char::is_lowercase) => {
+                    entree.meta.insert(cle.trim_end_matches(':').to_string(), valeur.clone());
+                }
+                [Jeton::Mot(compte)] => entree.postes.push((compte.clone(), None)),
+                [Jeton::Mot(compte), Jeton::Mot(valeur), ..] => {
+                    let valeur: f64 = valeur
+                        .parse()
+                        .map_err(|_| erreur(tr!("import.montant_invalide", montant = valeur)))?;
+                    entree.postes.push((compte.clone(), Some(valeur)));
+                }
+                _ => return Err(erreur(tr!("import.ligne_non_reconnue", ligne = contenu))),
+            }
+            continue;
+        }
+
+        let premier = jetons.first().map(Jeton::texte).unwrap_or_default();
+        if matches!(premier, "option" | "plugin" | "include") {
+            continue;
+        }
+        let date = NaiveDate::parse_from_str(premier, "%Y-%m-%d")
+            .map_err(|_| erreur(tr!("import.date_attendue", texte = premier)))?;
+        let genre = jetons
+            .get(1)
+            .map(|jeton| jeton.texte().to_string())
+            .ok_or_else(|| erreur(tr!("import.directive_incomplete").to_string()))?;
+        entrees.push(Entree {
+            ligne: numero,
+            date,
+            genre,
+            jetons: jetons[2..].to_vec(),
+            meta: HashMap::new(),
+            postes: Vec::new(),
+        });
+    }
+    Ok(entrees)
+}
+
+fn id_compte(compte: &str) -> Option<u32> {
+    compte.strip_prefix(PREFIXE_COMPTE)?.parse().ok()
+}
+
+fn id_attente(compte: &str) -> Option<u32> {
+    compte.strip_prefix(PREFIXE_ATTENTE)?.parse().ok()
+}
+
+// Importe un export Beancount de la banque et retourne les événements qui la reconstruisent.
+// Les assertions de solde sont vérifiées au fur et à mesure.
+pub fn importer_beancount(texte: &str) -> Result<Vec<EvenementDate>, ErreurImport> {
+    let mut banque = Banque::new();
+    let mut evenements = Vec::new();
+    // Compte ouvert dont l'écriture de solde initial peut encore suivre
+    let mut ouverture: Option<(NaiveDate, u32, String)> = None;
+
+    let mut emettre = |banque: &mut Banque, date: NaiveDate, evenement: Evenement| {
+        let evenement = EvenementDate {
+            numero: evenements.len() as u64 + 1,
+            date: Local
+                .from_local_datetime(&date.and_hms_opt(12, 0, 0).expect("Heure valide"))
+                .earliest()
+                .unwrap_or_else(Local::now),
+            evenement,
+        };
+        banque.appliquer(&evenement);
+        evenements.push(evenement);
+    };
+
+    for entree in lire_entrees(texte)? {
+        let erreur = |message: String| ErreurImport {
+            ligne: entree.ligne,
+            message,
+        };
+        let premier_compte = entree.jetons.first().map(Jeton::texte).unwrap_or_default();
+
+        // Équilibre les postes : le montant omis est l'opposé de la somme des autres
+        let somme: f64 = entree.postes.iter().filter_map(|(_, valeur)| *valeur).sum();
+        let postes: Vec<(String, f64)> = entree
+            .postes
+            .iter()
+            .map(|(compte, valeur)| (compte.clone(), valeur.unwrap_or(-somme)))
+            .collect();
+        let poste = |compte: &str| postes.iter().find(|(c, _)| c == compte).map(|(_, valeur)| *valeur);
+        let comptes: Vec<(u32, f64)> = postes
+            .iter()
+            .filter_map(|(compte, valeur)| id_compte(compte).map(|id| (id, *valeur)))
+            .collect();
+        let attentes: Vec<(u32, f64)> = postes
+            .iter()
+            .filter_map(|(compte, valeur)| id_attente(compte).map(|id| (id, *valeur)))
+            .collect();
+        let libelle = entree
+            .jetons
+            .iter()
+            .rev()
+            .find_map(|jeton| match jeton {
+                Jeton::Chaine(texte) => Some(texte.clone()),
+                Jeton::Mot(_) => None,
+            })
+            .unwrap_or_default();
+        let numero_encaissement = || -> Result<u32, ErreurImport> {
+            entree
+                .meta
+                .get("encaissement")
+                .and_then(|jeton| jeton.texte().parse().ok())
+                .ok_or_else(|| erreur(tr!("import.metadonnee_manquante", cle = "encaissement")))
+        };
+        let date_meta = |cle: &str| {
+            entree
+                .meta
+                .get(cle)
+                .and_then(|jeton| NaiveDate::parse_from_str(jeton.texte(), "%Y-%m-%d").ok())
+        };
+
+        // Le solde initial suit directement l'ouverture du compte
+        if let Some((date, id, nom)) = ouverture.take() {
+            if entree.genre == "*" && comptes.len() == 1 && comptes[0].0 == id && poste(OUVERTURE).is_some() {
+                emettre(&mut banque, date, Evenement::CompteOuvert { id, nom, solde: comptes[0].1 });
+                continue;
+            }
+            emettre(&mut banque, date, Evenement::CompteOuvert { id, nom, solde: 0.0 });
+        }
+
+        match entree.genre.as_str() {
+            "open" => {
+                if let Some(id) = id_compte(premier_compte) {
+                    let nom = entree
+                        .meta
+                        .get("nom")
+                        .map(|jeton| jeton.texte().to_string())
+                        .unwrap_or_else(|| format!("Compte {}", id));
+                    ouverture = Some((entree.date, id, nom));
+                }
+            }
+            "close" => {
+                if let Some(id) = id_compte(premier_compte) {
+                    emettre(&mut banque, entree.date, Evenement::CompteFerme { id });
+                }
+            }
+            "balance" => {
+                let attendu: f64 = entree
+                    .jetons
+                    .get(1)
+                    .and_then(|jeton| jeton.texte().parse().ok())
+                    .ok_or_else(|| erreur(tr!("import.assertion_invalide").to_string()))?;
+                let trouve = if let Some(id) = id_compte(premier_compte) {
+                    banque.compte(id).map(|c| c.obtenir_solde())
+                } else if let Some(id) = id_attente(premier_compte) {
+                    banque.compte(id).map(|c| c.obtenir_solde_comptable() - c.obtenir_solde())
+                } else {
+                    continue;
+                }
+                .map_err(|e| erreur(e.to_string()))?;
+                if (trouve - attendu).abs() >= 0.005 {
+                    return Err(erreur(tr!(
+                        "import.solde_faux",
+                        compte = premier_compte,
+                        attendu = format!("{:.2}", attendu),
+                        trouve = format!("{:.2}", trouve)
+                    )));
+                }
+            }
+            "custom" => {
+                let id = entree.jetons.get(1).and_then(|jeton| id_compte(jeton.texte()));
+                let argument = entree.jetons.get(2).map(Jeton::texte).unwrap_or_default();
+                let annee = || -> Result<i32, ErreurImport> {
+                    let annee = entree.jetons.get(1).map(Jeton::texte).unwrap_or_default();
+                    annee.parse().map_err(|_| erreur(tr!("import.annee_invalide", annee = annee)))
+                };
+                let evenement = match (premier_compte, id) {
+                    ("tp2-renommage", Some(id)) => Evenement::CompteRenomme {
+                        id,
+                        nom: argument.to_string(),
+                    },
+                    ("tp2-type", Some(id)) => {
+                        let type_compte = TypeCompte::TOUS
+                            .into_iter()
+                            .find(|type_compte| format!("{:?}", type_compte) == argument)
+                            .ok_or_else(|| erreur(tr!("import.type_inconnu", type_compte = argument)))?;
+                        Evenement::TypeCompteChange { id, type_compte }
+                    }
+                    ("tp2-rapprochement", Some(id)) => Evenement::RapprochementValide {
+                        id,
+                        jusqu_au: NaiveDate::parse_from_str(argument, "%Y-%m-%d")
+                            .map_err(|_| erreur(tr!("import.date_invalide", date = argument)))?,
+                    },
+                    ("tp2-mois-facture", _) => {
+                        let mois = entree.jetons.get(1).map(Jeton::texte).unwrap_or_default();
+                        Evenement::MoisFacture {
+                            mois: NaiveDate::parse_from_str(mois, "%Y-%m-%d")
+                                .map_err(|_| erreur(tr!("import.date_invalide", date = mois)))?,
+                        }
+                    }
+                    ("tp2-cloture", _) => Evenement::ExerciceCloture { annee: annee()? },
+                    ("tp2-reouverture", _) => Evenement::ExerciceRouvert { annee: annee()? },
+                    _ => continue,
+                };
+                emettre(&mut banque, entree.date, evenement);
+            }
+            "*" | "!" | "txn" => {
+                let evenement = if poste(CLOTURE).is_some() {
+                    // Solde d'un compte supprimé : la directive close qui suit suffit
+                    continue;
+                } else if let [(id, valeur)] = attentes[..] {
+                    if valeur > 0.0 && poste(EXTERNE).is_some() {
+                        Evenement::EncaissementDepose {
+                            id,
+                            numero: numero_encaissement()?,
+                            libelle: libelle.clone(),
+                            montant: valeur,
+                            date_valeur: date_meta("date-valeur")
+                                .ok_or_else(|| erreur(tr!("import.metadonnee_manquante", cle = "date-valeur")))?,
+                        }
+                    } else if poste(EXTERNE).is_some() {
+                        Evenement::EncaissementRejete {
+                            numero: numero_encaissement()?,
+                            frais: poste(FRAIS).unwrap_or(0.0),
+                        }
+                    } else {
+                        Evenement::EncaissementCompense {
+                            numero: numero_encaissement()?,
+                        }
+                    }
+                } else if let (Some(_), [(id, valeur)]) = (poste(INTERETS), &comptes[..]) {
+                    Evenement::InteretsVerses {
+                        id: *id,
+                        montant: *valeur,
+                        annee: entree
+                            .meta
+                            .get("exercice")
+                            .and_then(|jeton| jeton.texte().parse().ok())
+                            .unwrap_or(entree.date.year() - 1),
+                    }
+                } else if let (Some(frais), [(id, _)]) = (poste(FRAIS), &comptes[..]) {
+                    Evenement::FraisPreleves {
+                        id: *id,
+                        libelle: libelle.clone(),
+                        montant: frais,
+                        mois: date_meta("mois-frais").unwrap_or(frais::debut_du_mois(entree.date)),
+                    }
+                } else if let [(a, montant_a), (b, _)] = comptes[..] {
+                    let (source, destination) = if montant_a < 0.0 { (a, b) } else { (b, a) };
+                    Evenement::Transfert {
+                        source,
+                        destination,
+                        montant: montant_a.abs(),
+                    }
+                } else if let [(id, valeur)] = comptes[..]
+                    && (poste(EXTERNE).is_some() || poste(OUVERTURE).is_some())
+                {
+                    if valeur >= 0.0 {
+                        Evenement::Depot { id, montant: valeur }
+                    } else {
+                        Evenement::Retrait { id, montant: -valeur }
+                    }
+                } else {
+                    return Err(erreur(tr!("import.ecriture_non_reconnue", libelle = libelle)));
+                };
+                emettre(&mut banque, entree.date, evenement);
+            }
+            _ => {}
+        }
+    }
+    if let Some((date, id, nom)) = ouverture {
+        emettre(&mut banque, date, Evenement::CompteOuvert { id, nom, solde: 0.0 });
+    }
+    Ok(evenements)
+}
@@ -0,0 +1,10 @@
+// Bibliothèque du TP2 : le modèle bancaire commun (crate banque_commune, partagé avec le tp1)
+// et les interfaces propres au TP2
+pub use banque_commune::{banque, cloture, compte, evenement, frais, journal, langue, notification, tr};
+
+pub mod api;
+pub mod comptabilite;
+pub mod menu;
+pub mod rapprochement;
+pub mod script;
+pub mod tui;
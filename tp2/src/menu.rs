@@ -0,0 +1,1044 @@
+use crate::banque::{Banque, ErreurBanque, FRAIS_REJET, Operation, ResultatOperation};
+use crate::compte::{CompteBancaire, TypeCompte};
+use crate::frais::{self, LigneFrais};
+use chrono::{Datelike, Days, Local, NaiveDate, TimeZone};
+use saisie::{ErreurSaisie, Lecteur, Resultat};
+use std::io::Write;
+use crate::cloture;
+use crate::comptabilite;
+use crate::journal::{ErreurJournal, Journal};
+use crate::{langue, tr};
+use crate::rapprochement::{self, Rapprochement};
+
+// Entrée et sortie du menu : la console de la crate saisie (stdin/stdout en temps normal, des tampons
+// en mémoire dans les tests)
+pub use saisie::Console;
+
+// Équivalents de println! et print! qui écrivent sur la sortie de la console
+macro_rules! afficher {
+    ($console:expr, $($arg:tt)*) => {{
+        let _ = writeln!($console.sortie, $($arg)*);
+    }};
+}
+
+macro_rules! ecrire {
+    ($console:expr, $($arg:tt)*) => {{
+        let _ = write!($console.sortie, $($arg)*);
+    }};
+}
+
+// Affiche un message du catalogue : dire!(console, "cle") ou dire!(console, "cle", nom = valeur)
+macro_rules! dire {
+    ($console:expr, $($arg:tt)*) => {{
+        let _ = writeln!($console.sortie, "{}", tr!($($arg)*));
+    }};
+}
+
+// Boucle du menu texte. Si un journal est donné, les événements y sont enregistrés après chaque choix.
+pub fn lancer(
+    console: &mut Console<impl Lecteur, impl Write>,
+    banque: &mut Banque,
+    mut journal: Option<&mut Journal>,
+) {
+    let options = [
+        tr!("menu.afficher_solde"),
+        tr!("menu.retrait"),
+        tr!("menu.depot"),
+        tr!("menu.renommer"),
+        tr!("menu.liste"),
+        tr!("menu.creer"),
+        tr!("menu.supprimer"),
+        tr!("menu.transfert"),
+        tr!("menu.rechercher"),
+        tr!("menu.statistiques"),
+        tr!("menu.depot_en_attente"),
+        tr!("menu.encaissements"),
+        tr!("menu.soldes_passes"),
+        tr!("menu.rapprochement"),
+        tr!("menu.frais"),
+        tr!("menu.exporter"),
+        tr!("menu.cloture"),
+        tr!("menu.quitter"),
+    ];
+
+    loop {
+        // Les opérations des autres utilisateurs du même journal sont prises en compte avant chaque choix
+        if let Some(journal) = journal.as_deref_mut()
+            && let Err(e) = journal.rafraichir(banque)
+        {
+            dire!(console, "journal.relecture_impossible", chemin = journal.chemin().display(), erreur = e);
+        }
+
+        // Les encaissements arrivés à leur date de valeur deviennent disponibles (sauf en lecture seule)
+        let lecture_seule = journal.as_deref().is_some_and(Journal::est_lecture_seule);
+        let compenses = if lecture_seule {
+            Vec::new()
+        } else {
+            banque.compenser_encaissements(Local::now().date_naive())
+        };
+        for encaissement in &compenses {
+            dire!(
+                console,
+                "encaissement.disponible",
+                libelle = encaissement.libelle,
+                montant = langue::montant(encaissement.montant)
+            );
+        }
+
+        // Afficher le menu
+        dire!(console, "menu.titre");
+        for (index, option) in options.iter().enumerate() {
+            afficher!(console, "{}. {}", index + 1, option);
+        }
+
+        // Lire le choix et le traiter ; la fin de l'entrée quitte le menu
+        let invite = format!("{} ", tr!("menu.choisir_option", nombre = options.len()));
+        let resultat = console
+            .entier(&invite, 1..=options.len())
+            .and_then(|choix| traiter_choix(choix, console, banque, journal.as_deref()));
+
+        // Enregistrer les événements après chaque opération
+        if let Some(journal) = journal.as_deref_mut() {
+            enregistrer(console, banque, journal);
+        }
+
+        match resultat {
+            Ok(Action::Continuer) => {}
+            Ok(Action::Quitter) | Err(ErreurSaisie::FinDeSaisie) => {
+                dire!(console, "menu.au_revoir");
+                break;
+            }
+            Err(e) => {
+                let erreur = console.messages().erreur(&e);
+                dire!(console, "menu.saisie_abandonnee", erreur = erreur);
+            }
+        }
+    }
+}
+
+enum Action {
+    Continuer,
+    Quitter,
+}
+
+// Exécute une option du menu ; une saisie interrompue (fin de l'entrée, trop d'essais) remonte en erreur
+fn traiter_choix(
+    choix: usize,
+    console: &mut Console<impl Lecteur, impl Write>,
+    banque: &mut Banque,
+    journal: Option<&Journal>,
+) -> Resultat<Action> {
+    match choix {
+        1 => afficher_solde(console, &banque.comptes)?,
+        2 => effectuer_retrait(console, banque)?,
+        3 => effectuer_depot(console, banque)?,
+        4 => renommer_compte(console, banque)?,
+        5 => lister_comptes(console, &banque.comptes),
+        6 => creer_nouveau_compte(console, banque)?,
+        7 => supprimer_compte(console, banque)?,
+        8 => transferer_fonds(console, banque)?,
+        9 => rechercher_compte(console, &banque.comptes)?,
+        10 => afficher_statistiques(console, &banque.comptes),
+        11 => effectuer_depot_en_attente(console, banque)?,
+        12 => gerer_encaissements(console, banque)?,
+        13 => afficher_soldes_passes(console, journal)?,
+        14 => rapprocher_compte(console, banque)?,
+        15 => gerer_frais(console, banque)?,
+        16 => exporter_comptabilite(console, journal)?,
+        17 => gerer_exercices(console, banque)?,
+        _ => return Ok(Action::Quitter),
+    }
+    Ok(Action::Continuer)
+}
+
+// Enregistre les événements de l'opération. Si un autre utilisateur a modifié les données entre-temps
+// (ou en lecture seule), l'opération est abandonnée et les données à jour sont rechargées.
+fn enregistrer(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque, journal: &mut Journal) {
+    let erreur = match journal.enregistrer(banque) {
+        Ok(()) => return,
+        Err(ErreurJournal::Io(e)) => {
+            dire!(console, "journal.enregistrement_impossible", chemin = journal.chemin().display(), erreur = e);
+            return;
+        }
+        Err(erreur) => erreur,
+    };
+    dire!(console, "journal.operation_non_enregistree", erreur = erreur);
+    match journal.recharger(banque) {
+        Ok(()) => dire!(console, "journal.donnees_rechargees"),
+        Err(e) => dire!(console, "journal.relecture_impossible", chemin = journal.chemin().display(), erreur = e),
+    }
+}
+
+// Position (à partir de 0) d'un élément choisi par son numéro dans une liste déjà affichée, hors `exclu`.
+// Une réponse invalide affiche `erreur` et est redemandée.
+fn lire_choix(
+    console: &mut Console<impl Lecteur, impl Write>,
+    nombre: usize,
+    erreur: &str,
+    exclu: Option<usize>,
+) -> Resultat<usize> {
+    console.valeur("", erreur, |texte| {
+        let num = texte.parse::<usize>().ok().filter(|num| (1..=nombre).contains(num))?;
+        (Some(num - 1) != exclu).then_some(num - 1)
+    })
+}
+
+// Date JJ/MM/AAAA, `par_defaut` si la réponse est vide
+fn lire_date(console: &mut Console<impl Lecteur, impl Write>, par_defaut: Option<NaiveDate>) -> Resultat<NaiveDate> {
+    console.valeur("", tr!("commun.date_invalide"), |texte| match par_defaut {
+        Some(date) if texte.is_empty() => Some(date),
+        _ => NaiveDate::parse_from_str(texte, "%d/%m/%Y").ok(),
+    })
+}
+
+// Fonction pour afficher le solde d'un compte
+fn afficher_solde(console: &mut Console<impl Lecteur, impl Write>, comptes: &[CompteBancaire]) -> Resultat<()> {
+    dire!(console, "solde.titre");
+
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return Ok(());
+    }
+
+    dire!(console, "commun.choisir_compte");
+    // Points bonus: utilisation de .iter() et .enumerate()
+    for (index, compte) in comptes.iter().enumerate() {
+        afficher!(console, "{}. {}", index + 1, compte.obtenir_nom());
+    }
+
+    let choix = lire_choix(console, comptes.len(), tr!("commun.choix_invalide"), None)?;
+
+    let compte = &comptes[choix];
+    dire!(
+        console,
+        "solde.de",
+        nom = compte.obtenir_nom(),
+        solde = langue::montant(compte.obtenir_solde())
+    );
+
+    // Afficher si le compte est en découvert
+    if compte.est_en_decouvert() {
+        dire!(console, "solde.decouvert");
+    }
+    if !compte.en_attente().is_empty() {
+        dire!(
+            console,
+            "solde.comptable",
+            solde = langue::montant(compte.obtenir_solde_comptable())
+        );
+    }
+    Ok(())
+}
+
+// Délai par défaut avant qu'un chèque déposé soit disponible
+const DELAI_ENCAISSEMENT_JOURS: u64 = 2;
+
+// Dépôt d'un chèque ou d'un virement entrant, disponible seulement à sa date de valeur
+fn effectuer_depot_en_attente(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "attente.titre");
+    let comptes = &banque.comptes;
+
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return Ok(());
+    }
+
+    dire!(console, "commun.choisir_compte");
+    for (index, compte) in comptes.iter().enumerate() {
+        afficher!(console, "{}. {}", index + 1, compte.obtenir_nom());
+    }
+
+    let choix = lire_choix(console, comptes.len(), tr!("commun.choix_invalide"), None)?;
+    let id = comptes[choix].obtenir_id();
+
+    dire!(console, "attente.type");
+    let libelle = ["Chèque", "Virement entrant"][console.entier("", 1..=2)? - 1];
+
+    dire!(console, "attente.montant");
+    let montant = console.montant("")?;
+
+    let par_defaut = Local::now().date_naive() + Days::new(DELAI_ENCAISSEMENT_JOURS);
+    dire!(console, "attente.date_valeur", date = par_defaut.format("%d/%m/%Y"));
+    let date_valeur = lire_date(console, Some(par_defaut))?;
+
+    match banque.deposer_en_attente(id, montant, date_valeur, libelle) {
+        Ok(numero) => dire!(
+            console,
+            "attente.enregistre",
+            libelle = libelle,
+            numero = numero,
+            montant = langue::montant(montant),
+            date = date_valeur.format("%d/%m/%Y")
+        ),
+        Err(e) => afficher!(console, "{}", e),
+    }
+    Ok(())
+}
+
+// Liste les encaissements en attente et permet d'en rejeter un (impayé)
+fn gerer_encaissements(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "encaissement.titre");
+
+    let mut aucun = true;
+    for compte in &banque.comptes {
+        for encaissement in compte.en_attente() {
+            aucun = false;
+            dire!(
+                console,
+                "encaissement.ligne",
+                numero = encaissement.numero,
+                nom = compte.obtenir_nom(),
+                libelle = encaissement.libelle,
+                montant = langue::montant(encaissement.montant),
+                date = encaissement.date_valeur.format("%d/%m/%Y")
+            );
+        }
+    }
+    if aucun {
+        dire!(console, "encaissement.aucun");
+        return Ok(());
+    }
+
+    dire!(console, "encaissement.numero_rejete", frais = langue::montant(FRAIS_REJET));
+    // Une réponse vide ne rejette rien
+    let numero = console.valeur("", tr!("commun.choix_invalide"), |texte| {
+        if texte.is_empty() { Some(None) } else { texte.parse::<u32>().ok().map(Some) }
+    })?;
+    let Some(numero) = numero else {
+        return Ok(());
+    };
+
+    match banque.rejeter_encaissement(numero) {
+        Ok(encaissement) => dire!(
+            console,
+            "encaissement.rejete",
+            libelle = encaissement.libelle,
+            numero = encaissement.numero,
+            montant = langue::montant(encaissement.montant),
+            frais = langue::montant(FRAIS_REJET)
+        ),
+        Err(e) => afficher!(console, "{}", e),
+    }
+    Ok(())
+}
+
+// Compare les transactions d'un compte au relevé CSV de la banque et verrouille la période rapprochée
+fn rapprocher_compte(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "rapprochement.titre");
+    let comptes = &banque.comptes;
+
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return Ok(());
+    }
+
+    dire!(console, "commun.choisir_compte");
+    for (index, compte) in comptes.iter().enumerate() {
+        afficher!(console, "{}. {}", index + 1, compte.obtenir_nom());
+    }
+
+    let choix = lire_choix(console, comptes.len(), tr!("commun.choix_invalide"), None)?;
+    let compte = &comptes[choix];
+    if let Some(date) = compte.rapproche_jusqu_au() {
+        dire!(console, "rapprochement.deja", date = date.format("%d/%m/%Y"));
+    }
+
+    dire!(console, "rapprochement.fichier");
+    let chemin = console.texte("")?;
+    let releve = match rapprochement::charger_releve(std::path::Path::new(&chemin)) {
+        Ok(releve) => releve,
+        Err(e) => {
+            afficher!(console, "{}", e);
+            return Ok(());
+        }
+    };
+    let mut seance = match Rapprochement::nouveau(banque, compte.obtenir_id(), releve) {
+        Ok(seance) => seance,
+        Err(e) => {
+            afficher!(console, "{}", e);
+            return Ok(());
+        }
+    };
+    let trouvees = seance.rapprocher_automatiquement();
+    dire!(console, "rapprochement.automatique", nombre = trouvees);
+
+    loop {
+        afficher_rapprochement(console, &seance);
+        dire!(console, "rapprochement.actions");
+        match console.entier("", 1..=4)? {
+            1 => {
+                dire!(console, "rapprochement.ligne");
+                let ligne = console.entier("", 1..=seance.releve().len())?;
+                dire!(console, "rapprochement.transaction");
+                let transaction = console.entier("", 1..=seance.transactions().len())?;
+                match seance.associer(ligne - 1, transaction - 1) {
+                    Ok(()) => dire!(console, "rapprochement.associee", ligne = ligne, transaction = transaction),
+                    Err(e) => afficher!(console, "{}", e),
+                }
+            }
+            2 => {
+                dire!(console, "rapprochement.ligne");
+                let ligne = console.entier("", 1..=seance.releve().len())?;
+                match seance.dissocier(ligne - 1) {
+                    Ok(()) => dire!(console, "rapprochement.dissociee", ligne = ligne),
+                    Err(e) => afficher!(console, "{}", e),
+                }
+            }
+            3 => {
+                let Some(par_defaut) = seance.derniere_date() else {
+                    dire!(console, "rapprochement.releve_vide");
+                    continue;
+                };
+                dire!(console, "rapprochement.jusqu_au", date = par_defaut.format("%d/%m/%Y"));
+                let jusqu_au = lire_date(console, Some(par_defaut))?;
+                match seance.valider(banque, jusqu_au) {
+                    Ok(()) => {
+                        dire!(console, "rapprochement.valide", date = jusqu_au.format("%d/%m/%Y"));
+                        return Ok(());
+                    }
+                    Err(e) => afficher!(console, "{}", e),
+                }
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+// Affiche les éléments non rapprochés des deux côtés
+fn afficher_rapprochement(console: &mut Console<impl Lecteur, impl Write>, seance: &Rapprochement) {
+    dire!(
+        console,
+        "rapprochement.bilan",
+        paires = seance.correspondances().len(),
+        lignes = seance.releve().len()
+    );
+
+    let lignes = seance.lignes_non_rapprochees();
+    dire!(console, "rapprochement.releve_restant");
+    if lignes.is_empty() {
+        dire!(console, "rapprochement.aucune_ligne");
+    }
+    for ligne in lignes {
+        let l = &seance.releve()[ligne];
+        afficher!(
+            console,
+            "  R{} - {} - {} : {}",
+            ligne + 1,
+            l.date.format("%d/%m/%Y"),
+            l.libelle,
+            langue::montant(l.montant)
+        );
+    }
+
+    let transactions = seance.transactions_non_rapprochees();
+    dire!(console, "rapprochement.transactions_restantes");
+    if transactions.is_empty() {
+        dire!(console, "rapprochement.aucune_transaction");
+    }
+    for transaction in transactions {
+        let t = &seance.transactions()[transaction];
+        afficher!(
+            console,
+            "  T{} - {} - {} : {}",
+            transaction + 1,
+            t.date.format("%d/%m/%Y"),
+            t.libelle,
+            langue::montant(t.montant)
+        );
+    }
+}
+
+// Prélèvement et récapitulatif des frais mensuels, type des comptes
+fn gerer_frais(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "frais.titre");
+    dire!(console, "frais.prelever");
+    dire!(console, "frais.recapitulatif");
+    dire!(console, "frais.changer_type");
+
+    match console.entier("", 1..=3)? {
+        1 => {
+            let mois = lire_mois(console)?;
+            match banque.prelever_frais(mois) {
+                Ok(lignes) => afficher_recapitulatif_frais(console, &banque.comptes, mois, &lignes),
+                Err(e) => afficher!(console, "{}", e),
+            }
+        }
+        2 => {
+            let mois = lire_mois(console)?;
+            let lignes = banque.recapitulatif_frais(mois);
+            afficher_recapitulatif_frais(console, &banque.comptes, mois, &lignes);
+        }
+        _ => changer_type_compte(console, banque)?,
+    }
+    Ok(())
+}
+
+// Demande un mois (MM/AAAA), le mois précédent par défaut
+fn lire_mois(console: &mut Console<impl Lecteur, impl Write>) -> Resultat<NaiveDate> {
+    let debut = frais::debut_du_mois(Local::now().date_naive());
+    let precedent = frais::debut_du_mois(debut.pred_opt().expect("Date valide"));
+    dire!(console, "frais.mois", mois = precedent.format("%m/%Y"));
+    console.valeur("", tr!("frais.mois_invalide"), |texte| {
+        if texte.is_empty() {
+            return Some(precedent);
+        }
+        NaiveDate::parse_from_str(&format!("01/{}", texte), "%d/%m/%Y").ok()
+    })
+}
+
+fn afficher_recapitulatif_frais(
+    console: &mut Console<impl Lecteur, impl Write>,
+    comptes: &[CompteBancaire],
+    mois: NaiveDate,
+    lignes: &[LigneFrais],
+) {
+    dire!(console, "frais.du_mois", mois = mois.format("%m/%Y"));
+    for compte in comptes {
+        let frais: Vec<&LigneFrais> = lignes.iter().filter(|ligne| ligne.id == compte.obtenir_id()).collect();
+        if frais.is_empty() {
+            dire!(console, "frais.aucun", nom = compte.obtenir_nom(), type_compte = compte.type_compte());
+            continue;
+        }
+        afficher!(console, "  {} ({}):", compte.obtenir_nom(), compte.type_compte());
+        for ligne in &frais {
+            afficher!(console, "    {}: {}", ligne.libelle, langue::montant(ligne.montant));
+        }
+        let total: f64 = frais.iter().map(|ligne| ligne.montant).sum();
+        dire!(console, "frais.total_compte", total = langue::montant(total));
+    }
+    let total: f64 = lignes.iter().map(|ligne| ligne.montant).sum();
+    dire!(console, "frais.total_mois", total = langue::montant(total));
+}
+
+fn changer_type_compte(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    let comptes = &banque.comptes;
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return Ok(());
+    }
+
+    dire!(console, "commun.choisir_compte");
+    for (index, compte) in comptes.iter().enumerate() {
+        afficher!(console, "{}. {} ({})", index + 1, compte.obtenir_nom(), compte.type_compte());
+    }
+    let choix = lire_choix(console, comptes.len(), tr!("commun.choix_invalide"), None)?;
+    let id = comptes[choix].obtenir_id();
+
+    dire!(console, "frais.nouveau_type");
+    for (index, type_compte) in TypeCompte::TOUS.iter().enumerate() {
+        let tarif = banque.tarifs().tarif(*type_compte);
+        dire!(
+            console,
+            "frais.tarif",
+            numero = index + 1,
+            type_compte = type_compte,
+            frais_mensuels = langue::montant(tarif.frais_mensuels),
+            gratuites = tarif.operations_gratuites,
+            frais_par_operation = langue::montant(tarif.frais_par_operation),
+            taux_decouvert = format!("{:.1}", tarif.taux_decouvert * 100.0),
+            taux_remuneration = format!("{:.1}", tarif.taux_remuneration * 100.0)
+        );
+    }
+    let choix = lire_choix(console, TypeCompte::TOUS.len(), tr!("commun.choix_invalide"), None)?;
+    let type_compte = TypeCompte::TOUS[choix];
+
+    match banque.changer_type_compte(id, type_compte) {
+        Ok(()) => dire!(console, "frais.type_change", type_compte = type_compte.to_string().to_lowercase()),
+        Err(e) => afficher!(console, "{}", e),
+    }
+    Ok(())
+}
+
+fn gerer_exercices(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "cloture.titre");
+    dire!(console, "cloture.cloturer");
+    dire!(console, "cloture.recapitulatif");
+    dire!(console, "cloture.rouvrir");
+
+    match console.entier("", 1..=3)? {
+        1 => {
+            let annee = lire_annee(console)?;
+            match banque.cloturer_exercice(annee) {
+                Ok(rapport) => {
+                    ecrire!(console, "\n{}", rapport.en_texte());
+                    dire!(console, "cloture.cloture", annee = annee);
+                }
+                Err(e) => afficher!(console, "{}", e),
+            }
+        }
+        2 => {
+            let annee = lire_annee(console)?;
+            let rapport = cloture::rapport_fiscal(&banque.comptes, annee);
+            ecrire!(console, "\n{}", rapport.en_texte());
+            dire!(console, "cloture.fichier_csv");
+            let input = console.demander("")?;
+            if !input.trim().is_empty() {
+                match std::fs::write(input.trim(), rapport.en_csv()) {
+                    Ok(()) => dire!(console, "cloture.recapitulatif_enregistre", chemin = input.trim()),
+                    Err(e) => dire!(console, "commun.ecriture_impossible", chemin = input.trim(), erreur = e),
+                }
+            }
+        }
+        _ => {
+            let annee = lire_annee(console)?;
+            dire!(console, "cloture.jeton");
+            let jeton = console.demander("")?;
+            match banque.rouvrir_exercice(annee, jeton.trim()) {
+                Ok(()) => dire!(console, "cloture.rouvert", annee = annee),
+                Err(e) => afficher!(console, "{}", e),
+            }
+        }
+    }
+    Ok(())
+}
+
+// Demande une année, l'année précédente par défaut
+fn lire_annee(console: &mut Console<impl Lecteur, impl Write>) -> Resultat<i32> {
+    let precedente = Local::now().year() - 1;
+    dire!(console, "cloture.annee", annee = precedente);
+    console.valeur("", tr!("cloture.annee_invalide"), |texte| {
+        if texte.is_empty() { Some(precedente) } else { texte.parse().ok() }
+    })
+}
+
+// Demande une clé d'idempotence facultative pour pouvoir rejouer une opération sans la doubler
+fn lire_cle_idempotence(console: &mut Console<impl Lecteur, impl Write>) -> Resultat<Option<String>> {
+    dire!(console, "idempotence.cle");
+    let cle = console.demander("")?;
+
+    let cle = cle.trim();
+    Ok(if cle.is_empty() { None } else { Some(cle.to_string()) })
+}
+
+fn signaler_rejeu(console: &mut Console<impl Lecteur, impl Write>, rejouee: bool) {
+    if rejouee {
+        dire!(console, "idempotence.rejeu");
+    }
+}
+
+// Fonction pour effectuer un retrait
+fn effectuer_retrait(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "retrait.titre");
+    let comptes = &banque.comptes;
+
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return Ok(());
+    }
+
+    dire!(console, "commun.choisir_compte");
+    // Points bonus: utilisation de .iter() et .enumerate()
+    for (index, compte) in comptes.iter().enumerate() {
+        dire!(
+            console,
+            "commun.compte_et_solde",
+            numero = index + 1,
+            nom = compte.obtenir_nom(),
+            solde = langue::montant(compte.obtenir_solde())
+        );
+    }
+
+    let choix = lire_choix(console, comptes.len(), tr!("commun.choix_invalide"), None)?;
+
+    dire!(console, "retrait.montant");
+    let montant = console.montant("")?;
+
+    let cle = lire_cle_idempotence(console)?;
+
+    // La banque utilise la méthode retrait de la struct
+    let execution = banque.executer(Operation::Retrait { id: comptes[choix].obtenir_id(), montant }, cle.as_deref());
+    signaler_rejeu(console, execution.rejouee);
+    match execution.resultat {
+        Ok(ResultatOperation::Retrait { solde, .. }) => {
+            dire!(console, "retrait.effectue", montant = langue::montant(montant));
+            dire!(console, "commun.nouveau_solde", solde = langue::montant(solde));
+        }
+        Err(e @ ErreurBanque::CleDejaUtilisee(_)) => afficher!(console, "{}", e),
+        _ => dire!(console, "retrait.impossible"),
+    }
+    Ok(())
+}
+
+// Points bonus: Fonction pour effectuer un dépôt (empêche les montants négatifs)
+fn effectuer_depot(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "depot.titre");
+    let comptes = &banque.comptes;
+
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return Ok(());
+    }
+
+    dire!(console, "commun.choisir_compte");
+    for (index, compte) in comptes.iter().enumerate() {
+        dire!(
+            console,
+            "commun.compte_et_solde",
+            numero = index + 1,
+            nom = compte.obtenir_nom(),
+            solde = langue::montant(compte.obtenir_solde())
+        );
+    }
+
+    let choix = lire_choix(console, comptes.len(), tr!("commun.choix_invalide"), None)?;
+
+    dire!(console, "depot.montant");
+    let montant = console.montant("")?;
+
+    let cle = lire_cle_idempotence(console)?;
+
+    // La banque utilise la méthode depot qui empêche les montants négatifs
+    let execution = banque.executer(Operation::Depot { id: comptes[choix].obtenir_id(), montant }, cle.as_deref());
+    signaler_rejeu(console, execution.rejouee);
+    match execution.resultat {
+        Ok(ResultatOperation::Depot { solde, .. }) => {
+            dire!(console, "depot.effectue", montant = langue::montant(montant));
+            dire!(console, "commun.nouveau_solde", solde = langue::montant(solde));
+        }
+        Err(e @ ErreurBanque::CleDejaUtilisee(_)) => afficher!(console, "{}", e),
+        _ => dire!(console, "depot.impossible"),
+    }
+    Ok(())
+}
+
+// Points bonus: Fonction pour renommer un compte
+fn renommer_compte(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "renommer.titre");
+    let comptes = &banque.comptes;
+
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return Ok(());
+    }
+
+    dire!(console, "renommer.choisir");
+    for (index, compte) in comptes.iter().enumerate() {
+        afficher!(console, "{}. {}", index + 1, compte.obtenir_nom());
+    }
+
+    let choix = lire_choix(console, comptes.len(), tr!("commun.choix_invalide"), None)?;
+
+    dire!(console, "renommer.nom");
+    let nouveau_nom = console.demander("")?;
+
+    let nouveau_nom = nouveau_nom.trim().to_string();
+
+    // La banque utilise la méthode renommer qui renvoie un nouveau compte
+    match banque.renommer(comptes[choix].obtenir_id(), &nouveau_nom) {
+        Ok(()) => dire!(console, "renommer.effectue", nom = nouveau_nom),
+        Err(e) => afficher!(console, "{}", e),
+    }
+    Ok(())
+}
+
+// Nouvelle fonction pour créer un nouveau compte
+fn creer_nouveau_compte(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "creer.titre");
+
+    dire!(console, "creer.nom");
+    let nom = console.demander("")?;
+    
+    let nom = nom.trim().to_string();
+    
+    if nom.is_empty() {
+        dire!(console, "creer.nom_vide");
+        return Ok(());
+    }
+
+    // Vérifier si le nom existe déjà
+    if banque.comptes.iter().any(|compte| compte.obtenir_nom() == nom) {
+        dire!(console, "creer.nom_existant");
+        return Ok(());
+    }
+
+    dire!(console, "creer.solde");
+    let virgule_des_milliers = console.messages().virgule_des_milliers;
+    let solde = console.valeur("", tr!("creer.solde_invalide"), |texte| {
+        saisie::lire_montant(texte, virgule_des_milliers).filter(|solde| *solde >= 0.0)
+    })?;
+
+    match banque.ouvrir_compte(&nom, solde) {
+        Ok(_) => dire!(console, "creer.effectue", nom = nom, solde = langue::montant(solde)),
+        Err(e) => afficher!(console, "{}", e),
+    }
+    Ok(())
+}
+
+// Nouvelle fonction pour supprimer un compte
+fn supprimer_compte(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "supprimer.titre");
+    let comptes = &banque.comptes;
+
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return Ok(());
+    }
+
+    dire!(console, "supprimer.choisir");
+    for (index, compte) in comptes.iter().enumerate() {
+        dire!(
+            console,
+            "commun.compte_et_solde",
+            numero = index + 1,
+            nom = compte.obtenir_nom(),
+            solde = langue::montant(compte.obtenir_solde())
+        );
+    }
+
+    let choix = lire_choix(console, comptes.len(), tr!("commun.choix_invalide"), None)?;
+
+    let compte_a_supprimer = &comptes[choix];
+    let id = compte_a_supprimer.obtenir_id();
+    let nom = compte_a_supprimer.obtenir_nom().to_string();
+    let solde = compte_a_supprimer.obtenir_solde();
+
+    // Confirmation avant suppression
+    dire!(console, "supprimer.confirmer", nom = nom, solde = langue::montant(solde));
+    if console.oui_non("")? {
+        match banque.fermer_compte(id) {
+            Ok(_) => dire!(console, "supprimer.effectue", nom = nom),
+            Err(e) => afficher!(console, "{}", e),
+        }
+    } else {
+        dire!(console, "supprimer.annule");
+    }
+    Ok(())
+}
+
+// Nouvelle fonction pour effectuer un transfert entre comptes
+fn transferer_fonds(console: &mut Console<impl Lecteur, impl Write>, banque: &mut Banque) -> Resultat<()> {
+    dire!(console, "transfert.titre");
+    let comptes = &banque.comptes;
+
+    if comptes.len() < 2 {
+        dire!(console, "transfert.deux_comptes");
+        return Ok(());
+    }
+
+    dire!(console, "transfert.source");
+    for (index, compte) in comptes.iter().enumerate() {
+        dire!(
+            console,
+            "commun.compte_et_solde",
+            numero = index + 1,
+            nom = compte.obtenir_nom(),
+            solde = langue::montant(compte.obtenir_solde())
+        );
+    }
+
+    let source = lire_choix(console, comptes.len(), tr!("commun.choix_invalide"), None)?;
+
+    dire!(console, "transfert.destination");
+    for (index, compte) in comptes.iter().enumerate() {
+        if index != source {
+            dire!(
+            console,
+            "commun.compte_et_solde",
+            numero = index + 1,
+            nom = compte.obtenir_nom(),
+            solde = langue::montant(compte.obtenir_solde())
+        );
+        }
+    }
+
+    let destination = lire_choix(console, comptes.len(), tr!("transfert.destination_invalide"), Some(source))?;
+
+    dire!(console, "transfert.montant");
+    let montant = console.montant("")?;
+
+    let cle = lire_cle_idempotence(console)?;
+    let nom_source = comptes[source].obtenir_nom().to_string();
+    let nom_destination = comptes[destination].obtenir_nom().to_string();
+
+    // Effectuer le transfert
+    let operation = Operation::Transfert {
+        source: comptes[source].obtenir_id(),
+        destination: comptes[destination].obtenir_id(),
+        montant,
+    };
+    let execution = banque.executer(operation, cle.as_deref());
+    signaler_rejeu(console, execution.rejouee);
+    match execution.resultat {
+        Ok(ResultatOperation::Transfert { solde_source, solde_destination, .. }) => {
+            dire!(console, "transfert.effectue", montant = langue::montant(montant));
+            dire!(console, "transfert.de", nom = nom_source, solde = langue::montant(solde_source));
+            dire!(console, "transfert.vers", nom = nom_destination, solde = langue::montant(solde_destination));
+        }
+        Err(e @ ErreurBanque::CleDejaUtilisee(_)) => afficher!(console, "{}", e),
+        _ => dire!(console, "transfert.impossible"),
+    }
+    Ok(())
+}
+
+// Nouvelle fonction pour rechercher un compte par nom
+fn rechercher_compte(console: &mut Console<impl Lecteur, impl Write>, comptes: &[CompteBancaire]) -> Resultat<()> {
+    dire!(console, "rechercher.titre");
+
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return Ok(());
+    }
+
+    dire!(console, "rechercher.nom");
+    let recherche = console.demander("")?;
+
+    let recherche = recherche.trim().to_lowercase();
+
+    if recherche.is_empty() {
+        dire!(console, "rechercher.nom_vide");
+        return Ok(());
+    }
+
+    // Utiliser les itérateurs pour filtrer les comptes
+    let comptes_trouves: Vec<&CompteBancaire> = comptes
+        .iter()
+        .filter(|compte| compte.obtenir_nom().to_lowercase().contains(&recherche))
+        .collect();
+
+    if comptes_trouves.is_empty() {
+        dire!(console, "rechercher.aucun", recherche = recherche);
+    } else {
+        dire!(console, "rechercher.trouves");
+        for compte in comptes_trouves {
+            ecrire!(console, "• ");
+            let _ = compte.afficher_infos(&mut console.sortie);
+        }
+    }
+    Ok(())
+}
+
+// Exporte tout le journal pour les outils de comptabilité en texte brut
+fn exporter_comptabilite(
+    console: &mut Console<impl Lecteur, impl Write>,
+    journal: Option<&Journal>,
+) -> Resultat<()> {
+    let Some(journal) = journal else {
+        dire!(console, "export.indisponible");
+        return Ok(());
+    };
+
+    dire!(console, "export.format");
+    let par_defaut = ["banque.beancount", "banque.ledger"][console.entier("", 1..=2)? - 1];
+    dire!(console, "export.fichier", chemin = par_defaut);
+    let input = console.demander("")?;
+    let mut destination = std::path::PathBuf::from(if input.trim().is_empty() { par_defaut } else { input.trim() });
+    // L'extension choisit le format
+    if par_defaut.ends_with(".ledger") {
+        destination.set_extension("ledger");
+    }
+
+    match comptabilite::exporter_fichier(journal, &destination) {
+        Ok(()) => dire!(console, "export.effectue", chemin = destination.display()),
+        Err(e) => dire!(console, "export.impossible", chemin = destination.display(), erreur = e),
+    }
+    Ok(())
+}
+
+// Rejoue le journal jusqu'à la fin du jour demandé pour afficher les soldes et statistiques de ce jour-là
+fn afficher_soldes_passes(
+    console: &mut Console<impl Lecteur, impl Write>,
+    journal: Option<&Journal>,
+) -> Resultat<()> {
+    let Some(journal) = journal else {
+        dire!(console, "historique.indisponible");
+        return Ok(());
+    };
+
+    dire!(console, "historique.date");
+    let date = lire_date(console, None)?;
+    // Soldes en fin de journée
+    let fin_de_journee = date.and_hms_nano_opt(23, 59, 59, 999_999_999).expect("Heure valide");
+    let Some(instant) = Local.from_local_datetime(&fin_de_journee).latest() else {
+        dire!(console, "commun.date_invalide");
+        return Ok(());
+    };
+
+    let banque = match journal.banque_au(instant) {
+        Ok(banque) => banque,
+        Err(e) => {
+            dire!(console, "commun.lecture_impossible", chemin = journal.chemin().display(), erreur = e);
+            return Ok(());
+        }
+    };
+
+    dire!(console, "historique.etat", date = date.format("%d/%m/%Y"));
+    lister_comptes(console, &banque.comptes);
+    afficher_statistiques(console, &banque.comptes);
+    Ok(())
+}
+
+// Nouvelle fonction pour afficher des statistiques
+fn afficher_statistiques(console: &mut Console<impl Lecteur, impl Write>, comptes: &[CompteBancaire]) {
+    dire!(console, "statistiques.titre");
+
+    if comptes.is_empty() {
+        dire!(console, "commun.aucun_compte");
+        return;
+    }
+
+    let total_comptes = comptes.len();
+    
+    // Utiliser les itérateurs pour calculer les statistiques
+    let solde_total: f64 = comptes.iter().map(|compte| compte.obtenir_solde()).sum();
+    let solde_moyen = solde_total / total_comptes as f64;
+    
+    let solde_max = comptes
+        .iter()
+        .map(|compte| compte.obtenir_solde())
+        .fold(f64::NEG_INFINITY, f64::max);
+    
+    let solde_min = comptes
+        .iter()
+        .map(|compte| compte.obtenir_solde())
+        .fold(f64::INFINITY, f64::min);
+
+    let comptes_en_decouvert = comptes
+        .iter()
+        .filter(|compte| compte.est_en_decouvert())
+        .count();
+
+    let compte_plus_riche = comptes
+        .iter()
+        .max_by(|a, b| a.obtenir_solde().partial_cmp(&b.obtenir_solde()).unwrap());
+
+    dire!(console, "statistiques.generales");
+    dire!(console, "statistiques.nombre", nombre = total_comptes);
+    dire!(console, "statistiques.total", solde = langue::montant(solde_total));
+    dire!(console, "statistiques.moyen", solde = langue::montant(solde_moyen));
+    dire!(console, "statistiques.maximum", solde = langue::montant(solde_max));
+    dire!(console, "statistiques.minimum", solde = langue::montant(solde_min));
+    dire!(console, "statistiques.decouvert", nombre = comptes_en_decouvert);
+    
+    if let Some(compte) = compte_plus_riche {
+        dire!(
+            console,
+            "statistiques.plus_riche",
+            nom = compte.obtenir_nom(),
+            solde = langue::montant(compte.obtenir_solde())
+        );
+    }
+}
+
+
+// Fonction pour lister tous les comptes
+fn lister_comptes(console: &mut Console<impl Lecteur, impl Write>, comptes: &[CompteBancaire]) {
+    dire!(console, "liste.titre");
+
+    if comptes.is_empty() {
+        dire!(console, "liste.aucun");
+        return;
+    }
+
+    // Points bonus: utilisation de .iter() et .enumerate()
+    for (index, compte) in comptes.iter().enumerate() {
+        ecrire!(console, "{}. ", index + 1);
+        let _ = compte.afficher_infos(&mut console.sortie);
+        
+        // Ajouter des indicateurs visuels
+        if compte.est_en_decouvert() {
+            dire!(console, "liste.decouvert");
+        } else if compte.est_vip() {
+            dire!(console, "liste.vip");
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use crate::banque::{Banque, ErreurBanque};
+use crate::compte::Transaction;
+use crate::langue::Langue;
+use crate::tr;
+use chrono::NaiveDate;
+use std::path::Path;
+use std::{fmt, fs, io};
+
+// Écart maximal (en jours) entre la date du relevé et celle de la transaction pour les rapprocher
+pub const FENETRE_JOURS: i64 = 3;
+
+// Ligne du relevé envoyé par la banque (montant positif = crédit, négatif = débit)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LigneReleve {
+    pub date: NaiveDate,
+    pub libelle: String,
+    pub montant: f64,
+}
+
+#[derive(Debug)]
+pub enum ErreurRapprochement {
+    Lecture(io::Error),
+    LigneInvalide { ligne: u64, message: String },
+    LigneIntrouvable(usize),
+    TransactionIntrouvable(usize),
+    DejaRapprochee,
+    MontantsDifferents,
+}
+
+impl fmt::Display for ErreurRapprochement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            ErreurRapprochement::Lecture(e) => tr!("releve.lecture", erreur = e),
+            ErreurRapprochement::LigneInvalide { ligne, message } => {
+                tr!("releve.ligne_invalide", ligne = ligne, message = message)
+            }
+            ErreurRapprochement::LigneIntrouvable(numero) => tr!("releve.ligne_introuvable", numero = numero),
+            ErreurRapprochement::TransactionIntrouvable(numero) => {
+                tr!("releve.transaction_introuvable", numero = numero)
+            }
+            ErreurRapprochement::DejaRapprochee => tr!("releve.deja_rapprochee").to_string(),
+            ErreurRapprochement::MontantsDifferents => tr!("releve.montants_differents").to_string(),
+        };
+        f.write_str(&message)
+    }
+}
+
+fn lire_date(texte: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(texte, "%d/%m/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(texte, "%Y-%m-%d"))
+        .ok()
+}
+
+// Lit un relevé CSV à trois colonnes : date;libellé;montant. Le séparateur (';' ou ',') est deviné
+// sur la première ligne, et une éventuelle ligne d'en-tête est ignorée.
+pub fn lire_releve(contenu: &str) -> Result<Vec<LigneReleve>, ErreurRapprochement> {
+    let premiere_ligne = contenu.lines().next().unwrap_or_default();
+    let separateur = if premiere_ligne.contains(';') { b';' } else { b',' };
+    let mut lecteur = csv::ReaderBuilder::new()
+        .delimiter(separateur)
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(contenu.as_bytes());
+
+    let mut lignes = Vec::new();
+    for (index, enregistrement) in lecteur.records().enumerate() {
+        let enregistrement = enregistrement.map_err(|e| ErreurRapprochement::LigneInvalide {
+            ligne: e.position().map_or(0, |position| position.line()),
+            message: e.to_string(),
+        })?;
+        let numero = enregistrement.position().map_or(0, |position| position.line());
+        let invalide = |message: &str| ErreurRapprochement::LigneInvalide {
+            ligne: numero,
+            message: message.to_string(),
+        };
+        if enregistrement.len() < 3 {
+            return Err(invalide(tr!("releve.colonnes")));
+        }
+
+        let Some(date) = lire_date(&enregistrement[0]) else {
+            if index == 0 {
+                // Ligne d'en-tête
+                continue;
+            }
+            return Err(invalide(tr!("releve.date_invalide")));
+        };
+        // Le format du relevé ne dépend pas de la langue de l'interface : "-1 234,56", "1234.56 €"
+        let montant = Langue::Francais
+            .lire_montant(&enregistrement[2])
+            .ok_or_else(|| invalide(tr!("releve.montant_invalide")))?;
+        lignes.push(LigneReleve {
+            date,
+            libelle: enregistrement[1].to_string(),
+            montant,
+        });
+    }
+    Ok(lignes)
+}
+
+pub fn charger_releve(chemin: &Path) -> Result<Vec<LigneReleve>, ErreurRapprochement> {
+    let contenu = fs::read_to_string(chemin).map_err(ErreurRapprochement::Lecture)?;
+    lire_releve(&contenu)
+}
+
+// Ressemblance entre deux libellés, de 0 (rien à voir) à 1 (identiques ou l'un contient l'autre)
+fn similarite(a: &str, b: &str) -> f64 {
+    let a = a.to_lowercase();
+    let b = b.to_lowercase();
+    if !a.is_empty() && !b.is_empty() && (a.contains(&b) || b.contains(&a)) {
+        return 1.0;
+    }
+    strsim::jaro_winkler(&a, &b)
+}
+
+fn memes_montants(a: f64, b: f64) -> bool {
+    (a - b).abs() < 0.005
+}
+
+// Séance de rapprochement d'un compte avec un relevé. Seules les transactions postérieures au
+// dernier rapprochement sont proposées : les précédentes sont verrouillées.
+pub struct Rapprochement {
+    id: u32,
+    releve: Vec<LigneReleve>,
+    transactions: Vec<Transaction>,
+    // Paires (index dans le relevé, index dans les transactions)
+    correspondances: Vec<(usize, usize)>,
+}
+
+impl Rapprochement {
+    pub fn nouveau(banque: &Banque, id: u32, releve: Vec<LigneReleve>) -> Result<Rapprochement, ErreurBanque> {
+        let compte = banque.compte(id)?;
+        let transactions = compte
+            .historique()
+            .iter()
+            .filter(|transaction| !compte.est_verrouillee(transaction))
+            .cloned()
+            .collect();
+        Ok(Rapprochement {
+            id,
+            releve,
+            transactions,
+            correspondances: Vec::new(),
+        })
+    }
+
+    pub fn releve(&self) -> &[LigneReleve] {
+        &self.releve
+    }
+
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    pub fn correspondances(&self) -> &[(usize, usize)] {
+        &self.correspondances
+    }
+
+    fn ligne_libre(&self, ligne: usize) -> bool {
+        !self.correspondances.iter().any(|(l, _)| *l == ligne)
+    }
+
+    fn transaction_libre(&self, transaction: usize) -> bool {
+        !self.correspondances.iter().any(|(_, t)| *t == transaction)
+    }
+
+    pub fn lignes_non_rapprochees(&self) -> Vec<usize> {
+        (0..self.releve.len()).filter(|ligne| self.ligne_libre(*ligne)).collect()
+    }
+
+    pub fn transactions_non_rapprochees(&self) -> Vec<usize> {
+        (0..self.transactions.len())
+            .filter(|transaction| self.transaction_libre(*transaction))
+            .collect()
+    }
+
+    // Date de la dernière ligne du relevé
+    pub fn derniere_date(&self) -> Option<NaiveDate> {
+        self.releve.iter().map(|ligne| ligne.date).max()
+    }
+
+    // Rapproche les lignes de même montant dont les dates sont proches, en préférant les libellés
+    // qui se ressemblent puis les dates les plus proches. Retourne le nombre de paires trouvées.
+    pub fn rapprocher_automatiquement(&mut self) -> usize {
+        let mut candidats = Vec::new();
+        for ligne in self.lignes_non_rapprochees() {
+            for transaction in self.transactions_non_rapprochees() {
+                let (l, t) = (&self.releve[ligne], &self.transactions[transaction]);
+                let ecart = (l.date - t.date.date_naive()).num_days().abs();
+                if memes_montants(l.montant, t.montant) && ecart <= FENETRE_JOURS {
+                    let score = similarite(&l.libelle, &t.libelle) - 0.05 * ecart as f64;
+                    candidats.push((score, ligne, transaction));
+                }
+            }
+        }
+        candidats.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let avant = self.correspondances.len();
+        for (_, ligne, transaction) in candidats {
+            if self.ligne_libre(ligne) && self.transaction_libre(transaction) {
+                self.correspondances.push((ligne, transaction));
+            }
+        }
+        self.correspondances.len() - avant
+    }
+
+    // Rapprochement manuel, quelles que soient les dates et les libellés
+    pub fn associer(&mut self, ligne: usize, transaction: usize) -> Result<(), ErreurRapprochement> {
+        let l = self
+            .releve
+            .get(ligne)
+            .ok_or(ErreurRapprochement::LigneIntrouvable(ligne + 1))?;
+        let t = self
+            .transactions
+            .get(transaction)
+            .ok_or(ErreurRapprochement::TransactionIntrouvable(transaction + 1))?;
+        if !memes_montants(l.montant, t.montant) {
+            return Err(ErreurRapprochement::MontantsDifferents);
+        }
+        if !self.ligne_libre(ligne) || !self.transaction_libre(transaction) {
+            return Err(ErreurRapprochement::DejaRapprochee);
+        }
+        self.correspondances.push((ligne, transaction));
+        Ok(())
+    }
+
+    // Défait le rapprochement d'une ligne du relevé
+    pub fn dissocier(&mut self, ligne: usize) -> Result<(), ErreurRapprochement> {
+        let position = self
+            .correspondances
+            .iter()
+            .position(|(l, _)| *l == ligne)
+            .ok_or(ErreurRapprochement::LigneIntrouvable(ligne + 1))?;
+        self.correspondances.remove(position);
+        Ok(())
+    }
+
+    // Enregistre le compte comme rapproché jusqu'à la date donnée, si tout ce qui précède cette date
+    // (des deux côtés) a été rapproché
+    pub fn valider(&self, banque: &mut Banque, jusqu_au: NaiveDate) -> Result<(), ErreurBanque> {
+        let lignes = self
+            .lignes_non_rapprochees()
+            .into_iter()
+            .filter(|ligne| self.releve[*ligne].date <= jusqu_au)
+            .count();
+        let transactions = self
+            .transactions_non_rapprochees()
+            .into_iter()
+            .filter(|transaction| self.transactions[*transaction].date.date_naive() <= jusqu_au)
+            .count();
+        if lignes > 0 || transactions > 0 {
+            return Err(ErreurBanque::RapprochementIncomplet { lignes, transactions });
+        }
+        banque.marquer_rapproche(self.id, jusqu_au)
+    }
+}
@@ -0,0 +1,197 @@
+use crate::banque::Banque;
+use crate::{langue, tr};
+use std::fmt;
+
+// Scripts d'opérations bancaires, une commande par ligne :
+//   create "Dan" 200          ouvre un compte (solde initial facultatif)
+//   deposit 3 50              dépôt
+//   withdraw 3 20             retrait
+//   transfer 1 3 50           transfert du compte 1 vers le compte 3
+//   rename 3 "Dan Martin"     renomme un compte
+//   close 3                   supprime un compte
+//   assert-balance 3 800      vérifie le solde disponible d'un compte
+// Les montants s'écrivent avec un point ou une virgule décimale (12.50 ou 12,50).
+// Les lignes vides et ce qui suit un # sont ignorés.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Commande {
+    Creer { nom: String, solde: f64 },
+    Deposer { id: u32, montant: f64 },
+    Retirer { id: u32, montant: f64 },
+    Transferer { source: u32, destination: u32, montant: f64 },
+    Renommer { id: u32, nom: String },
+    Fermer { id: u32 },
+    VerifierSolde { id: u32, solde: f64 },
+}
+
+// Commande avec le numéro de sa ligne dans le script
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    pub ligne: usize,
+    pub commande: Commande,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErreurScript {
+    pub ligne: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ErreurScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&tr!("script.erreur", ligne = self.ligne, message = self.message))
+    }
+}
+
+// Découpe une ligne en mots, les chaînes entre guillemets pouvant contenir des espaces
+fn decouper(ligne: &str) -> Result<Vec<String>, String> {
+    let mut mots = Vec::new();
+    let mut caracteres = ligne.chars().peekable();
+    while let Some(&c) = caracteres.peek() {
+        if c.is_whitespace() {
+            caracteres.next();
+        } else if c == '#' {
+            break;
+        } else if c == '"' {
+            caracteres.next();
+            let mut mot = String::new();
+            loop {
+                match caracteres.next() {
+                    Some('\\') => mot.extend(caracteres.next()),
+                    Some('"') => break,
+                    Some(c) => mot.push(c),
+                    None => return Err(tr!("script.guillemet").to_string()),
+                }
+            }
+            mots.push(mot);
+        } else {
+            let mut mot = String::new();
+            while let Some(&c) = caracteres.peek() {
+                if c.is_whitespace() || c == '#' {
+                    break;
+                }
+                mot.push(c);
+                caracteres.next();
+            }
+            mots.push(mot);
+        }
+    }
+    Ok(mots)
+}
+
+fn identifiant(texte: &str) -> Result<u32, String> {
+    texte.parse().map_err(|_| tr!("script.identifiant_invalide", texte = texte))
+}
+
+// Les scripts ne dépendent pas de la langue : une virgule seule est toujours décimale
+fn montant(texte: &str) -> Result<f64, String> {
+    saisie::lire_montant(texte, false)
+        .filter(|montant| montant.is_finite())
+        .ok_or_else(|| tr!("script.montant_invalide", texte = texte))
+}
+
+fn lire_commande(mots: &[String]) -> Result<Commande, String> {
+    let arguments: Vec<&str> = mots[1..].iter().map(String::as_str).collect();
+    let usage = |syntaxe: &str| tr!("script.usage", commande = mots[0], syntaxe = syntaxe);
+    let commande = match (mots[0].as_str(), arguments.as_slice()) {
+        ("create", [nom]) => Commande::Creer {
+            nom: nom.to_string(),
+            solde: 0.0,
+        },
+        ("create", [nom, solde]) => Commande::Creer {
+            nom: nom.to_string(),
+            solde: montant(solde)?,
+        },
+        ("create", _) => return Err(usage(tr!("script.syntaxe.create"))),
+        ("deposit", [id, valeur]) => Commande::Deposer {
+            id: identifiant(id)?,
+            montant: montant(valeur)?,
+        },
+        ("deposit", _) => return Err(usage(tr!("script.syntaxe.montant"))),
+        ("withdraw", [id, valeur]) => Commande::Retirer {
+            id: identifiant(id)?,
+            montant: montant(valeur)?,
+        },
+        ("withdraw", _) => return Err(usage(tr!("script.syntaxe.montant"))),
+        ("transfer", [source, destination, valeur]) => Commande::Transferer {
+            source: identifiant(source)?,
+            destination: identifiant(destination)?,
+            montant: montant(valeur)?,
+        },
+        ("transfer", _) => return Err(usage(tr!("script.syntaxe.transfer"))),
+        ("rename", [id, nom]) => Commande::Renommer {
+            id: identifiant(id)?,
+            nom: nom.to_string(),
+        },
+        ("rename", _) => return Err(usage(tr!("script.syntaxe.rename"))),
+        ("close", [id]) => Commande::Fermer { id: identifiant(id)? },
+        ("close", _) => return Err(usage(tr!("script.syntaxe.close"))),
+        ("assert-balance", [id, solde]) => Commande::VerifierSolde {
+            id: identifiant(id)?,
+            solde: montant(solde)?,
+        },
+        ("assert-balance", _) => return Err(usage(tr!("script.syntaxe.assert"))),
+        (inconnue, _) => return Err(tr!("script.commande_inconnue", commande = inconnue)),
+    };
+    Ok(commande)
+}
+
+// Lit tout le script avant de l'exécuter : une erreur de syntaxe n'exécute rien
+pub fn lire_script(texte: &str) -> Result<Vec<Instruction>, ErreurScript> {
+    let mut instructions = Vec::new();
+    for (index, ligne) in texte.lines().enumerate() {
+        let erreur = |message: String| ErreurScript {
+            ligne: index + 1,
+            message,
+        };
+        let mots = decouper(ligne).map_err(erreur)?;
+        if mots.is_empty() {
+            continue;
+        }
+        instructions.push(Instruction {
+            ligne: index + 1,
+            commande: lire_commande(&mots).map_err(erreur)?,
+        });
+    }
+    Ok(instructions)
+}
+
+fn verifier_solde(banque: &Banque, id: u32, solde: f64) -> Result<(), String> {
+    let trouve = banque.compte(id).map_err(|e| e.to_string())?.obtenir_solde();
+    if (trouve - solde).abs() >= 0.005 {
+        return Err(tr!(
+            "script.solde_inattendu",
+            id = id,
+            attendu = langue::montant(solde),
+            trouve = langue::montant(trouve)
+        ));
+    }
+    Ok(())
+}
+
+fn executer_commande(banque: &mut Banque, commande: &Commande) -> Result<(), String> {
+    let resultat = match commande {
+        Commande::Creer { nom, solde } => banque.ouvrir_compte(nom, *solde).map(|_| ()),
+        Commande::Deposer { id, montant } => banque.deposer(*id, *montant).map(|_| ()),
+        Commande::Retirer { id, montant } => banque.retirer(*id, *montant).map(|_| ()),
+        Commande::Transferer { source, destination, montant } => banque.transferer(*source, *destination, *montant),
+        Commande::Renommer { id, nom } => banque.renommer(*id, nom),
+        Commande::Fermer { id } => banque.fermer_compte(*id).map(|_| ()),
+        Commande::VerifierSolde { id, solde } => return verifier_solde(banque, *id, *solde),
+    };
+    resultat.map_err(|e| e.to_string())
+}
+
+// Exécute les instructions de façon transactionnelle : sur une copie de la banque, qui ne remplace
+// l'originale que si toutes ont réussi. En cas d'erreur, la banque n'est pas modifiée.
+pub fn executer(banque: &mut Banque, instructions: &[Instruction]) -> Result<(), ErreurScript> {
+    let mut essai = banque.clone();
+    for instruction in instructions {
+        executer_commande(&mut essai, &instruction.commande).map_err(|message| ErreurScript {
+            ligne: instruction.ligne,
+            message,
+        })?;
+    }
+    *banque = essai;
+    Ok(())
+}
@@ -0,0 +1,563 @@
+use crate::banque::{Banque, Operation};
+use crate::journal::Journal;
+use crate::{langue, tr};
+use chrono::Local;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+
+// Ordre d'affichage du tableau des comptes (touche 's' pour changer)
+#[derive(Clone, Copy)]
+enum Tri {
+    Nom,
+    SoldeCroissant,
+    SoldeDecroissant,
+}
+
+impl Tri {
+    fn suivant(self) -> Tri {
+        match self {
+            Tri::Nom => Tri::SoldeCroissant,
+            Tri::SoldeCroissant => Tri::SoldeDecroissant,
+            Tri::SoldeDecroissant => Tri::Nom,
+        }
+    }
+
+    fn libelle(self) -> &'static str {
+        match self {
+            Tri::Nom => tr!("tui.tri.nom"),
+            Tri::SoldeCroissant => tr!("tui.tri.solde_croissant"),
+            Tri::SoldeDecroissant => tr!("tui.tri.solde_decroissant"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TypeOperation {
+    Depot,
+    Retrait,
+    Transfert,
+}
+
+// Champ du dialogue où se trouve le curseur (Tab pour passer au suivant)
+#[derive(Clone, Copy, PartialEq)]
+enum Champ {
+    Destination,
+    Montant,
+    Cle,
+}
+
+// Boîte de dialogue de saisie d'une opération
+struct Dialogue {
+    operation: TypeOperation,
+    source: usize,
+    destination: String,
+    montant: String,
+    // Clé d'idempotence facultative, pour pouvoir rejouer l'opération sans la doubler
+    cle: String,
+    champ: Champ,
+}
+
+impl Dialogue {
+    // Champs dans l'ordre de saisie ; la destination n'existe que pour un transfert
+    fn champs(&self) -> &'static [Champ] {
+        if self.operation == TypeOperation::Transfert {
+            &[Champ::Destination, Champ::Montant, Champ::Cle]
+        } else {
+            &[Champ::Montant, Champ::Cle]
+        }
+    }
+
+    fn changer_champ(&mut self, decalage: usize) {
+        let champs = self.champs();
+        let position = champs.iter().position(|champ| *champ == self.champ).unwrap_or(0);
+        self.champ = champs[(position + decalage) % champs.len()];
+    }
+}
+
+// État de l'interface. Les touches le font évoluer sans passer par le terminal, ce qui permet de piloter
+// l'interface depuis les tests ; seul l'affichage (dessiner) dépend de ratatui.
+pub struct App<'a> {
+    banque: &'a mut Banque,
+    journal: &'a mut Journal,
+    table: TableState,
+    tri: Tri,
+    filtre: String,
+    saisie_filtre: bool,
+    dialogue: Option<Dialogue>,
+    message: String,
+}
+
+// Lance l'interface plein écran sur les comptes de la banque. Chaque opération est enregistrée dans le
+// journal dès qu'elle est validée.
+pub fn lancer(banque: &mut Banque, journal: &mut Journal) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let resultat = App::new(banque, journal).executer(&mut terminal);
+    ratatui::restore();
+    resultat
+}
+
+impl<'a> App<'a> {
+    pub fn new(banque: &'a mut Banque, journal: &'a mut Journal) -> App<'a> {
+        App {
+            banque,
+            journal,
+            table: TableState::default().with_selected(Some(0)),
+            tri: Tri::Nom,
+            filtre: String::new(),
+            saisie_filtre: false,
+            dialogue: None,
+            message: tr!("tui.bienvenue").to_string(),
+        }
+    }
+}
+
+impl App<'_> {
+    fn executer(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            self.mettre_a_jour();
+            terminal.draw(|frame| self.dessiner(frame))?;
+
+            let Event::Key(touche) = event::read()? else {
+                continue;
+            };
+            if touche.kind == KeyEventKind::Press && !self.touche(touche.code) {
+                return Ok(());
+            }
+        }
+    }
+
+    // Traite une touche selon ce qui a le focus (dialogue, filtre ou tableau) ; false pour quitter
+    pub fn touche(&mut self, code: KeyCode) -> bool {
+        if self.dialogue.is_some() {
+            self.touche_dialogue(code);
+        } else if self.saisie_filtre {
+            self.touche_filtre(code);
+        } else {
+            return self.touche_tableau(code);
+        }
+        true
+    }
+
+    pub fn banque(&self) -> &Banque {
+        self.banque
+    }
+
+    // Message de la barre d'état
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    // Identifiants des comptes dans l'ordre du tableau
+    pub fn comptes_affiches(&self) -> Vec<u32> {
+        self.visibles().into_iter().map(|i| self.banque.comptes[i].obtenir_id()).collect()
+    }
+
+    pub fn dialogue_ouvert(&self) -> bool {
+        self.dialogue.is_some()
+    }
+
+    // Erreur de saisie affichée dans le dialogue ouvert (None si la saisie est valide ou sans dialogue)
+    pub fn erreur_dialogue(&self) -> Option<String> {
+        self.verifier(self.dialogue.as_ref()?).err()
+    }
+
+    // Prend en compte les opérations des autres utilisateurs du même journal, puis enregistre les
+    // encaissements arrivés à leur date de valeur (sauf en lecture seule)
+    pub fn mettre_a_jour(&mut self) {
+        if let Err(e) = self.journal.rafraichir(self.banque) {
+            self.message = tr!("journal.relecture_impossible", chemin = self.journal.chemin().display(), erreur = e);
+            return;
+        }
+        let aujourd_hui = Local::now().date_naive();
+        if self.journal.est_lecture_seule() || !self.banque.encaissements_a_compenser(aujourd_hui) {
+            return;
+        }
+        match self.journal.modifier(self.banque, |banque| banque.compenser_encaissements(aujourd_hui)) {
+            Ok(compenses) if compenses.is_empty() => {}
+            Ok(compenses) => self.message = tr!("tui.encaissements_disponibles", nombre = compenses.len()),
+            Err(e) => {
+                let chemin = self.journal.chemin().display();
+                self.message = tr!("journal.enregistrement_impossible", chemin = chemin, erreur = e);
+            }
+        }
+    }
+
+    // Indices (dans comptes) des comptes affichés, filtrés puis triés
+    fn visibles(&self) -> Vec<usize> {
+        let filtre = self.filtre.to_lowercase();
+        let mut indices: Vec<usize> = (0..self.banque.comptes.len())
+            .filter(|&i| self.banque.comptes[i].obtenir_nom().to_lowercase().contains(&filtre))
+            .collect();
+
+        match self.tri {
+            Tri::Nom => indices.sort_by_key(|&i| self.banque.comptes[i].obtenir_nom().to_lowercase()),
+            Tri::SoldeCroissant => indices.sort_by(|&a, &b| {
+                self.banque.comptes[a].obtenir_solde().total_cmp(&self.banque.comptes[b].obtenir_solde())
+            }),
+            Tri::SoldeDecroissant => indices.sort_by(|&a, &b| {
+                self.banque.comptes[b].obtenir_solde().total_cmp(&self.banque.comptes[a].obtenir_solde())
+            }),
+        }
+        indices
+    }
+
+    fn compte_selectionne(&self) -> Option<usize> {
+        let visibles = self.visibles();
+        self.table.selected().and_then(|ligne| visibles.get(ligne).copied())
+    }
+
+    // Retourne false quand l'utilisateur demande à quitter
+    fn touche_tableau(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+            KeyCode::Char('s') => {
+                self.tri = self.tri.suivant();
+                self.message = tr!("tui.tri_par", tri = self.tri.libelle());
+            }
+            KeyCode::Char('/') => {
+                self.saisie_filtre = true;
+                self.message = tr!("tui.filtre_aide").to_string();
+            }
+            KeyCode::Char('d') => self.ouvrir_dialogue(TypeOperation::Depot),
+            KeyCode::Char('r') => self.ouvrir_dialogue(TypeOperation::Retrait),
+            KeyCode::Char('t') => self.ouvrir_dialogue(TypeOperation::Transfert),
+            _ => {}
+        }
+        true
+    }
+
+    fn touche_filtre(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.saisie_filtre = false,
+            KeyCode::Esc => {
+                self.filtre.clear();
+                self.saisie_filtre = false;
+            }
+            KeyCode::Backspace => {
+                self.filtre.pop();
+            }
+            KeyCode::Char(c) => self.filtre.push(c),
+            _ => {}
+        }
+        self.table.select(Some(0));
+    }
+
+    fn ouvrir_dialogue(&mut self, operation: TypeOperation) {
+        let Some(source) = self.compte_selectionne() else {
+            self.message = tr!("tui.aucun_compte").to_string();
+            return;
+        };
+        if operation == TypeOperation::Transfert && self.banque.comptes.len() < 2 {
+            self.message = tr!("transfert.deux_comptes").to_string();
+            return;
+        }
+        self.dialogue = Some(Dialogue {
+            operation,
+            source,
+            destination: String::new(),
+            montant: String::new(),
+            cle: String::new(),
+            champ: if operation == TypeOperation::Transfert { Champ::Destination } else { Champ::Montant },
+        });
+    }
+
+    fn touche_dialogue(&mut self, code: KeyCode) {
+        let Some(dialogue) = self.dialogue.as_mut() else {
+            return;
+        };
+        let nombre = dialogue.champ != Champ::Cle;
+        let champ = match dialogue.champ {
+            Champ::Destination => &mut dialogue.destination,
+            Champ::Montant => &mut dialogue.montant,
+            Champ::Cle => &mut dialogue.cle,
+        };
+
+        match code {
+            KeyCode::Esc => {
+                self.dialogue = None;
+                self.message = tr!("tui.annulee").to_string();
+            }
+            KeyCode::Tab => dialogue.changer_champ(1),
+            KeyCode::BackTab => dialogue.changer_champ(dialogue.champs().len() - 1),
+            KeyCode::Backspace => {
+                champ.pop();
+            }
+            KeyCode::Char(c) if !nombre || c.is_ascii_digit() || c == '.' || c == ',' => champ.push(c),
+            KeyCode::Enter => self.valider_dialogue(),
+            _ => {}
+        }
+    }
+
+    // Vérifie la saisie du dialogue : retourne (destination, montant) ou le message d'erreur
+    fn verifier(&self, dialogue: &Dialogue) -> Result<(usize, f64), String> {
+        let source = &self.banque.comptes[dialogue.source];
+
+        let mut destination = dialogue.source;
+        if dialogue.operation == TypeOperation::Transfert {
+            // La destination est saisie par son numéro de compte (colonne #)
+            destination = match dialogue.destination.trim().parse::<u32>().map(|id| self.banque.position(id)) {
+                Ok(Ok(position)) if position != dialogue.source => position,
+                _ => return Err(tr!("transfert.destination_invalide").to_string()),
+            };
+        }
+
+        let montant = match langue::lire_montant(&dialogue.montant) {
+            Some(num) if num > 0.0 => num,
+            _ => return Err(tr!("commun.montant_invalide").to_string()),
+        };
+
+        if dialogue.operation != TypeOperation::Depot && montant > source.obtenir_solde() {
+            return Err(tr!("tui.solde_insuffisant", solde = langue::montant(source.obtenir_solde())));
+        }
+
+        Ok((destination, montant))
+    }
+
+    fn valider_dialogue(&mut self) {
+        let Some(dialogue) = self.dialogue.take() else {
+            return;
+        };
+        let (destination, montant) = match self.verifier(&dialogue) {
+            Ok(valeurs) => valeurs,
+            Err(_) => {
+                // On garde le dialogue ouvert tant que la saisie est invalide
+                self.dialogue = Some(dialogue);
+                return;
+            }
+        };
+
+        let source = self.banque.comptes[dialogue.source].obtenir_id();
+        let id_destination = self.banque.comptes[destination].obtenir_id();
+        let operation = match dialogue.operation {
+            TypeOperation::Depot => Operation::Depot { id: source, montant },
+            TypeOperation::Retrait => Operation::Retrait { id: source, montant },
+            TypeOperation::Transfert => Operation::Transfert {
+                source,
+                destination: id_destination,
+                montant,
+            },
+        };
+        let cle = Some(dialogue.cle.trim()).filter(|cle| !cle.is_empty());
+        // La banque est mise à jour des écritures des autres utilisateurs avant l'opération, enregistrée
+        // aussitôt sous le verrou du journal
+        let resultat = self.journal.modifier(self.banque, |banque| banque.executer(operation, cle));
+
+        let execution = match resultat {
+            Ok(execution) => execution,
+            Err(e) => {
+                let chemin = self.journal.chemin().display();
+                self.message = tr!("journal.enregistrement_impossible", chemin = chemin, erreur = e);
+                return;
+            }
+        };
+        let message = match execution.resultat {
+            Err(e) => tr!("tui.refusee", erreur = e),
+            Ok(_) => match dialogue.operation {
+                TypeOperation::Depot => tr!("depot.effectue", montant = langue::montant(montant)),
+                TypeOperation::Retrait => tr!("retrait.effectue", montant = langue::montant(montant)),
+                TypeOperation::Transfert => tr!(
+                    "tui.transfert_effectue",
+                    montant = langue::montant(montant),
+                    nom = self.banque.compte(id_destination).map_or("", |compte| compte.obtenir_nom())
+                ),
+            },
+        };
+        self.message = if execution.rejouee {
+            format!("{} {}", tr!("idempotence.rejeu"), message)
+        } else {
+            message
+        };
+    }
+
+}
+
+// Affichage de l'état dans le terminal
+impl App<'_> {
+    fn dessiner(&mut self, frame: &mut Frame) {
+        let [principal, barre] = Layout::vertical([Constraint::Min(5), Constraint::Length(3)]).areas(frame.area());
+        let [zone_table, zone_detail] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(principal);
+
+        self.dessiner_table(frame, zone_table);
+        self.dessiner_detail(frame, zone_detail);
+
+        let aide = if self.saisie_filtre {
+            tr!("tui.filtre", filtre = self.filtre)
+        } else {
+            tr!("tui.aide").to_string()
+        };
+        let barre_texte = vec![Line::from(aide), Line::from(self.message.as_str())];
+        frame.render_widget(Paragraph::new(barre_texte).block(Block::default().borders(Borders::TOP)), barre);
+
+        if let Some(dialogue) = &self.dialogue {
+            self.dessiner_dialogue(frame, dialogue);
+        }
+    }
+
+    fn dessiner_table(&mut self, frame: &mut Frame, zone: Rect) {
+        let lignes: Vec<Row> = self
+            .visibles()
+            .into_iter()
+            .map(|i| {
+                let compte = &self.banque.comptes[i];
+                let (statut, couleur) = if compte.est_en_decouvert() {
+                    (tr!("tui.decouvert"), Color::Red)
+                } else if compte.est_vip() {
+                    (tr!("tui.vip"), Color::Yellow)
+                } else {
+                    ("", Color::Reset)
+                };
+                Row::new(vec![
+                    Cell::from(compte.obtenir_id().to_string()),
+                    Cell::from(compte.obtenir_nom().to_string()),
+                    Cell::from(format!("{:>13}", langue::montant(compte.obtenir_solde()))),
+                    Cell::from(statut).style(Style::default().fg(couleur)),
+                ])
+            })
+            .collect();
+
+        let titre = if self.filtre.is_empty() {
+            format!(" {} ", tr!("tui.titre_comptes", tri = self.tri.libelle()))
+        } else {
+            format!(" {} ", tr!("tui.titre_comptes_filtre", tri = self.tri.libelle(), filtre = self.filtre))
+        };
+        let table = Table::new(
+            lignes,
+            [Constraint::Length(4), Constraint::Min(15), Constraint::Length(14), Constraint::Length(14)],
+        )
+        .header(
+            Row::new(vec!["#", tr!("tui.colonne.nom"), tr!("tui.colonne.solde"), tr!("tui.colonne.statut")])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::default().borders(Borders::ALL).title(titre))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(table, zone, &mut self.table);
+    }
+
+    fn dessiner_detail(&self, frame: &mut Frame, zone: Rect) {
+        let bloc = Block::default().borders(Borders::ALL).title(format!(" {} ", tr!("tui.detail")));
+        let Some(index) = self.compte_selectionne() else {
+            frame.render_widget(Paragraph::new(tr!("tui.aucun_compte")).block(bloc), zone);
+            return;
+        };
+
+        let compte = &self.banque.comptes[index];
+        let mut lignes = vec![
+            Line::from(tr!("tui.nom", nom = compte.obtenir_nom())),
+            Line::from(tr!("tui.solde", solde = langue::montant(compte.obtenir_solde()))),
+        ];
+        if !compte.en_attente().is_empty() {
+            let comptable = langue::montant(compte.obtenir_solde_comptable());
+            lignes.push(Line::from(tr!("tui.solde_comptable", solde = comptable)));
+            lignes.push(Line::from(tr!("tui.en_attente")).style(Style::default().add_modifier(Modifier::BOLD)));
+            for encaissement in compte.en_attente() {
+                lignes.push(Line::from(format!(
+                    "  {}",
+                    tr!(
+                        "tui.encaissement",
+                        numero = encaissement.numero,
+                        libelle = encaissement.libelle,
+                        montant = langue::montant_signe(encaissement.montant),
+                        date = encaissement.date_valeur.format("%d/%m/%Y")
+                    )
+                )));
+            }
+        }
+        if let Some(date) = compte.rapproche_jusqu_au() {
+            lignes.push(Line::from(tr!("tui.rapproche", date = date.format("%d/%m/%Y"))));
+        }
+        lignes.extend([
+            Line::from(""),
+            Line::from(tr!("tui.historique")).style(Style::default().add_modifier(Modifier::BOLD)),
+        ]);
+        if compte.historique().is_empty() {
+            lignes.push(Line::from(format!("  {}", tr!("tui.aucune_operation"))));
+        }
+        // Les opérations les plus récentes en premier
+        for transaction in compte.historique().iter().rev() {
+            let couleur = if transaction.montant < 0.0 { Color::Red } else { Color::Green };
+            // Les transactions d'une période rapprochée ou d'un exercice clôturé sont verrouillées
+            let verrou = if self.banque.est_verrouillee(compte, transaction) { "🔒" } else { "  " };
+            lignes.push(
+                Line::from(format!(
+                    " {}{} {:<24} {:>14}",
+                    verrou,
+                    transaction.date.format("%d/%m/%Y %H:%M"),
+                    transaction.libelle,
+                    langue::montant_signe(transaction.montant)
+                ))
+                .style(Style::default().fg(couleur)),
+            );
+        }
+
+        frame.render_widget(Paragraph::new(lignes).block(bloc), zone);
+    }
+
+    fn dessiner_dialogue(&self, frame: &mut Frame, dialogue: &Dialogue) {
+        let titre = match dialogue.operation {
+            TypeOperation::Depot => tr!("tui.depot"),
+            TypeOperation::Retrait => tr!("tui.retrait"),
+            TypeOperation::Transfert => tr!("tui.transfert"),
+        };
+        let curseur = |actif: bool| if actif { "▏" } else { "" };
+
+        let source = &self.banque.comptes[dialogue.source];
+        let mut lignes = vec![Line::from(tr!(
+            "tui.compte",
+            nom = source.obtenir_nom(),
+            solde = langue::montant(source.obtenir_solde())
+        ))];
+        if dialogue.operation == TypeOperation::Transfert {
+            lignes.push(Line::from(format!(
+                "{}{}",
+                tr!("tui.destination", destination = dialogue.destination),
+                curseur(dialogue.champ == Champ::Destination)
+            )));
+        }
+        lignes.push(Line::from(format!(
+            "{}{}",
+            tr!("tui.montant", montant = dialogue.montant),
+            curseur(dialogue.champ == Champ::Montant)
+        )));
+        lignes.push(Line::from(format!(
+            "{}{}",
+            tr!("tui.cle", cle = dialogue.cle),
+            curseur(dialogue.champ == Champ::Cle)
+        )));
+        lignes.push(Line::from(""));
+
+        // Validation en direct de la saisie
+        lignes.push(match self.verifier(dialogue) {
+            Ok(_) => Line::from(tr!("tui.valider")).style(Style::default().fg(Color::Green)),
+            Err(erreur) => Line::from(erreur).style(Style::default().fg(Color::Red)),
+        });
+        lignes.push(Line::from(tr!("tui.changer_champ")));
+
+        let zone = centrer(frame.area(), 50, lignes.len() as u16 + 2);
+        frame.render_widget(Clear, zone);
+        frame.render_widget(
+            Paragraph::new(lignes).block(Block::default().borders(Borders::ALL).title(format!(" {} ", titre))),
+            zone,
+        );
+    }
+}
+
+// Rectangle de taille donnée centré dans la zone
+fn centrer(zone: Rect, largeur: u16, hauteur: u16) -> Rect {
+    let largeur = largeur.min(zone.width);
+    let hauteur = hauteur.min(zone.height);
+    Rect::new(
+        zone.x + (zone.width - largeur) / 2,
+        zone.y + (zone.height - hauteur) / 2,
+        largeur,
+        hauteur,
+    )
+}
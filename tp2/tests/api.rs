@@ -0,0 +1,151 @@
+// API HTTP : codes d'erreur, clés d'idempotence et erreurs du journal, sur un journal temporaire
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use tp2::api;
+use tp2::banque::Banque;
+use tp2::journal::Journal;
+
+// Réponse d'une requête : code, rejeu signalé par l'en-tête Idempotent-Replayed, corps JSON
+struct Reponse {
+    statut: StatusCode,
+    rejouee: bool,
+    corps: Value,
+}
+
+// Serveur sur la banque de démonstration, déjà enregistrée dans le journal
+fn serveur(dossier: &tempfile::TempDir, lecture_seule: bool) -> Router {
+    let mut journal = Journal::new(&dossier.path().join("banque.journal"));
+    let mut banque = Banque::demo();
+    journal.enregistrer(&mut banque).unwrap();
+    journal.definir_lecture_seule(lecture_seule);
+    api::routes(banque, journal)
+}
+
+async fn requete(serveur: &Router, methode: &str, uri: &str, corps: Option<Value>, cle: Option<&str>) -> Reponse {
+    let mut requete = Request::builder().method(methode).uri(uri);
+    if let Some(cle) = cle {
+        requete = requete.header("Idempotency-Key", cle);
+    }
+    let requete = match corps {
+        Some(corps) => requete.header("content-type", "application/json").body(Body::from(corps.to_string())),
+        None => requete.body(Body::empty()),
+    };
+    let reponse = serveur.clone().oneshot(requete.unwrap()).await.unwrap();
+    let statut = reponse.status();
+    let rejouee = reponse.headers().get("Idempotent-Replayed").is_some_and(|valeur| valeur == "true");
+    let octets = reponse.into_body().collect().await.unwrap().to_bytes();
+    let corps = if octets.is_empty() { Value::Null } else { serde_json::from_slice(&octets).unwrap() };
+    Reponse { statut, rejouee, corps }
+}
+
+async fn get(serveur: &Router, uri: &str) -> Reponse {
+    requete(serveur, "GET", uri, None, None).await
+}
+
+async fn post(serveur: &Router, uri: &str, corps: Value) -> Reponse {
+    requete(serveur, "POST", uri, Some(corps), None).await
+}
+
+// Le corps d'une erreur est toujours {"erreur": "..."}
+fn erreur(reponse: &Reponse) -> &str {
+    reponse.corps["erreur"].as_str().unwrap_or_else(|| panic!("corps d'erreur attendu: {}", reponse.corps))
+}
+
+#[tokio::test]
+async fn consultation_et_creation() {
+    let dossier = tempfile::tempdir().unwrap();
+    let serveur = serveur(&dossier, false);
+
+    let comptes = get(&serveur, "/comptes").await;
+    assert_eq!(comptes.statut, StatusCode::OK);
+    assert_eq!(comptes.corps.as_array().unwrap().len(), 3);
+    assert_eq!(get(&serveur, "/comptes/2").await.corps["solde"], 500.0);
+
+    let absent = get(&serveur, "/comptes/9").await;
+    assert_eq!(absent.statut, StatusCode::NOT_FOUND);
+    assert_eq!(erreur(&absent), "Aucun compte avec l'identifiant 9");
+    assert_eq!(get(&serveur, "/comptes/9/transactions").await.statut, StatusCode::NOT_FOUND);
+
+    let cree = post(&serveur, "/comptes", json!({ "nom": "Dan", "solde": 10.0 })).await;
+    assert_eq!(cree.statut, StatusCode::CREATED);
+    assert_eq!(cree.corps["id"], 4);
+    let doublon = post(&serveur, "/comptes", json!({ "nom": "Dan" })).await;
+    assert_eq!(doublon.statut, StatusCode::CONFLICT);
+    erreur(&doublon);
+    assert_eq!(post(&serveur, "/comptes", json!({ "nom": " " })).await.statut, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn operations_refusees() {
+    let dossier = tempfile::tempdir().unwrap();
+    let serveur = serveur(&dossier, false);
+
+    let trop = post(&serveur, "/comptes/2/retrait", json!({ "montant": 600.0 })).await;
+    assert_eq!(trop.statut, StatusCode::UNPROCESSABLE_ENTITY);
+    erreur(&trop);
+    let negatif = post(&serveur, "/comptes/2/depot", json!({ "montant": -5.0 })).await;
+    assert_eq!(negatif.statut, StatusCode::BAD_REQUEST);
+    let meme_compte = post(&serveur, "/transferts", json!({ "source": 1, "destination": 1, "montant": 5.0 })).await;
+    assert_eq!(meme_compte.statut, StatusCode::BAD_REQUEST);
+    let absent = post(&serveur, "/comptes/9/depot", json!({ "montant": 5.0 })).await;
+    assert_eq!(absent.statut, StatusCode::NOT_FOUND);
+
+    // Aucune opération refusée n'a touché aux soldes
+    assert_eq!(get(&serveur, "/comptes/2").await.corps["solde"], 500.0);
+}
+
+#[tokio::test]
+async fn une_cle_d_idempotence_rejoue_le_resultat_d_origine() {
+    let dossier = tempfile::tempdir().unwrap();
+    let serveur = serveur(&dossier, false);
+    let transfert = json!({ "source": 1, "destination": 2, "montant": 100.0 });
+
+    let premier = requete(&serveur, "POST", "/transferts", Some(transfert.clone()), Some("cle-1")).await;
+    assert_eq!(premier.statut, StatusCode::OK);
+    assert!(!premier.rejouee);
+    assert_eq!(premier.corps["solde_destination"], 600.0);
+
+    let rejeu = requete(&serveur, "POST", "/transferts", Some(transfert), Some("cle-1")).await;
+    assert_eq!(rejeu.statut, StatusCode::OK);
+    assert!(rejeu.rejouee);
+    assert_eq!(rejeu.corps, premier.corps);
+    assert_eq!(get(&serveur, "/comptes/2").await.corps["solde"], 600.0);
+
+    // La même clé pour une autre opération est refusée
+    let autre = requete(&serveur, "POST", "/comptes/2/depot", Some(json!({ "montant": 1.0 })), Some("cle-1")).await;
+    assert_eq!(autre.statut, StatusCode::CONFLICT);
+    assert!(!autre.rejouee);
+    erreur(&autre);
+
+    // Les opérations sont dans le journal
+    let rechargee = Journal::new(&dossier.path().join("banque.journal")).charger().unwrap();
+    assert_eq!(rechargee.compte(2).unwrap().obtenir_solde(), 600.0);
+}
+
+#[tokio::test]
+async fn erreurs_du_journal() {
+    let dossier = tempfile::tempdir().unwrap();
+    let lecture_seule = serveur(&dossier, true);
+    let refuse = post(&lecture_seule, "/comptes/2/depot", json!({ "montant": 5.0 })).await;
+    assert_eq!(refuse.statut, StatusCode::FORBIDDEN);
+    erreur(&refuse);
+    assert_eq!(get(&lecture_seule, "/comptes/2").await.corps["solde"], 500.0);
+
+    // Un journal illisible donne une erreur plutôt que des données périmées
+    let dossier = tempfile::tempdir().unwrap();
+    let serveur = serveur(&dossier, false);
+    let chemin = dossier.path().join("banque.journal");
+    std::fs::remove_file(&chemin).unwrap();
+    std::fs::create_dir(&chemin).unwrap();
+    for uri in ["/comptes", "/comptes/2", "/comptes/2/transactions", "/comptes?au=2025-01-01T00:00:00%2B01:00"] {
+        let reponse = get(&serveur, uri).await;
+        assert_eq!(reponse.statut, StatusCode::INTERNAL_SERVER_ERROR, "{}", uri);
+        erreur(&reponse);
+    }
+    let depot = post(&serveur, "/comptes/2/depot", json!({ "montant": 5.0 })).await;
+    assert_eq!(depot.statut, StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,156 @@
+// Export Beancount / Ledger-cli et import Beancount aller-retour
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use tp2::banque::Banque;
+use tp2::comptabilite::{exporter_beancount, exporter_ledger, importer_beancount};
+use tp2::compte::TypeCompte;
+use tp2::evenement::{Evenement, EvenementDate};
+
+fn le(jour: u32, mois: u32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, mois, jour, 12, 0, 0).unwrap()
+}
+
+fn date(jour: u32, mois: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, mois, jour).unwrap()
+}
+
+// Trois mois d'activité couvrant tous les types d'événements
+fn journal() -> Vec<EvenementDate> {
+    let evenements = vec![
+        (le(10, 1), Evenement::CompteOuvert { id: 1, nom: "Alice Dupont".to_string(), solde: 1000.0 }),
+        (le(10, 1), Evenement::CompteOuvert { id: 2, nom: "Bob \"le banquier\" Martin".to_string(), solde: 500.0 }),
+        (le(10, 1), Evenement::CompteOuvert { id: 3, nom: "Claire Durand".to_string(), solde: 0.0 }),
+        (le(12, 1), Evenement::Depot { id: 3, montant: 75.5 }),
+        (le(15, 1), Evenement::Retrait { id: 2, montant: 20.0 }),
+        (le(20, 1), Evenement::Transfert { source: 1, destination: 3, montant: 150.25 }),
+        (le(2, 2), Evenement::CompteRenomme { id: 3, nom: "Claire Martin".to_string() }),
+        (le(3, 2), Evenement::TypeCompteChange { id: 2, type_compte: TypeCompte::Epargne }),
+        (
+            le(5, 2),
+            Evenement::EncaissementDepose {
+                id: 1,
+                numero: 1,
+                libelle: "Chèque".to_string(),
+                montant: 300.0,
+                date_valeur: date(7, 2),
+            },
+        ),
+        (
+            le(6, 2),
+            Evenement::EncaissementDepose {
+                id: 2,
+                numero: 2,
+                libelle: "Virement entrant".to_string(),
+                montant: 80.0,
+                date_valeur: date(20, 2),
+            },
+        ),
+        (le(7, 2), Evenement::EncaissementCompense { numero: 1 }),
+        (le(10, 2), Evenement::EncaissementRejete { numero: 2, frais: 20.0 }),
+        (
+            le(12, 2),
+            Evenement::EncaissementDepose {
+                id: 3,
+                numero: 3,
+                libelle: "Chèque".to_string(),
+                montant: 40.0,
+                date_valeur: date(20, 3),
+            },
+        ),
+        (
+            le(1, 3),
+            Evenement::FraisPreleves {
+                id: 2,
+                libelle: "Frais d'opérations (1 au-delà de 0) 02/2025".to_string(),
+                montant: 1.0,
+                mois: date(1, 2),
+            },
+        ),
+        (le(1, 3), Evenement::MoisFacture { mois: date(1, 2) }),
+        (le(2, 3), Evenement::RapprochementValide { id: 1, jusqu_au: date(28, 2) }),
+        (le(3, 3), Evenement::CompteOuvert { id: 4, nom: "Dan".to_string(), solde: 200.0 }),
+        (le(4, 3), Evenement::Transfert { source: 4, destination: 2, montant: 50.0 }),
+        (le(5, 3), Evenement::CompteFerme { id: 4 }),
+        (le(5, 3), Evenement::InteretsVerses { id: 3, montant: 1.25, annee: 2024 }),
+        (le(5, 3), Evenement::ExerciceCloture { annee: 2024 }),
+        (le(5, 3), Evenement::ExerciceRouvert { annee: 2024 }),
+    ];
+    evenements
+        .into_iter()
+        .enumerate()
+        .map(|(index, (date, evenement))| EvenementDate { numero: index as u64 + 1, date, evenement })
+        .collect()
+}
+
+type Etat = (u32, String, f64, f64, TypeCompte, Option<NaiveDate>);
+
+fn etat(banque: &Banque) -> Vec<Etat> {
+    banque
+        .comptes
+        .iter()
+        .map(|compte| {
+            (
+                compte.obtenir_id(),
+                compte.obtenir_nom().to_string(),
+                compte.obtenir_solde(),
+                compte.obtenir_solde_comptable(),
+                compte.type_compte(),
+                compte.rapproche_jusqu_au(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn l_import_de_l_export_beancount_reproduit_les_soldes() {
+    let journal = journal();
+    let origine = Banque::reconstruire(&journal);
+
+    let export = exporter_beancount(&journal);
+    let importee = Banque::reconstruire(&importer_beancount(&export).unwrap());
+    assert_eq!(etat(&importee), etat(&origine));
+
+    // Un second aller-retour donne exactement le même fichier
+    let evenements = importer_beancount(&export).unwrap();
+    assert_eq!(exporter_beancount(&evenements), export);
+}
+
+#[test]
+fn l_export_beancount_contient_ouvertures_clotures_assertions_et_identifiants() {
+    let export = exporter_beancount(&journal());
+
+    assert!(export.contains("2025-01-10 open Assets:Tp2:C1 EUR\n  nom: \"Alice Dupont\"\n"));
+    assert!(export.contains("nom: \"Bob \\\"le banquier\\\" Martin\""));
+    assert!(export.contains("2025-03-05 close Assets:Tp2:C4\n"));
+    // Soldes de fin de janvier et de fin d'export
+    assert!(export.contains("2025-02-01 balance Assets:Tp2:C1 849.75 EUR\n"));
+    assert!(export.contains("2025-03-06 balance Assets:Tp2:Attente:C3 40.00 EUR\n"));
+    assert!(export.contains("2025-01-20 * \"Transfert de Alice Dupont vers Claire Durand\"\n  tp2-id: \"6\"\n"));
+    assert!(export.contains("  date-valeur: 2025-02-07\n"));
+    assert!(export.contains("  exercice: \"2024\"\n  Assets:Tp2:C3"));
+    assert!(export.contains("2025-03-05 custom \"tp2-cloture\" \"2024\"\n"));
+}
+
+#[test]
+fn l_export_ledger_controle_les_soldes() {
+    let export = exporter_ledger(&journal());
+
+    assert!(export.contains("account Assets:Tp2:C1\n    note Alice Dupont\n"));
+    assert!(export.contains("2025-01-15 * Retrait\n    ; tp2-id: 5\n"));
+    assert!(export.contains("2025-02-01 * Contrôle des soldes\n"));
+    assert!(export.contains("Assets:Tp2:C2                    0 EUR = 480.00 EUR\n"));
+    assert!(export.contains("; 2025-03-05 clôture de Assets:Tp2:C4\n"));
+}
+
+#[test]
+fn une_assertion_fausse_fait_echouer_l_import() {
+    let export = exporter_beancount(&journal()).replace(
+        "2025-02-01 balance Assets:Tp2:C1 849.75 EUR",
+        "2025-02-01 balance Assets:Tp2:C1 850.00 EUR",
+    );
+
+    let erreur = importer_beancount(&export).unwrap_err();
+    assert_eq!(
+        erreur.to_string(),
+        format!("Ligne {}: solde de Assets:Tp2:C1 faux: 850.00 attendu, 849.75 trouvé", erreur.ligne)
+    );
+}
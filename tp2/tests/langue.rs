@@ -0,0 +1,53 @@
+// Messages du tp2 en français et en anglais
+use tp2::banque::Banque;
+use tp2::comptabilite;
+use tp2::langue::{self, Langue};
+use tp2::menu::{self, Console};
+
+// Toutes les clés utilisées par le tp2 (tr!("cle", ...) et dire!(console, "cle", ...)) sont au catalogue
+// commun ; banque_commune vérifie les siennes et celles du tp1
+#[test]
+fn les_messages_utilises_existent() {
+    let francais = Langue::Francais.catalogue();
+    let dossier = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    for entree in std::fs::read_dir(dossier).unwrap() {
+        let chemin = entree.unwrap().path();
+        let source = std::fs::read_to_string(&chemin).unwrap();
+        let appels = source.match_indices("tr!(").chain(source.match_indices("dire!("));
+        // include_str!( se termine aussi par tr!( : seul un nom de macro complet compte
+        let appels = appels.filter(|(debut, _)| {
+            !source[..*debut].ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        });
+        for (debut, _) in appels {
+            let Some((_, suite)) = source[debut..].split_once('"') else {
+                continue;
+            };
+            let cle = suite.split('"').next().unwrap();
+            if cle == "cle" || cle.contains(' ') {
+                // Exemples dans les commentaires
+                continue;
+            }
+            assert!(francais.contains_key(cle), "{}: clé {} absente du catalogue", chemin.display(), cle);
+        }
+    }
+}
+
+// La langue est globale : tout ce qui est vérifié en anglais l'est dans ce seul test
+#[test]
+fn menu_et_import_en_anglais() {
+    langue::definir_langue(Langue::Anglais);
+    let mut banque = Banque::demo();
+    let mut sortie = Vec::new();
+    let mut console = Console::new("3\n2\n1,000.5\n\n1\n2\n".as_bytes(), &mut sortie);
+    menu::lancer(&mut console, &mut banque, None);
+    let import = comptabilite::importer_beancount("2025-01-01 open \"Assets:Tp2:C1").unwrap_err().to_string();
+    langue::definir_langue(Langue::Francais);
+
+    assert_eq!(import, "Line 1: unclosed quote");
+
+    let sortie = String::from_utf8(sortie).unwrap();
+    assert!(sortie.contains("Choose an option (1-18):"));
+    assert!(sortie.contains("Deposit of €1,000.50 completed successfully!"));
+    assert!(sortie.contains("Balance of Bob Martin: €1,500.50"));
+    assert!(sortie.ends_with("Goodbye!\n"));
+}
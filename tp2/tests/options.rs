@@ -0,0 +1,42 @@
+// Ligne de commande : les arguments inconnus et les valeurs invalides arrêtent le programme avec l'usage
+use std::process::{Command, Output, Stdio};
+
+fn lancer(arguments: &[&str]) -> Output {
+    let dossier = tempfile::tempdir().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_tp2"))
+        .args(["--langue", "fr", "--donnees"])
+        .arg(dossier.path().join("banque.journal"))
+        .args(arguments)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap()
+}
+
+fn refuse(arguments: &[&str], message: &str) {
+    let sortie = lancer(arguments);
+    let erreurs = String::from_utf8(sortie.stderr).unwrap();
+    assert!(!sortie.status.success(), "{arguments:?} accepté");
+    assert!(erreurs.starts_with(message), "{erreurs}");
+    assert!(erreurs.contains("Usage: tp2"), "{erreurs}");
+    assert!(sortie.stdout.is_empty());
+}
+
+#[test]
+fn arguments_refuses() {
+    refuse(&["--inconnue"], "Argument inconnu: --inconnue");
+    refuse(&["menu"], "Argument inconnu: menu");
+    refuse(&["tui", "fichier"], "Argument inconnu: fichier");
+    refuse(&["export", "a.beancount", "b.beancount"], "Argument inconnu: b.beancount");
+    refuse(&["--retention-cles", "-5"], "--retention-cles attend un nombre d'heures positif.");
+    refuse(&["--retention-cles", "0"], "--retention-cles attend un nombre d'heures positif.");
+    refuse(&["--retention-cles", "deux"], "--retention-cles attend un nombre d'heures positif.");
+    refuse(&["--tarifs"], "--tarifs attend une valeur.");
+}
+
+#[test]
+fn arguments_acceptes() {
+    // Sans saisie, le menu s'arrête aussitôt sur la fin de l'entrée
+    let sortie = lancer(&["--retention-cles", "48"]);
+    assert!(sortie.status.success());
+    assert!(String::from_utf8(sortie.stdout).unwrap().contains("Au revoir!"));
+}
@@ -0,0 +1,111 @@
+// Rapprochement d'un compte avec le relevé CSV de la banque
+use chrono::{Days, Local, NaiveDate};
+use tp2::banque::{Banque, ErreurBanque};
+use tp2::rapprochement::{ErreurRapprochement, Rapprochement, lire_releve};
+
+fn aujourd_hui() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+fn jj_mm_aaaa(date: NaiveDate) -> String {
+    date.format("%d/%m/%Y").to_string()
+}
+
+// Bob (id 2) : solde initial 500, dépôt de 40, transfert de 40 reçu d'Alice, transfert de 100 vers Alice
+fn banque_de_test() -> Banque {
+    let mut banque = Banque::demo();
+    banque.deposer(2, 40.0).unwrap();
+    banque.transferer(1, 2, 40.0).unwrap();
+    banque.transferer(2, 1, 100.0).unwrap();
+    banque
+}
+
+#[test]
+fn lecture_d_un_releve_francais_ou_anglais() {
+    let francais = lire_releve("Date;Libellé;Montant\n01/03/2025;CB Boulangerie;-1 234,50\n02/03/2025;Virement;20\n").unwrap();
+    assert_eq!(francais.len(), 2);
+    assert_eq!(francais[0].date, NaiveDate::from_ymd_opt(2025, 3, 1).unwrap());
+    assert_eq!(francais[0].montant, -1234.5);
+
+    let anglais = lire_releve("2025-03-01,Card payment,-12.30\n").unwrap();
+    assert_eq!(anglais[0].libelle, "Card payment");
+    assert_eq!(anglais[0].montant, -12.3);
+
+    let invalide = lire_releve("01/03/2025;Retrait;-20\n02/03/2025;Retrait;abc\n");
+    assert!(matches!(invalide, Err(ErreurRapprochement::LigneInvalide { ligne: 2, .. })));
+}
+
+#[test]
+fn le_rapprochement_automatique_utilise_montant_date_et_libelle() {
+    let banque = banque_de_test();
+    let hier = jj_mm_aaaa(aujourd_hui() - Days::new(1));
+    let loin = jj_mm_aaaa(aujourd_hui() - Days::new(30));
+    let csv = format!(
+        "{hier};VIR DE ALICE DUPONT;40,00\n{hier};DEPOT AGENCE;40,00\n{hier};VIR VERS ALICE DUPONT;-100,00\n{loin};Solde initial;500,00\n"
+    );
+
+    let mut seance = Rapprochement::nouveau(&banque, 2, lire_releve(&csv).unwrap()).unwrap();
+    assert_eq!(seance.rapprocher_automatiquement(), 3);
+
+    // Même montant, même date : c'est le libellé qui départage le dépôt et le transfert reçu
+    let libelle = |ligne: usize| {
+        let (_, transaction) = seance.correspondances().iter().find(|(l, _)| *l == ligne).unwrap();
+        seance.transactions()[*transaction].libelle.clone()
+    };
+    assert_eq!(libelle(0), "Transfert de Alice Dupont");
+    assert_eq!(libelle(1), "Dépôt");
+
+    // La ligne trop ancienne et le solde initial restent à rapprocher des deux côtés
+    assert_eq!(seance.lignes_non_rapprochees(), vec![3]);
+    let restantes = seance.transactions_non_rapprochees();
+    assert_eq!(restantes.len(), 1);
+    assert_eq!(seance.transactions()[restantes[0]].libelle, "Solde initial");
+}
+
+#[test]
+fn rapprochement_manuel_et_validation() {
+    let mut banque = banque_de_test();
+    let aujourd_hui = aujourd_hui();
+    let loin = jj_mm_aaaa(aujourd_hui - Days::new(30));
+    let date = jj_mm_aaaa(aujourd_hui);
+    let csv = format!(
+        "{loin};Ouverture;500\n{date};Depot;40\n{date};Virement recu;40\n{date};Virement;-100\n"
+    );
+    let mut seance = Rapprochement::nouveau(&banque, 2, lire_releve(&csv).unwrap()).unwrap();
+    seance.rapprocher_automatiquement();
+
+    // Tant que tout n'est pas rapproché, la période ne peut pas être validée
+    assert_eq!(
+        seance.valider(&mut banque, aujourd_hui),
+        Err(ErreurBanque::RapprochementIncomplet { lignes: 1, transactions: 1 })
+    );
+
+    let solde_initial = seance.transactions_non_rapprochees()[0];
+    assert!(matches!(seance.associer(1, solde_initial), Err(ErreurRapprochement::MontantsDifferents)));
+    seance.associer(0, solde_initial).unwrap();
+    assert!(matches!(seance.associer(0, solde_initial), Err(ErreurRapprochement::DejaRapprochee)));
+    seance.valider(&mut banque, aujourd_hui).unwrap();
+    assert_eq!(banque.compte(2).unwrap().rapproche_jusqu_au(), Some(aujourd_hui));
+
+    // Les transactions rapprochées sont verrouillées : elles ne sont plus proposées
+    let suivante = Rapprochement::nouveau(&banque, 2, Vec::new()).unwrap();
+    assert!(suivante.transactions().is_empty());
+    assert_eq!(
+        banque.marquer_rapproche(2, aujourd_hui - Days::new(1)),
+        Err(ErreurBanque::PeriodeRapprochee(aujourd_hui))
+    );
+}
+
+#[test]
+fn un_encaissement_d_une_periode_rapprochee_ne_peut_plus_etre_rejete() {
+    let mut banque = Banque::demo();
+    let numero = banque
+        .deposer_en_attente(2, 300.0, aujourd_hui() + Days::new(2), "Chèque")
+        .unwrap();
+    banque.marquer_rapproche(2, aujourd_hui()).unwrap();
+
+    assert_eq!(
+        banque.rejeter_encaissement(numero),
+        Err(ErreurBanque::PeriodeRapprochee(aujourd_hui()))
+    );
+}
@@ -0,0 +1,179 @@
+// Scénarios du menu texte : on rejoue une saisie clavier et on vérifie ce qui est affiché
+use tp2::banque::Banque;
+use tp2::menu::{self, Console};
+
+// Exécute le menu sur la banque de démonstration avec la saisie donnée
+fn executer(saisie: &str) -> (Banque, String) {
+    let mut banque = Banque::demo();
+    let mut sortie = Vec::new();
+    let mut console = Console::new(saisie.as_bytes(), &mut sortie);
+    menu::lancer(&mut console, &mut banque, None);
+    (banque, String::from_utf8(sortie).unwrap())
+}
+
+fn solde(banque: &Banque, id: u32) -> f64 {
+    banque.compte(id).unwrap().obtenir_solde()
+}
+
+#[test]
+fn afficher_solde() {
+    let (_, sortie) = executer("1\n2\n");
+    assert!(sortie.contains("Solde de Bob Martin: 500,00 €"));
+    assert!(sortie.ends_with("Au revoir!\n"));
+}
+
+#[test]
+fn retrait_reussi() {
+    let (banque, sortie) = executer("2\n1\n200\n\n");
+    assert!(sortie.contains("Retrait de 200,00 € effectué avec succès!"));
+    assert!(sortie.contains("Nouveau solde: 800,00 €"));
+    assert_eq!(solde(&banque, 1), 800.0);
+}
+
+#[test]
+fn retrait_refuse_si_solde_insuffisant() {
+    let (banque, sortie) = executer("2\n2\n600\n\n");
+    assert!(sortie.contains("Retrait impossible (solde insuffisant ou montant invalide)."));
+    assert_eq!(solde(&banque, 2), 500.0);
+}
+
+#[test]
+fn depot_negatif_refuse() {
+    let (banque, sortie) = executer("3\n3\n-20\n\n");
+    assert!(sortie.contains("Dépôt impossible (montant négatif ou nul)."));
+    assert_eq!(solde(&banque, 3), 750.0);
+}
+
+#[test]
+fn depot_avec_virgule_et_milliers() {
+    let (banque, sortie) = executer("3\n3\n1 250,5\n\n");
+    assert!(sortie.contains("Dépôt de 1 250,50 € effectué avec succès!"));
+    assert!(sortie.contains("Nouveau solde: 2 000,50 €"));
+    assert_eq!(solde(&banque, 3), 2000.5);
+}
+
+#[test]
+fn transfert_entre_comptes() {
+    let (banque, sortie) = executer("8\n1\n3\n250\n\n");
+    assert!(sortie.contains("Transfert de 250,00 € effectué avec succès!"));
+    assert!(sortie.contains("De: Alice Dupont (nouveau solde: 750,00 €)"));
+    assert!(sortie.contains("Vers: Claire Durand (nouveau solde: 1 000,00 €)"));
+    assert_eq!(solde(&banque, 1) + solde(&banque, 3), 1750.0);
+}
+
+#[test]
+fn transfert_vers_le_meme_compte_refuse() {
+    let (_, sortie) = executer("8\n2\n2\n");
+    assert!(sortie.contains("Choix invalide ou même compte que la source!"));
+}
+
+#[test]
+fn creer_compte_puis_doublon() {
+    let (banque, sortie) = executer("6\nDan\n200\n6\nDan\n");
+    assert!(sortie.contains("Compte 'Dan' créé avec succès avec un solde de 200,00 €!"));
+    assert!(sortie.contains("Un compte avec ce nom existe déjà!"));
+    assert_eq!(banque.comptes.len(), 4);
+}
+
+#[test]
+fn supprimer_compte_avec_confirmation() {
+    let (banque, sortie) = executer("7\n2\nnon\n7\n2\noui\n");
+    assert!(sortie.contains("Suppression annulée."));
+    assert!(sortie.contains("Compte 'Bob Martin' supprimé avec succès!"));
+    assert!(banque.compte(2).is_err());
+}
+
+#[test]
+fn renommer_compte() {
+    let (banque, sortie) = executer("4\n1\nAlice Martin\n");
+    assert!(sortie.contains("Compte renommé avec succès en: Alice Martin"));
+    assert_eq!(banque.compte(1).unwrap().obtenir_nom(), "Alice Martin");
+    assert_eq!(solde(&banque, 1), 1000.0);
+}
+
+#[test]
+fn rechercher_compte() {
+    let (_, sortie) = executer("9\nmar\n");
+    assert!(sortie.contains("• Bob Martin - Solde: 500,00 €"));
+    assert!(!sortie.contains("• Alice"));
+}
+
+#[test]
+fn statistiques() {
+    let (_, sortie) = executer("10\n");
+    assert!(sortie.contains("Nombre total de comptes: 3"));
+    assert!(sortie.contains("Solde total de la banque: 2 250,00 €"));
+    assert!(sortie.contains("Compte le plus riche: Alice Dupont (1 000,00 €)"));
+}
+
+#[test]
+fn choix_invalides() {
+    let (_, sortie) = executer("abc\n42\n");
+    assert_eq!(sortie.matches("Veuillez entrer un nombre entre 1 et 18.").count(), 2);
+    assert!(sortie.ends_with("Au revoir!\n"));
+}
+
+#[test]
+fn une_reponse_invalide_est_redemandee() {
+    let (banque, sortie) = executer("2\n9\n1\ncent\n100\n\n");
+    assert!(sortie.contains("Choix invalide!"));
+    assert!(sortie.contains("Montant invalide!"));
+    assert_eq!(solde(&banque, 1), 900.0);
+
+    // Au bout de trois réponses invalides, retour au menu
+    let (_, sortie) = executer("1\n0\n9\nBob\n10\n");
+    assert_eq!(sortie.matches("Choix invalide!").count(), 3);
+    assert!(sortie.contains("Saisie abandonnée: trop de réponses invalides"));
+    assert!(sortie.contains("Nombre total de comptes: 3"));
+
+    // Une date mal saisie est redemandée au lieu d'abandonner le dépôt
+    let (banque, sortie) = executer("11\n1\n2\n100\n32/01/2000\n01/01/2000\n");
+    assert!(sortie.contains("Date invalide!"));
+    assert_eq!(solde(&banque, 1), 1100.0);
+}
+
+#[test]
+fn fin_de_saisie_au_milieu_d_une_operation() {
+    let (banque, sortie) = executer("2\n1\n");
+    assert!(sortie.ends_with("Au revoir!\n"));
+    assert_eq!(solde(&banque, 1), 1000.0);
+}
+
+#[test]
+fn fin_de_saisie_quitte_le_menu() {
+    let (_, sortie) = executer("5\n");
+    assert!(sortie.contains("1. Alice Dupont - Solde: 1 000,00 €"));
+    assert!(sortie.ends_with("Au revoir!\n"));
+}
+
+#[test]
+fn retrait_rejoue_avec_la_meme_cle() {
+    let (banque, sortie) = executer("2\n1\n100\ncle-1\n2\n1\n100\ncle-1\n");
+    assert!(sortie.contains("Clé déjà utilisée"));
+    assert_eq!(solde(&banque, 1), 900.0);
+}
+
+#[test]
+fn cle_reutilisee_pour_une_autre_operation() {
+    let (banque, sortie) = executer("2\n1\n100\ncle-1\n3\n1\n100\ncle-1\n");
+    assert!(sortie.contains("La clé d'idempotence 'cle-1' a déjà servi pour une autre opération"));
+    assert_eq!(solde(&banque, 1), 900.0);
+}
+
+#[test]
+fn depot_de_cheque_puis_rejet() {
+    let (banque, sortie) = executer("11\n2\n1\n300\n31/12/2999\n1\n2\n12\n1\n");
+    assert!(sortie.contains("Chèque n°1 de 300,00 € enregistré, disponible le 31/12/2999."));
+    assert!(sortie.contains("Solde de Bob Martin: 500,00 €"));
+    assert!(sortie.contains("Solde comptable (avec encaissements en attente): 800,00 €"));
+    assert!(sortie.contains("n°1 - Bob Martin - Chèque de 300,00 €, valeur le 31/12/2999"));
+    assert!(sortie.contains("Chèque n°1 de 300,00 € rejeté, frais de 20,00 € prélevés."));
+    assert_eq!(solde(&banque, 2), 480.0);
+}
+
+#[test]
+fn virement_entrant_date_passee_disponible_au_tour_suivant() {
+    let (banque, sortie) = executer("11\n1\n2\n100\n01/01/2000\n");
+    assert!(sortie.contains("✅ Virement entrant de 100,00 € désormais disponible."));
+    assert_eq!(solde(&banque, 1), 1100.0);
+}
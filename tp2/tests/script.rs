@@ -0,0 +1,105 @@
+// Scripts d'opérations : lecture, erreurs numérotées et exécution tout ou rien
+use tp2::banque::Banque;
+use tp2::script::{self, Commande, ErreurScript};
+
+#[test]
+fn lecture_des_commandes_et_commentaires() {
+    let instructions = script::lire_script(
+        "# Mise en place\n\ncreate \"Dan Martin\" 200\ntransfer 1 3 50   # loyer\nassert-balance 3 800\nclose 2\n",
+    )
+    .unwrap();
+
+    let commandes: Vec<_> = instructions.iter().map(|instruction| (instruction.ligne, &instruction.commande)).collect();
+    assert_eq!(
+        commandes,
+        vec![
+            (3, &Commande::Creer { nom: "Dan Martin".to_string(), solde: 200.0 }),
+            (4, &Commande::Transferer { source: 1, destination: 3, montant: 50.0 }),
+            (5, &Commande::VerifierSolde { id: 3, solde: 800.0 }),
+            (6, &Commande::Fermer { id: 2 }),
+        ]
+    );
+}
+
+#[test]
+fn montants_avec_point_ou_virgule() {
+    let instructions = script::lire_script("deposit 1 12,50\nwithdraw 1 12.5\ncreate \"Dan\" 1000,25€\n").unwrap();
+    let commandes: Vec<_> = instructions.into_iter().map(|instruction| instruction.commande).collect();
+    assert_eq!(
+        commandes,
+        vec![
+            Commande::Deposer { id: 1, montant: 12.5 },
+            Commande::Retirer { id: 1, montant: 12.5 },
+            Commande::Creer { nom: "Dan".to_string(), solde: 1000.25 },
+        ]
+    );
+}
+
+#[test]
+fn erreurs_de_syntaxe_numerotees() {
+    let erreur = |texte: &str| script::lire_script(texte).unwrap_err();
+
+    assert_eq!(erreur("deposit 1 10\npay 1 10\n").ligne, 2);
+    assert_eq!(erreur("deposit 1 10\npay 1 10\n").to_string(), "Ligne 2: commande inconnue: pay");
+    assert_eq!(erreur("\n\ntransfer 1 2\n").ligne, 3);
+    assert_eq!(erreur("withdraw un 10").message, "identifiant de compte invalide: un");
+    assert_eq!(erreur("deposit 1 NaN").message, "montant invalide: NaN");
+    assert_eq!(erreur("deposit 1 dix").message, "montant invalide: dix");
+    assert_eq!(erreur("create \"Dan 200").message, "guillemet non fermé");
+}
+
+#[test]
+fn un_echec_annule_tout_le_script() {
+    let mut banque = Banque::demo();
+    banque.marquer_enregistres();
+    let instructions = script::lire_script("create \"Dan\" 200\ntransfer 1 4 50\nassert-balance 4 300\n").unwrap();
+
+    let erreur = script::executer(&mut banque, &instructions).unwrap_err();
+
+    assert_eq!(
+        erreur,
+        ErreurScript {
+            ligne: 3,
+            message: "solde du compte 4: 300,00 € attendu, 250,00 € trouvé".to_string()
+        }
+    );
+    assert_eq!(banque.comptes.len(), 3);
+    assert_eq!(banque.compte(1).unwrap().obtenir_solde(), 1000.0);
+    assert!(banque.nouveaux_evenements().is_empty());
+}
+
+#[test]
+fn une_operation_refusee_indique_sa_ligne() {
+    let mut banque = Banque::demo();
+    let instructions = script::lire_script("deposit 1 100\nwithdraw 2 10000\n").unwrap();
+
+    let erreur = script::executer(&mut banque, &instructions).unwrap_err();
+
+    assert_eq!(erreur.ligne, 2);
+    assert_eq!(banque.compte(1).unwrap().obtenir_solde(), 1000.0);
+}
+
+// Le script de régression est rejoué sur la banque de démonstration
+#[test]
+fn script_de_regression() {
+    let mut banque = Banque::demo();
+    banque.marquer_enregistres();
+    let instructions = script::lire_script(include_str!("scripts/demo.tp2")).unwrap();
+
+    script::executer(&mut banque, &instructions).unwrap();
+
+    let soldes: Vec<_> = banque
+        .comptes
+        .iter()
+        .map(|compte| (compte.obtenir_id(), compte.obtenir_nom().to_string(), compte.obtenir_solde()))
+        .collect();
+    assert_eq!(
+        soldes,
+        vec![
+            (1, "Alice Dupont".to_string(), 950.0),
+            (2, "Bob Martin".to_string(), 0.0),
+            (4, "Dan Martin".to_string(), 300.0),
+        ]
+    );
+    assert!(!banque.nouveaux_evenements().is_empty());
+}
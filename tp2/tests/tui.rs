@@ -0,0 +1,145 @@
+// Interface plein écran : tri, filtre, validation des dialogues et opérations enregistrées dans le journal,
+// en pilotant l'état avec des touches sur un journal temporaire
+use ratatui::crossterm::event::KeyCode;
+use tp2::banque::Banque;
+use tp2::journal::Journal;
+use tp2::tui::App;
+
+// Banque de démonstration déjà enregistrée dans le journal
+fn journal(dossier: &tempfile::TempDir) -> (Banque, Journal) {
+    let mut journal = Journal::new(&dossier.path().join("banque.journal"));
+    let mut banque = Banque::demo();
+    journal.enregistrer(&mut banque).unwrap();
+    (banque, journal)
+}
+
+fn taper(app: &mut App, texte: &str) {
+    for c in texte.chars() {
+        app.touche(KeyCode::Char(c));
+    }
+}
+
+fn solde(banque: &Banque, id: u32) -> f64 {
+    banque.compte(id).unwrap().obtenir_solde()
+}
+
+#[test]
+fn tri_et_filtre() {
+    let dossier = tempfile::tempdir().unwrap();
+    let (mut banque, mut journal) = journal(&dossier);
+    let mut app = App::new(&mut banque, &mut journal);
+
+    // Alice 1000, Bob 500, Claire 750
+    assert_eq!(app.comptes_affiches(), [1, 2, 3]);
+    app.touche(KeyCode::Char('s'));
+    assert_eq!(app.comptes_affiches(), [2, 3, 1]);
+    assert_eq!(app.message(), "Tri par solde ↑");
+    app.touche(KeyCode::Char('s'));
+    assert_eq!(app.comptes_affiches(), [1, 3, 2]);
+    app.touche(KeyCode::Char('s'));
+    assert_eq!(app.comptes_affiches(), [1, 2, 3]);
+
+    // Le filtre ignore la casse ; les touches tapées pendant la saisie ne sont pas des commandes
+    app.touche(KeyCode::Char('/'));
+    taper(&mut app, "Rx");
+    assert!(app.comptes_affiches().is_empty());
+    app.touche(KeyCode::Backspace);
+    assert_eq!(app.comptes_affiches(), [2, 3]);
+    taper(&mut app, "t");
+    assert_eq!(app.comptes_affiches(), [2]);
+    app.touche(KeyCode::Enter);
+    assert_eq!(app.comptes_affiches(), [2]);
+
+    // Échap pendant la saisie efface le filtre
+    app.touche(KeyCode::Char('/'));
+    app.touche(KeyCode::Esc);
+    assert_eq!(app.comptes_affiches(), [1, 2, 3]);
+    assert!(!app.touche(KeyCode::Char('q')));
+}
+
+#[test]
+fn validation_des_dialogues() {
+    let dossier = tempfile::tempdir().unwrap();
+    let (mut banque, mut journal) = journal(&dossier);
+    let mut app = App::new(&mut banque, &mut journal);
+
+    // Retrait sur Bob : le montant est vérifié en direct et le dialogue reste ouvert tant qu'il est invalide
+    app.touche(KeyCode::Down);
+    app.touche(KeyCode::Char('r'));
+    assert!(app.dialogue_ouvert());
+    taper(&mut app, "abc");
+    assert_eq!(app.erreur_dialogue().as_deref(), Some("Montant invalide!"));
+    taper(&mut app, "600");
+    assert_eq!(app.erreur_dialogue().as_deref(), Some("Solde insuffisant (500,00 € disponibles)"));
+    app.touche(KeyCode::Enter);
+    assert!(app.dialogue_ouvert());
+    app.touche(KeyCode::Esc);
+    assert!(!app.dialogue_ouvert());
+    assert_eq!(app.message(), "Opération annulée.");
+
+    // Transfert depuis Bob : la destination est un numéro de compte différent de la source
+    app.touche(KeyCode::Char('t'));
+    taper(&mut app, "2");
+    app.touche(KeyCode::Tab);
+    taper(&mut app, "50");
+    assert_eq!(app.erreur_dialogue().as_deref(), Some("Choix invalide ou même compte que la source!"));
+    app.touche(KeyCode::BackTab);
+    app.touche(KeyCode::Backspace);
+    taper(&mut app, "9");
+    assert_eq!(app.erreur_dialogue().as_deref(), Some("Choix invalide ou même compte que la source!"));
+    app.touche(KeyCode::Backspace);
+    taper(&mut app, "3");
+    assert_eq!(app.erreur_dialogue(), None);
+    app.touche(KeyCode::Esc);
+    assert_eq!(solde(app.banque(), 2), 500.0);
+}
+
+#[test]
+fn les_operations_sont_enregistrees_dans_le_journal() {
+    let dossier = tempfile::tempdir().unwrap();
+    let (mut banque, mut journal) = journal(&dossier);
+    let mut app = App::new(&mut banque, &mut journal);
+
+    // Transfert de 100,50 € d'Alice vers Claire
+    app.touche(KeyCode::Char('t'));
+    taper(&mut app, "3");
+    app.touche(KeyCode::Tab);
+    taper(&mut app, "100,5");
+    app.touche(KeyCode::Enter);
+    assert!(!app.dialogue_ouvert());
+    assert_eq!(app.message(), "Transfert de 100,50 € vers Claire Durand effectué avec succès!");
+
+    // Dépôt avec une clé d'idempotence, rejoué sans être refait
+    for _ in 0..2 {
+        app.touche(KeyCode::Char('d'));
+        taper(&mut app, "50");
+        app.touche(KeyCode::Tab);
+        taper(&mut app, "cle-1");
+        app.touche(KeyCode::Enter);
+    }
+    assert!(app.message().starts_with("Clé déjà utilisée"));
+    assert!(app.message().ends_with("Dépôt de 50,00 € effectué avec succès!"));
+    assert_eq!(solde(app.banque(), 1), 949.5);
+    assert_eq!(solde(app.banque(), 3), 850.5);
+
+    // Tout est déjà dans le journal
+    let rechargee = Journal::new(&dossier.path().join("banque.journal")).charger().unwrap();
+    assert_eq!(solde(&rechargee, 1), 949.5);
+    assert_eq!(solde(&rechargee, 3), 850.5);
+}
+
+#[test]
+fn un_journal_en_lecture_seule_refuse_les_operations() {
+    let dossier = tempfile::tempdir().unwrap();
+    let (mut banque, mut journal) = journal(&dossier);
+    journal.definir_lecture_seule(true);
+    let mut app = App::new(&mut banque, &mut journal);
+    app.mettre_a_jour();
+
+    app.touche(KeyCode::Char('d'));
+    taper(&mut app, "50");
+    app.touche(KeyCode::Enter);
+    assert!(!app.dialogue_ouvert());
+    assert!(app.message().ends_with("Les données sont ouvertes en lecture seule"));
+    assert_eq!(solde(app.banque(), 1), 1000.0);
+}
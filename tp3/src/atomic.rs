@@ -0,0 +1,88 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Écritures qui ne laissent jamais un fichier à moitié écrit : en cas d'erreur (disque plein, coupure),
+// le fichier garde son contenu d'avant.
+
+static TEMPORARY_FILES: AtomicUsize = AtomicUsize::new(0);
+
+// Remplace le contenu du fichier, ou le crée
+pub fn write(path: &Path, content: &[u8]) -> io::Result<()> {
+    write_with(path, |file| file.write_all(content))
+}
+
+// Le contenu est écrit par `fill` dans un fichier temporaire caché du même répertoire, synchronisé sur le
+// disque, puis renommé sur la cible : un rename est atomique, la cible est soit l'ancienne version, soit
+// la nouvelle. Les permissions d'un fichier existant sont conservées.
+pub fn write_with(path: &Path, fill: impl FnOnce(&mut File) -> io::Result<()>) -> io::Result<()> {
+    let temporary = temporary_path(path)?;
+    let mut file = OpenOptions::new().write(true).create_new(true).open(&temporary)?;
+    let resultat = (|| {
+        if let Ok(metadata) = fs::metadata(path) {
+            file.set_permissions(metadata.permissions())?;
+        }
+        fill(&mut file)?;
+        file.sync_all()?;
+        fs::rename(&temporary, path)
+    })();
+    if resultat.is_err() {
+        let _ = fs::remove_file(&temporary);
+        return resultat;
+    }
+    sync_directory(path)
+}
+
+// Ajoute des lignes à la fin du fichier (créé s'il n'existe pas), sans réécrire ce qu'il contient :
+// un retour à la ligne est d'abord ajouté si la dernière ligne n'en a pas
+pub fn append(path: &Path, content: &str) -> io::Result<()> {
+    append_with(path, |file| {
+        file.write_all(content.as_bytes())?;
+        if !content.ends_with('\n') {
+            file.write_all(b"\n")?;
+        }
+        Ok(())
+    })
+}
+
+// En cas d'erreur pendant `fill`, le fichier est ramené à sa taille d'origine
+pub fn append_with(path: &Path, fill: impl FnOnce(&mut File) -> io::Result<()>) -> io::Result<()> {
+    let mut file = OpenOptions::new().read(true).append(true).create(true).open(path)?;
+    let length = file.metadata()?.len();
+    let resultat = (|| {
+        if length > 0 {
+            let mut last = [0];
+            file.seek(SeekFrom::Start(length - 1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                file.write_all(b"\n")?;
+            }
+        }
+        fill(&mut file)?;
+        file.sync_data()
+    })();
+    if resultat.is_err() {
+        let _ = file.set_len(length).and_then(|_| file.sync_data());
+    }
+    resultat
+}
+
+// « .nom.1234.0.tmp » à côté de la cible : même système de fichiers, pour que rename soit atomique
+fn temporary_path(path: &Path) -> io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "nom de fichier manquant"))?;
+    let number = TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed);
+    let temporary = format!(".{}.{}.{}.tmp", name.to_string_lossy(), process::id(), number);
+    Ok(path.with_file_name(temporary))
+}
+
+// Le renommage n'est durable qu'une fois le répertoire lui-même synchronisé
+fn sync_directory(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => File::open(parent)?.sync_all(),
+        _ => File::open(".")?.sync_all(),
+    }
+}
@@ -0,0 +1,118 @@
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use regex::{NoExpand, Regex};
+use crate::versions;
+
+// Modifications ligne à ligne et remplacements, préparés puis appliqués après confirmation
+
+// Modification de lignes ; les numéros commencent à 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineEdit {
+    // Insère le texte (une ou plusieurs lignes) avant la ligne `line` ; après la dernière ligne, ajoute
+    // le texte à la fin
+    Insert { line: usize, text: String },
+    Delete { lines: RangeInclusive<usize> },
+    Replace { line: usize, text: String },
+}
+
+// Modification préparée, pas encore écrite : le contenu avant et après, pour l'aperçu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditPreview {
+    // Nom du fichier tel qu'il a été saisi
+    pub file: String,
+    pub(crate) path: PathBuf,
+    pub original: String,
+    pub content: String,
+    // Nombre de correspondances remplacées (0 pour une modification de lignes)
+    pub replacements: usize,
+}
+
+impl EditPreview {
+    pub fn is_unchanged(&self) -> bool {
+        self.original == self.content
+    }
+
+    // Lignes touchées avant et après, au format unifié
+    pub fn diff(&self) -> String {
+        versions::unified_diff(
+            &self.original,
+            &self.content,
+            &format!("{} (avant)", self.file),
+            &format!("{} (après)", self.file),
+        )
+    }
+}
+
+// Lignes d'un contenu sans leur fin de ligne, et si la dernière en avait une. Un contenu vide est
+// considéré comme terminé par une fin de ligne, pour que les lignes ajoutées en reçoivent une.
+fn split_lines(content: &str) -> (Vec<String>, bool) {
+    if content.is_empty() {
+        return (Vec::new(), true);
+    }
+    let trailing_newline = content.ends_with('\n');
+    let content = content.strip_suffix('\n').unwrap_or(content);
+    (content.split('\n').map(str::to_string).collect(), trailing_newline)
+}
+
+fn join_lines(lines: &[String], trailing_newline: bool) -> String {
+    let mut content = lines.join("\n");
+    if trailing_newline && !lines.is_empty() {
+        content.push('\n');
+    }
+    content
+}
+
+fn check_line(line: usize, count: usize, allowed: RangeInclusive<usize>) -> io::Result<()> {
+    if allowed.contains(&line) {
+        Ok(())
+    } else {
+        let message = format!("la ligne {} n'existe pas, le fichier a {} ligne(s)", line, count);
+        Err(io::Error::new(io::ErrorKind::InvalidInput, message))
+    }
+}
+
+// Contenu après une modification de lignes ; un numéro hors du fichier donne InvalidInput
+pub fn apply_line_edit(content: &str, edit: &LineEdit) -> io::Result<String> {
+    let (mut lines, trailing_newline) = split_lines(content);
+    let count = lines.len();
+    match edit {
+        LineEdit::Insert { line, text } => {
+            check_line(*line, count, 1..=count + 1)?;
+            let (new_lines, _) = split_lines(text);
+            // Une ligne vide insérée reste une ligne
+            let new_lines = if new_lines.is_empty() { vec![String::new()] } else { new_lines };
+            lines.splice(line - 1..line - 1, new_lines);
+        }
+        LineEdit::Delete { lines: range } => {
+            check_line(*range.start(), count, 1..=count)?;
+            check_line(*range.end(), count, *range.start()..=count)?;
+            lines.drain(range.start() - 1..*range.end());
+        }
+        LineEdit::Replace { line, text } => {
+            check_line(*line, count, 1..=count)?;
+            lines[line - 1] = text.trim_end_matches('\n').to_string();
+        }
+    }
+    Ok(join_lines(&lines, trailing_newline))
+}
+
+// Contenu après le remplacement de toutes les correspondances de `regex`, ligne par ligne, et leur
+// nombre. Avec `expand`, `$1` ou `${nom}` dans `replacement` désignent les groupes capturés ; sinon le
+// remplacement est pris tel quel.
+pub fn replace_all(content: &str, regex: &Regex, replacement: &str, expand: bool) -> (String, usize) {
+    let (lines, trailing_newline) = split_lines(content);
+    let mut replacements = 0;
+    let lines: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            replacements += regex.find_iter(line).count();
+            if expand {
+                regex.replace_all(line, replacement).into_owned()
+            } else {
+                regex.replace_all(line, NoExpand(replacement)).into_owned()
+            }
+        })
+        .collect();
+    (join_lines(&lines, trailing_newline), replacements)
+}
@@ -0,0 +1,648 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use chrono::TimeDelta;
+use crate::atomic;
+use crate::editing::{self, EditPreview, LineEdit};
+use crate::listing::{self, EntryDetails, ListOptions};
+use crate::search::{self, SearchLine, SearchOptions};
+use crate::transfer::{self, OverwritePolicy};
+use crate::trash::{self, TrashItem};
+use crate::versions::{self, Version};
+
+#[derive(Debug)]
+pub enum FileManagerError {
+    Io(io::Error),
+    // Le chemin demandé sort du répertoire racine (.., chemin absolu ou lien symbolique)
+    OutsideRoot(String),
+}
+
+impl fmt::Display for FileManagerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileManagerError::Io(e) => write!(f, "{}", e),
+            FileManagerError::OutsideRoot(path) => write!(f, "accès refusé, '{}' est hors du répertoire racine", path),
+        }
+    }
+}
+
+impl std::error::Error for FileManagerError {}
+
+impl From<io::Error> for FileManagerError {
+    fn from(e: io::Error) -> FileManagerError {
+        FileManagerError::Io(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, FileManagerError>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Directory,
+    Symlink,
+}
+
+impl EntryKind {
+    pub(crate) fn of(file_type: fs::FileType) -> EntryKind {
+        if file_type.is_symlink() {
+            EntryKind::Symlink
+        } else if file_type.is_dir() {
+            EntryKind::Directory
+        } else {
+            EntryKind::File
+        }
+    }
+}
+
+// Élément d'un répertoire, affiché avec la marque de son type comme ls -F : « dossier/ », « lien@ »
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    pub kind: EntryKind,
+}
+
+impl Entry {
+    pub(crate) fn from_dir_entry(entry: &fs::DirEntry) -> io::Result<Entry> {
+        Ok(Entry {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            kind: EntryKind::of(entry.file_type()?),
+        })
+    }
+
+    pub fn marker(&self) -> &'static str {
+        match self.kind {
+            EntryKind::File => "",
+            EntryKind::Directory => "/",
+            EntryKind::Symlink => "@",
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.name, self.marker())
+    }
+}
+
+pub struct FileManager {
+    // Chemins canoniques : current_directory est toujours dans root
+    root: PathBuf,
+    current_directory: PathBuf,
+    // Durée de conservation dans la corbeille ; None garde les éléments jusqu'à ce qu'elle soit vidée
+    trash_retention: Option<TimeDelta>,
+    // Nombre d'anciennes versions gardées par fichier ; 0 désactive les versions
+    max_versions: usize,
+}
+
+impl FileManager {
+    pub fn new(root: impl AsRef<Path>) -> io::Result<Self> {
+        let root = fs::canonicalize(root)?;
+        Ok(FileManager {
+            current_directory: root.clone(),
+            root,
+            trash_retention: None,
+            max_versions: 0,
+        })
+    }
+
+    pub fn set_trash_retention(&mut self, retention: Option<TimeDelta>) {
+        self.trash_retention = retention;
+    }
+
+    pub fn set_max_versions(&mut self, max_versions: usize) {
+        self.max_versions = max_versions;
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn current_directory(&self) -> &Path {
+        &self.current_directory
+    }
+
+    // Répertoire courant vu depuis la racine : « / », « /projets/rust »
+    pub fn pwd(&self) -> String {
+        self.display_path(&self.current_directory)
+    }
+
+    // Chemin vu depuis la racine, pour les messages
+    pub fn display_path(&self, path: &Path) -> String {
+        let relative = path.strip_prefix(&self.root).unwrap_or(Path::new(""));
+        format!("/{}", relative.to_string_lossy())
+    }
+
+    // Vrai pour un répertoire, ou un lien vers un répertoire
+    pub fn is_directory(&self, name: &str) -> Result<bool> {
+        Ok(fs::metadata(self.resolve(name)?)?.is_dir())
+    }
+
+    pub fn change_directory(&mut self, name: &str) -> Result<()> {
+        let path = self.resolve(name)?;
+        if !fs::metadata(&path)?.is_dir() {
+            return Err(io::Error::new(io::ErrorKind::NotADirectory, format!("'{}' n'est pas un répertoire", name)).into());
+        }
+        self.current_directory = path;
+        Ok(())
+    }
+
+    // Chemin réel d'un nom saisi, relatif au répertoire courant. Les chemins absolus, les .. qui
+    // remontent au-dessus de la racine et les liens symboliques qui pointent en dehors sont refusés.
+    pub fn resolve(&self, name: &str) -> Result<PathBuf> {
+        self.resolve_from(&self.current_directory, name)
+    }
+
+    fn resolve_from(&self, start: &Path, name: &str) -> Result<PathBuf> {
+        let outside = || FileManagerError::OutsideRoot(name.to_string());
+
+        let mut path = start.to_path_buf();
+        for component in Path::new(name).components() {
+            match component {
+                Component::Normal(part) => path.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if path == self.root {
+                        return Err(outside());
+                    }
+                    path.pop();
+                }
+                Component::RootDir | Component::Prefix(_) => return Err(outside()),
+            }
+        }
+
+        // Les liens symboliques sont suivis sur la partie du chemin qui existe déjà ; le reste (fichier
+        // à créer) est ajouté tel quel
+        let mut missing = Vec::new();
+        let mut existing = path.as_path();
+        while fs::symlink_metadata(existing).is_err() {
+            missing.push(existing.file_name().ok_or_else(outside)?.to_owned());
+            existing = existing.parent().ok_or_else(outside)?;
+        }
+        // Un lien cassé ne peut pas être vérifié : écrire à travers lui créerait sa cible, où qu'elle soit
+        let mut resolved = fs::canonicalize(existing).map_err(|_| outside())?;
+        if !resolved.starts_with(&self.root) {
+            return Err(outside());
+        }
+        resolved.extend(missing.iter().rev());
+        Ok(resolved)
+    }
+
+    // Comme resolve, sans suivre le dernier élément s'il s'agit d'un lien symbolique : supprimer un lien
+    // supprime le lien, pas sa cible
+    pub fn resolve_entry(&self, name: &str) -> Result<PathBuf> {
+        self.resolve_entry_from(&self.current_directory, name)
+    }
+
+    fn resolve_entry_from(&self, start: &Path, name: &str) -> Result<PathBuf> {
+        let path = Path::new(name);
+        match (path.parent(), path.file_name()) {
+            (Some(parent), Some(file_name)) => Ok(self.resolve_from(start, &parent.to_string_lossy())?.join(file_name)),
+            _ => self.resolve_from(start, name),
+        }
+    }
+
+    pub fn read_file(&self, filename: &str) -> Result<String> {
+        let path = self.resolve(filename)?;
+        Ok(fs::read_to_string(path)?)
+    }
+
+    // Écriture atomique : en cas d'erreur, le fichier existant reste intact (voir atomic::write). Le
+    // contenu remplacé est gardé comme version.
+    pub fn write_file(&self, filename: &str, content: &str) -> Result<()> {
+        let path = self.resolve(filename)?;
+        self.save_version(&path)?;
+        Ok(atomic::write(&path, content.as_bytes())?)
+    }
+
+    // Ajoute le contenu en fin de fichier, comme une nouvelle ligne, sans réécrire l'existant
+    pub fn modify_file(&self, filename: &str, new_content: &str) -> Result<()> {
+        let path = self.resolve(filename)?;
+        self.save_version(&path)?;
+        Ok(atomic::append(&path, new_content)?)
+    }
+
+    // Place le fichier (ou le lien, pas sa cible) dans la corbeille, d'où il peut être restauré. Les
+    // éléments plus anciens que la durée de conservation sont purgés au passage.
+    pub fn delete_file(&self, filename: &str) -> Result<TrashItem> {
+        let path = self.resolve_entry(filename)?;
+        let trash = self.trash_directory();
+        if path.starts_with(&trash) {
+            let message = format!("'{}' est dans la corbeille, videz-la pour le supprimer", filename);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+        }
+        if path.starts_with(self.versions_directory()) {
+            let message = format!("'{}' est une version gardée par le gestionnaire", filename);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+        }
+        if fs::symlink_metadata(&path)?.is_dir() {
+            return Err(io::Error::new(io::ErrorKind::IsADirectory, format!("'{}' est un répertoire", filename)).into());
+        }
+        let item = trash::put(&trash, &path, &self.display_path(&path))?;
+        // La suppression a réussi : un échec de la purge n'est pas une raison de la signaler en erreur
+        let _ = self.purge_trash();
+        Ok(item)
+    }
+
+    // Prépare l'insertion, la suppression ou le remplacement de lignes, sans rien écrire : le fichier
+    // n'est modifié que par apply_edit
+    pub fn edit_lines(&self, filename: &str, edit: &LineEdit) -> Result<EditPreview> {
+        let path = self.resolve(filename)?;
+        let original = fs::read_to_string(&path)?;
+        let content = editing::apply_line_edit(&original, edit)?;
+        Ok(EditPreview {
+            file: filename.to_string(),
+            path,
+            original,
+            content,
+            replacements: 0,
+        })
+    }
+
+    // Prépare le remplacement de `pattern` par `replacement`, ligne par ligne. Seules les options regex,
+    // ignore_case et whole_word de la recherche sont utilisées ; avec une expression régulière, `$1`
+    // dans le remplacement désigne le premier groupe capturé.
+    pub fn replace_text(
+        &self,
+        filename: &str,
+        pattern: &str,
+        replacement: &str,
+        options: &SearchOptions,
+    ) -> Result<EditPreview> {
+        let regex = search::build_regex(pattern, options)?;
+        let path = self.resolve(filename)?;
+        let original = fs::read_to_string(&path)?;
+        let (content, replacements) = editing::replace_all(&original, &regex, replacement, options.regex);
+        Ok(EditPreview {
+            file: filename.to_string(),
+            path,
+            original,
+            content,
+            replacements,
+        })
+    }
+
+    // Écrit une modification préparée, de façon atomique et en gardant l'ancien contenu comme version. Si
+    // le fichier a changé depuis l'aperçu, rien n'est écrit.
+    pub fn apply_edit(&self, preview: &EditPreview) -> Result<()> {
+        if fs::read_to_string(&preview.path)? != preview.original {
+            let message = format!("'{}' a été modifié depuis l'aperçu, recommencez", preview.file);
+            return Err(io::Error::other(message).into());
+        }
+        self.save_version(&preview.path)?;
+        Ok(atomic::write(&preview.path, preview.content.as_bytes())?)
+    }
+
+    // Répertoire de la corbeille, caché à la racine
+    pub fn trash_directory(&self) -> PathBuf {
+        self.root.join(trash::TRASH_DIRECTORY)
+    }
+
+    // Éléments de la corbeille, du plus ancien au plus récent
+    pub fn list_trash(&self) -> Result<Vec<TrashItem>> {
+        Ok(trash::list(&self.trash_directory())?)
+    }
+
+    // Remet un élément de la corbeille à son emplacement d'origine, en recréant ses répertoires parents.
+    // Si l'emplacement est occupé, `policy` décide comme pour un déplacement ; un élément laissé (None)
+    // reste dans la corbeille.
+    pub fn restore(
+        &self,
+        id: &str,
+        policy: OverwritePolicy,
+        ask: &mut dyn FnMut(&str) -> OverwritePolicy,
+    ) -> Result<Option<String>> {
+        let trash = self.trash_directory();
+        let Some(item) = trash::list(&trash)?.into_iter().find(|item| item.id == id) else {
+            let message = format!("'{}' n'est pas dans la corbeille", id);
+            return Err(io::Error::new(io::ErrorKind::NotFound, message).into());
+        };
+        let destination = self.resolve_entry_from(&self.root, item.original_path.trim_start_matches('/'))?;
+        if destination.starts_with(&trash) {
+            return Err(FileManagerError::OutsideRoot(item.original_path));
+        }
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let restored = self.transfer(&trash::files_path(&trash, id), destination, true, policy, ask)?;
+        if restored.is_some() {
+            trash::forget(&trash, id)?;
+        }
+        Ok(restored)
+    }
+
+    // Répertoire des anciennes versions, caché à la racine
+    pub fn versions_directory(&self) -> PathBuf {
+        self.root.join(versions::VERSIONS_DIRECTORY)
+    }
+
+    // Répertoire des versions d'un fichier, d'après son chemin réel depuis la racine
+    fn version_directory(&self, path: &Path) -> PathBuf {
+        self.versions_directory().join(path.strip_prefix(&self.root).unwrap_or(path))
+    }
+
+    // Garde le contenu actuel d'un fichier avant qu'il ne soit remplacé ou complété
+    fn save_version(&self, path: &Path) -> Result<()> {
+        let internal = path.starts_with(self.versions_directory()) || path.starts_with(self.trash_directory());
+        if self.max_versions > 0 && !internal && fs::metadata(path).is_ok_and(|metadata| metadata.is_file()) {
+            versions::save(&self.version_directory(path), path, self.max_versions)?;
+        }
+        Ok(())
+    }
+
+    // Anciennes versions d'un fichier, de la plus ancienne à la plus récente
+    pub fn list_versions(&self, filename: &str) -> Result<Vec<Version>> {
+        let path = self.resolve(filename)?;
+        Ok(versions::list(&self.version_directory(&path))?)
+    }
+
+    // Différences au format unifié entre deux versions d'un fichier ; None désigne le contenu actuel.
+    // Le résultat est vide si les deux contenus sont identiques.
+    pub fn diff_versions(&self, filename: &str, old: Option<&str>, new: Option<&str>) -> Result<String> {
+        let path = self.resolve(filename)?;
+        let directory = self.version_directory(&path);
+        let content = |id: Option<&str>| -> Result<(String, String)> {
+            match id {
+                Some(id) => {
+                    let label = format!("{}@{}", filename, id);
+                    Ok((fs::read_to_string(versions::path(&directory, id)?)?, label))
+                }
+                None => Ok((fs::read_to_string(&path)?, filename.to_string())),
+            }
+        };
+        let (old, old_label) = content(old)?;
+        let (new, new_label) = content(new)?;
+        Ok(versions::unified_diff(&old, &new, &old_label, &new_label))
+    }
+
+    // Remet le contenu d'une version ; le contenu remplacé devient lui-même une version, la restauration
+    // peut donc être annulée
+    pub fn restore_version(&self, filename: &str, id: &str) -> Result<()> {
+        let path = self.resolve(filename)?;
+        // Lu avant d'enregistrer la version actuelle, qui peut faire disparaître la plus ancienne
+        let content = fs::read(versions::path(&self.version_directory(&path), id)?)?;
+        self.save_version(&path)?;
+        Ok(atomic::write(&path, &content)?)
+    }
+
+    // Supprime définitivement tout le contenu de la corbeille ; retourne le nombre d'éléments supprimés
+    pub fn empty_trash(&self) -> Result<usize> {
+        Ok(trash::purge(&self.trash_directory(), None)?)
+    }
+
+    // Supprime définitivement les éléments plus anciens que la durée de conservation
+    pub fn purge_trash(&self) -> Result<usize> {
+        match self.trash_retention {
+            Some(retention) => Ok(trash::purge(&self.trash_directory(), Some(retention))?),
+            None => Ok(0),
+        }
+    }
+
+    // Crée le répertoire et ses parents manquants, comme mkdir -p
+    pub fn make_directory(&self, name: &str) -> Result<()> {
+        let path = self.resolve(name)?;
+        Ok(fs::create_dir_all(path)?)
+    }
+
+    // Sans `recursive`, seul un répertoire vide est supprimé (erreur DirectoryNotEmpty sinon). La racine
+    // et les répertoires qui contiennent le répertoire courant ne peuvent pas être supprimés.
+    pub fn remove_directory(&self, name: &str, recursive: bool) -> Result<()> {
+        let path = self.resolve_entry(name)?;
+        if self.current_directory.starts_with(&path) {
+            let message = format!("'{}' contient le répertoire courant", name);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+        }
+        if !fs::symlink_metadata(&path)?.is_dir() {
+            return Err(io::Error::new(io::ErrorKind::NotADirectory, format!("'{}' n'est pas un répertoire", name)).into());
+        }
+        if recursive {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_dir(path)?;
+        }
+        Ok(())
+    }
+
+    // Copie un fichier (le contenu d'un lien est copié). Si `to` est un répertoire, la copie y garde son
+    // nom, comme avec cp. Retourne le chemin de la copie, ou None si la destination existait et que
+    // `policy` (ou la réponse de `ask` pour OverwritePolicy::Ask) a choisi de la laisser.
+    pub fn copy_file(
+        &self,
+        from: &str,
+        to: &str,
+        policy: OverwritePolicy,
+        ask: &mut dyn FnMut(&str) -> OverwritePolicy,
+    ) -> Result<Option<String>> {
+        let source = self.resolve(from)?;
+        expect_kind(&source, from, false)?;
+        let destination = self.destination(&source, to)?;
+        self.transfer(&source, destination, false, policy, ask)
+    }
+
+    // Copie un répertoire et tout son contenu ; les liens symboliques sont recopiés comme liens
+    pub fn copy_directory(
+        &self,
+        from: &str,
+        to: &str,
+        policy: OverwritePolicy,
+        ask: &mut dyn FnMut(&str) -> OverwritePolicy,
+    ) -> Result<Option<String>> {
+        let source = self.resolve(from)?;
+        expect_kind(&source, from, true)?;
+        let destination = self.destination(&source, to)?;
+        self.transfer(&source, destination, false, policy, ask)
+    }
+
+    // Déplace un fichier (un lien est déplacé lui-même), dans un répertoire ou sous un autre nom
+    pub fn move_file(
+        &self,
+        from: &str,
+        to: &str,
+        policy: OverwritePolicy,
+        ask: &mut dyn FnMut(&str) -> OverwritePolicy,
+    ) -> Result<Option<String>> {
+        let source = self.resolve_entry(from)?;
+        expect_kind(&source, from, false)?;
+        let destination = self.destination(&source, to)?;
+        self.transfer(&source, destination, true, policy, ask)
+    }
+
+    pub fn move_directory(
+        &self,
+        from: &str,
+        to: &str,
+        policy: OverwritePolicy,
+        ask: &mut dyn FnMut(&str) -> OverwritePolicy,
+    ) -> Result<Option<String>> {
+        let source = self.resolve_entry(from)?;
+        expect_kind(&source, from, true)?;
+        let destination = self.destination(&source, to)?;
+        self.transfer(&source, destination, true, policy, ask)
+    }
+
+    // Renomme un fichier ou un répertoire sans le changer de répertoire : `new_name` est un simple nom
+    pub fn rename(
+        &self,
+        name: &str,
+        new_name: &str,
+        policy: OverwritePolicy,
+        ask: &mut dyn FnMut(&str) -> OverwritePolicy,
+    ) -> Result<Option<String>> {
+        let mut components = Path::new(new_name).components();
+        if !matches!((components.next(), components.next()), (Some(Component::Normal(_)), None)) {
+            let message = format!("'{}' n'est pas un simple nom de fichier", new_name);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+        }
+        let source = self.resolve_entry(name)?;
+        fs::symlink_metadata(&source)?;
+        let destination = source.with_file_name(new_name);
+        self.transfer(&source, destination, true, policy, ask)
+    }
+
+    // Destination d'une copie ou d'un déplacement : dans `to` si c'est un répertoire existant, `to` sinon
+    fn destination(&self, source: &Path, to: &str) -> Result<PathBuf> {
+        let target = self.resolve(to)?;
+        if target.is_dir() {
+            let name = source.file_name().ok_or_else(|| FileManagerError::OutsideRoot(to.to_string()))?;
+            Ok(target.join(name))
+        } else {
+            self.resolve_entry(to)
+        }
+    }
+
+    fn transfer(
+        &self,
+        source: &Path,
+        mut destination: PathBuf,
+        move_source: bool,
+        policy: OverwritePolicy,
+        ask: &mut dyn FnMut(&str) -> OverwritePolicy,
+    ) -> Result<Option<String>> {
+        let invalid = |message: String| Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+        if destination == source {
+            return invalid(format!("'{}' est déjà à cet endroit", self.display_path(source)));
+        }
+        if destination.starts_with(source) {
+            return invalid(format!("impossible de placer '{}' dans lui-même", self.display_path(source)));
+        }
+        if source.starts_with(&destination) {
+            return invalid(format!("'{}' contient la source", self.display_path(&destination)));
+        }
+        if move_source && self.current_directory.starts_with(source) {
+            return invalid(format!("'{}' contient le répertoire courant", self.display_path(source)));
+        }
+
+        if let Ok(existing) = fs::symlink_metadata(&destination) {
+            let policy = match policy {
+                OverwritePolicy::Ask => ask(&self.display_path(&destination)),
+                policy => policy,
+            };
+            match policy {
+                OverwritePolicy::Ask | OverwritePolicy::Skip => return Ok(None),
+                OverwritePolicy::KeepBoth => destination = transfer::keep_both_name(&destination),
+                OverwritePolicy::Overwrite => {
+                    // Un fichier ne remplace pas un répertoire, ni l'inverse
+                    if existing.is_dir() != fs::symlink_metadata(source)?.is_dir() {
+                        let message = format!("'{}' existe déjà et n'est pas du même type", self.display_path(&destination));
+                        return Err(io::Error::new(io::ErrorKind::AlreadyExists, message).into());
+                    }
+                    if self.current_directory.starts_with(&destination) {
+                        return invalid(format!("'{}' contient le répertoire courant", self.display_path(&destination)));
+                    }
+                    transfer::remove_path(&destination)?;
+                }
+            }
+        }
+
+        if move_source {
+            transfer::move_path(source, &destination)?;
+        } else {
+            transfer::copy_path(source, &destination)?;
+        }
+        Ok(Some(self.display_path(&destination)))
+    }
+
+    // Répertoires du gestionnaire, cachés à la racine : corbeille et versions
+    fn internal_directories(&self) -> [PathBuf; 2] {
+        [self.trash_directory(), self.versions_directory()]
+    }
+
+    // Contenu du répertoire courant (fichiers, répertoires et liens) sans la corbeille ni les versions,
+    // trié par nom
+    pub fn list_files(&self) -> Result<Vec<Entry>> {
+        list_directory(&self.current_directory, &self.internal_directories())
+    }
+
+    // Contenu détaillé du répertoire courant (taille, dates, permissions) sans la corbeille ni les
+    // versions, filtré et trié selon `options`
+    pub fn list_details(&self, options: &ListOptions) -> Result<Vec<EntryDetails>> {
+        listing::list_details(&self.current_directory, &self.internal_directories(), options)
+    }
+
+    // Cherche `pattern` dans le contenu des fichiers du répertoire courant, comme grep ; les chemins des
+    // résultats sont relatifs au répertoire courant. Un motif invalide donne InvalidInput.
+    pub fn search(&self, pattern: &str, options: &SearchOptions) -> Result<Vec<SearchLine>> {
+        let regex = search::build_regex(pattern, options)?;
+        Ok(search::search(&self.current_directory, &self.internal_directories(), &regex, options)?)
+    }
+
+    // Arborescence d'un répertoire (« . » pour le répertoire courant) dessinée comme la commande tree,
+    // sur `max_depth` niveaux au plus. Les liens symboliques sont affichés sans être suivis.
+    pub fn tree(&self, name: &str, max_depth: Option<usize>) -> Result<Vec<String>> {
+        let path = self.resolve(name)?;
+        if !fs::metadata(&path)?.is_dir() {
+            return Err(io::Error::new(io::ErrorKind::NotADirectory, format!("'{}' n'est pas un répertoire", name)).into());
+        }
+        let mut lines = vec![format!("{}/", name.trim_end_matches('/'))];
+        draw_tree(&path, "", 1, max_depth, &self.internal_directories(), &mut lines)?;
+        Ok(lines)
+    }
+}
+
+// Vérifie qu'une source est un répertoire (ou un fichier), en suivant les liens
+fn expect_kind(path: &Path, name: &str, directory: bool) -> Result<()> {
+    match (fs::metadata(path)?.is_dir(), directory) {
+        (true, false) => Err(io::Error::new(io::ErrorKind::IsADirectory, format!("'{}' est un répertoire", name)).into()),
+        (false, true) => {
+            Err(io::Error::new(io::ErrorKind::NotADirectory, format!("'{}' n'est pas un répertoire", name)).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+// Contenu d'un répertoire trié par nom, sans les éléments `hidden` (répertoires du gestionnaire)
+fn list_directory(path: &Path, hidden: &[PathBuf]) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        if !hidden.contains(&entry.path()) {
+            entries.push(Entry::from_dir_entry(&entry)?);
+        }
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+fn draw_tree(
+    path: &Path,
+    prefix: &str,
+    depth: usize,
+    max_depth: Option<usize>,
+    hidden: &[PathBuf],
+    lines: &mut Vec<String>,
+) -> Result<()> {
+    if max_depth.is_some_and(|max_depth| depth > max_depth) {
+        return Ok(());
+    }
+    let entries = list_directory(path, hidden)?;
+    for (index, entry) in entries.iter().enumerate() {
+        let last = index + 1 == entries.len();
+        lines.push(format!("{}{}{}", prefix, if last { "└── " } else { "├── " }, entry));
+        if entry.kind == EntryKind::Directory {
+            let prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            draw_tree(&path.join(&entry.name), &prefix, depth + 1, max_depth, hidden, lines)?;
+        }
+    }
+    Ok(())
+}
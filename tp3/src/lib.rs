@@ -0,0 +1,9 @@
+// Gestionnaire de fichiers : les opérations du menu, limitées à un répertoire racine
+pub mod atomic;
+pub mod editing;
+pub mod file_manager;
+pub mod listing;
+pub mod search;
+pub mod transfer;
+pub mod trash;
+pub mod versions;
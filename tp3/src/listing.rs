@@ -0,0 +1,183 @@
+use chrono::{DateTime, Local};
+use glob::Pattern;
+use std::cmp::Ordering;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use crate::file_manager::{Entry, EntryKind, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    #[default]
+    Name,
+    Size,
+    Modified,
+}
+
+// Options de la liste détaillée : ListOptions { sort: SortKey::Size, ..ListOptions::default() }
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    pub sort: SortKey,
+    pub reverse: bool,
+    // Motif sur le nom, comme « *.rs » ou « note?.txt »
+    pub pattern: Option<Pattern>,
+    // Extension sans le point, sans tenir compte de la casse
+    pub extension: Option<String>,
+    pub min_size: Option<u64>,
+    // Les noms commençant par un point sont cachés sauf demande contraire, comme avec ls
+    pub show_hidden: bool,
+}
+
+impl ListOptions {
+    fn accepts(&self, details: &EntryDetails) -> bool {
+        let name = &details.entry.name;
+        (self.show_hidden || !name.starts_with('.'))
+            && self.pattern.as_ref().is_none_or(|pattern| pattern.matches(name))
+            && self.extension.as_ref().is_none_or(|extension| {
+                Path::new(name)
+                    .extension()
+                    .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case(extension.trim_start_matches('.')))
+            })
+            && self.min_size.is_none_or(|min_size| details.size >= min_size)
+    }
+
+    fn compare(&self, a: &EntryDetails, b: &EntryDetails) -> Ordering {
+        let ordering = match self.sort {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Size => a.size.cmp(&b.size),
+            SortKey::Modified => a.modified.cmp(&b.modified),
+        }
+        .then_with(|| a.entry.name.cmp(&b.entry.name));
+        if self.reverse { ordering.reverse() } else { ordering }
+    }
+}
+
+// Ligne de la liste détaillée. Les liens symboliques sont décrits eux-mêmes, sans suivre leur cible.
+#[derive(Debug, Clone)]
+pub struct EntryDetails {
+    pub entry: Entry,
+    pub size: u64,
+    pub modified: Option<DateTime<Local>>,
+    // Date de création, si le système de fichiers la connaît
+    pub created: Option<DateTime<Local>>,
+    pub mode: u32,
+}
+
+impl EntryDetails {
+    fn read(entry: &fs::DirEntry) -> Result<EntryDetails> {
+        let metadata = entry.metadata()?;
+        Ok(EntryDetails {
+            entry: Entry::from_dir_entry(entry)?,
+            size: metadata.len(),
+            modified: metadata.modified().ok().map(DateTime::from),
+            created: metadata.created().ok().map(DateTime::from),
+            mode: metadata.permissions().mode(),
+        })
+    }
+
+    // Type et permissions comme ls -l : « drwxr-xr-x »
+    pub fn permissions(&self) -> String {
+        let type_char = match self.entry.kind {
+            EntryKind::File => '-',
+            EntryKind::Directory => 'd',
+            EntryKind::Symlink => 'l',
+        };
+        let mut text = String::from(type_char);
+        for shift in [6, 3, 0] {
+            let bits = (self.mode >> shift) & 0o7;
+            text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+            text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+            text.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+        }
+        text
+    }
+
+    pub fn kind_name(&self) -> &'static str {
+        match self.entry.kind {
+            EntryKind::File => "fichier",
+            EntryKind::Directory => "répertoire",
+            EntryKind::Symlink => "lien",
+        }
+    }
+
+    // Colonnes : permissions, type, taille, modification, création, nom
+    pub fn line(&self) -> String {
+        format!(
+            "{}  {:<10}  {:>8}  {}  {}  {}",
+            self.permissions(),
+            self.kind_name(),
+            format_size(self.size),
+            format_date(self.modified),
+            format_date(self.created),
+            self.entry
+        )
+    }
+}
+
+// En-tête aligné sur EntryDetails::line
+pub fn header() -> String {
+    format!(
+        "{:<10}  {:<10}  {:>8}  {:<16}  {:<16}  {}",
+        "Droits", "Type", "Taille", "Modifié", "Créé", "Nom"
+    )
+}
+
+fn format_date(date: Option<DateTime<Local>>) -> String {
+    match date {
+        Some(date) => date.format("%Y-%m-%d %H:%M").to_string(),
+        None => format!("{:<16}", "-"),
+    }
+}
+
+const UNITS: [&str; 5] = ["o", "Ko", "Mo", "Go", "To"];
+
+// Taille lisible en multiples de 1024 : « 512 o », « 1,5 Ko », « 12 Mo »
+pub fn format_size(size: u64) -> String {
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", size, UNITS[0])
+    } else if value < 10.0 {
+        format!("{:.1} {}", value, UNITS[unit]).replace('.', ",")
+    } else {
+        format!("{:.0} {}", value, UNITS[unit])
+    }
+}
+
+// Lit une taille saisie : « 2048 », « 10K », « 1,5 Mo », « 3G »
+pub fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim().replace(',', ".");
+    let split = text.find(|c: char| c.is_alphabetic()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.trim().parse().ok().filter(|n: &f64| *n >= 0.0)?;
+    let power = match unit.trim().to_lowercase().as_str() {
+        "" | "o" | "b" => 0,
+        "k" | "ko" | "kb" => 1,
+        "m" | "mo" | "mb" => 2,
+        "g" | "go" | "gb" => 3,
+        "t" | "to" | "tb" => 4,
+        _ => return None,
+    };
+    Some((number * 1024f64.powi(power)).round() as u64)
+}
+
+// Les éléments `hidden` (répertoires du gestionnaire) n'apparaissent jamais, même avec show_hidden
+pub(crate) fn list_details(directory: &Path, hidden: &[PathBuf], options: &ListOptions) -> Result<Vec<EntryDetails>> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        if hidden.contains(&entry.path()) {
+            continue;
+        }
+        let details = EntryDetails::read(&entry)?;
+        if options.accepts(&details) {
+            entries.push(details);
+        }
+    }
+    entries.sort_by(|a, b| options.compare(a, b));
+    Ok(entries)
+}
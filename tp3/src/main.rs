@@ -1,170 +1,546 @@
-use std::fs;
-use std::io::{self, Write};
-use chrono::{DateTime, Local};
+use std::io::{self, IsTerminal, Write};
+use chrono::{DateTime, Local, TimeDelta};
+use saisie::{Console, ErreurSaisie, Lecteur};
+use tp3::editing::LineEdit;
+use tp3::file_manager::{FileManager, FileManagerError};
+use tp3::listing::{self, ListOptions, SortKey};
+use tp3::search::{self, SearchOptions};
+use tp3::transfer::OverwritePolicy;
+use tp3::versions::Version;
 
-struct FileManager {
-    current_directory: String,
-}
-
-impl FileManager {
-    fn new() -> Self {
-        FileManager {
-            current_directory: String::from("./"),
+fn main() {
+    // Toutes les opérations restent dans le répertoire de lancement
+    let mut file_manager = match FileManager::new(".") {
+        Ok(file_manager) => file_manager,
+        Err(e) => {
+            println!("Répertoire courant inaccessible: {}", e);
+            return;
         }
+    };
+    // Les éléments de la corbeille sont purgés après TP3_CORBEILLE_JOURS jours (0 pour les garder)
+    let retention = std::env::var(VARIABLE_CORBEILLE_JOURS)
+        .ok()
+        .and_then(|days| days.trim().parse::<i64>().ok())
+        .unwrap_or(CORBEILLE_JOURS_PAR_DEFAUT);
+    file_manager.set_trash_retention((retention > 0).then(|| TimeDelta::days(retention)));
+    match file_manager.purge_trash() {
+        Ok(0) => {}
+        Ok(purged) => println!("{} élément(s) de plus de {} jours supprimé(s) de la corbeille", purged, retention),
+        Err(e) => println!("Erreur lors de la purge de la corbeille: {}", e),
     }
 
-    fn read_file(&self, filename: &str) -> Result<String, io::Error> {
-        let path = format!("{}{}", self.current_directory, filename);
-        fs::read_to_string(path)
-    }
+    // Nombre d'anciennes versions gardées pour chaque fichier (0 pour n'en garder aucune)
+    let max_versions = std::env::var(VARIABLE_VERSIONS)
+        .ok()
+        .and_then(|count| count.trim().parse::<usize>().ok())
+        .unwrap_or(VERSIONS_PAR_DEFAUT);
+    file_manager.set_max_versions(max_versions);
 
-    fn write_file(&self, filename: &str, content: &str) -> Result<(), io::Error> {
-        let path = format!("{}{}", self.current_directory, filename);
-        fs::write(path, content)
-    }
+    let now: DateTime<Local> = Local::now();
+    // Saisie avec édition de la ligne et historique dans un terminal ; Ctrl-D termine le programme
+    let mut console = Console::standard();
 
-    fn modify_file(&self, filename: &str, new_content: &str) -> Result<(), io::Error> {
-        let path = format!("{}{}", self.current_directory, filename);
-        let mut existing_content = match fs::read_to_string(&path) {
-            Ok(content) => content,
-            Err(_) => String::new(),
-        };
-        existing_content.push_str("\n");
-        existing_content.push_str(new_content);
-        fs::write(path, existing_content)
-    }
+    println!("Gestionnaire de Fichiers - Démarré le {}", now.format("%Y-%m-%d %H:%M:%S"));
+    println!("Répertoire courant: {}", file_manager.current_directory().display());
 
-    fn delete_file(&self, filename: &str) -> Result<(), io::Error> {
-        let path = format!("{}{}", self.current_directory, filename);
-        fs::remove_file(path)
-    }
+    loop {
+        println!("\n=== MENU PRINCIPAL === ({})", file_manager.pwd());
+        for (index, option) in OPTIONS.iter().enumerate() {
+            println!("{}. {}", index + 1, option);
+        }
 
-    fn list_files(&self) -> Result<Vec<String>, io::Error> {
-        let mut files = Vec::new();
-        for entry in fs::read_dir(&self.current_directory)? {
-            let entry = entry?;
-            if entry.file_type()?.is_file() {
-                if let Some(filename) = entry.file_name().to_str() {
-                    files.push(filename.to_string());
-                }
+        let resultat = console
+            .entier(&format!("Votre choix (1-{}): ", OPTIONS.len()), 1..=OPTIONS.len())
+            .and_then(|choice| traiter_choix(choice, &mut console, &mut file_manager));
+
+        match resultat {
+            Ok(Action::Continuer) => {}
+            Ok(Action::Quitter) | Err(ErreurSaisie::FinDeSaisie) => {
+                println!("Au revoir !");
+                break;
             }
+            Err(e) => println!("Saisie abandonnée: {}", console.messages().erreur(&e)),
         }
-        Ok(files)
     }
 }
 
-fn get_user_input(prompt: &str) -> String {
-    print!("{}", prompt);
-    io::stdout().flush().unwrap();
-    
-    let mut input = String::new();
-    while input.trim().is_empty() {
-        input.clear();
-        io::stdin().read_line(&mut input).expect("Erreur de lecture");
-    }
-    input.trim().to_string()
+const VARIABLE_CORBEILLE_JOURS: &str = "TP3_CORBEILLE_JOURS";
+const CORBEILLE_JOURS_PAR_DEFAUT: i64 = 30;
+const VARIABLE_VERSIONS: &str = "TP3_VERSIONS";
+const VERSIONS_PAR_DEFAUT: usize = 10;
+
+const OPTIONS: [&str; 22] = [
+    "Lire un fichier",
+    "Écrire un fichier",
+    "Modifier un fichier",
+    "Supprimer un fichier (corbeille)",
+    "Lister les fichiers",
+    "Changer de répertoire (cd)",
+    "Afficher le répertoire courant (pwd)",
+    "Créer un répertoire (mkdir)",
+    "Supprimer un répertoire (rmdir)",
+    "Afficher l'arborescence (tree)",
+    "Liste détaillée (ls -l)",
+    "Copier un fichier ou un répertoire (cp)",
+    "Déplacer un fichier ou un répertoire (mv)",
+    "Renommer un fichier ou un répertoire",
+    "Afficher la corbeille",
+    "Restaurer depuis la corbeille",
+    "Vider la corbeille",
+    "Versions d'un fichier",
+    "Comparer deux versions (diff)",
+    "Restaurer une version",
+    "Rechercher dans les fichiers (grep)",
+    "Quitter",
+];
+
+enum Action {
+    Continuer,
+    Quitter,
 }
 
-fn main() {
-    let file_manager = FileManager::new();
-    let now: DateTime<Local> = Local::now();
-    
-    println!("Gestionnaire de Fichiers - Démarré le {}", now.format("%Y-%m-%d %H:%M:%S"));
-    println!("Répertoire courant: {}", file_manager.current_directory);
-    
-    loop {
-        println!("\n=== MENU PRINCIPAL ===");
-        println!("1. Lire un fichier");
-        println!("2. Écrire un fichier");
-        println!("3. Modifier un fichier");
-        println!("4. Supprimer un fichier");
-        println!("5. Lister les fichiers");
-        println!("6. Quitter");
-
-        let choice = get_user_input("Votre choix (1-6): ");
-
-        match choice.as_str() {
-            "1" => {
-                let filename = get_user_input("Nom du fichier à lire: ");
-                match file_manager.read_file(&filename) {
-                    Ok(content) => {
-                        println!("\nContenu du fichier '{}':", filename);
-                        println!("{}", content);
-                    }
-                    Err(e) => println!("Erreur lors de la lecture: {}", e),
+// Exécute une option du menu ; une saisie interrompue (fin de l'entrée, trop d'essais) remonte en erreur
+fn traiter_choix(
+    choice: usize,
+    console: &mut Console<impl Lecteur, impl Write>,
+    file_manager: &mut FileManager,
+) -> saisie::Resultat<Action> {
+    match choice {
+        1 => {
+            let filename = console.texte("Nom du fichier à lire: ")?;
+            match file_manager.read_file(&filename) {
+                Ok(content) => {
+                    println!("\nContenu du fichier '{}':", filename);
+                    println!("{}", content);
+                }
+                Err(e) => println!("Erreur lors de la lecture: {}", e),
+            }
+        }
+
+        2 => {
+            let filename = console.texte("Nom du fichier à créer: ")?;
+            println!("Entrez le contenu (tapez 'FIN' sur une ligne pour terminer):");
+            let content = console.lignes_jusqu_a("", "FIN")?;
+
+            match file_manager.write_file(&filename, &content) {
+                Ok(()) => println!("Fichier '{}' créé avec succès!", filename),
+                Err(e) => println!("Erreur lors de l'écriture: {}", e),
+            }
+        }
+
+        3 => {
+            let filename = console.texte("Nom du fichier à modifier: ")?;
+            let operations = [
+                "ajouter une ligne à la fin",
+                "insérer une ligne",
+                "supprimer des lignes",
+                "remplacer une ligne",
+                "rechercher et remplacer",
+            ];
+            let operation = console.choisir("Modification: ", &operations)?;
+            if operation == 0 {
+                let new_content = console.texte("Contenu à ajouter: ")?;
+                match file_manager.modify_file(&filename, &new_content) {
+                    Ok(()) => println!("Fichier '{}' modifié avec succès!", filename),
+                    Err(e) => println!("Erreur lors de la modification: {}", e),
                 }
+                return Ok(Action::Continuer);
             }
-            
-            "2" => {
-                let filename = get_user_input("Nom du fichier à créer: ");
-                println!("Entrez le contenu (tapez 'FIN' sur une ligne pour terminer):");
 
-                let mut content = String::new();
-                loop {
-                    let line = get_user_input("");
-                    if line == "FIN" {
-                        break;
+            let preview = if operation == 4 {
+                let pattern = console.texte("Texte ou motif à remplacer: ")?;
+                let replacement = console.demander("Remplacer par ($1 pour un groupe capturé): ")?;
+                let options = SearchOptions {
+                    regex: console.oui_non("Expression régulière ? (oui/non): ")?,
+                    ignore_case: console.oui_non("Ignorer la casse ? (oui/non): ")?,
+                    whole_word: console.oui_non("Mots entiers seulement ? (oui/non): ")?,
+                    ..SearchOptions::default()
+                };
+                file_manager.replace_text(&filename, &pattern, &replacement, &options)
+            } else {
+                let count = match file_manager.read_file(&filename) {
+                    Ok(content) => content.lines().count(),
+                    Err(e) => {
+                        println!("Erreur lors de la lecture: {}", e);
+                        return Ok(Action::Continuer);
                     }
-                    content.push_str(&line);
-                    content.push('\n');
+                };
+                if count == 0 && operation != 1 {
+                    println!("'{}' est vide", filename);
+                    return Ok(Action::Continuer);
+                }
+                let edit = ask_line_edit(console, operation, count)?;
+                file_manager.edit_lines(&filename, &edit)
+            };
+            let preview = match preview {
+                Ok(preview) if preview.is_unchanged() => {
+                    println!("Aucun changement");
+                    return Ok(Action::Continuer);
                 }
-                
-                match file_manager.write_file(&filename, &content) {
-                    Ok(()) => println!("Fichier '{}' créé avec succès!", filename),
-                    Err(e) => println!("Erreur lors de l'écriture: {}", e),
+                Ok(preview) => preview,
+                Err(e) => {
+                    println!("Erreur lors de la modification: {}", e);
+                    return Ok(Action::Continuer);
                 }
+            };
+
+            // Aperçu des lignes avant et après, puis confirmation
+            print!("{}", preview.diff());
+            if preview.replacements > 0 {
+                println!("{} remplacement(s)", preview.replacements);
             }
-            
-            "3" => {
-                let filename = get_user_input("Nom du fichier à modifier: ");
-                let new_content = get_user_input("Contenu à ajouter: ");
-                
-                match file_manager.modify_file(&filename, &new_content) {
+            if console.oui_non("Appliquer ces changements ? (oui/non): ")? {
+                match file_manager.apply_edit(&preview) {
                     Ok(()) => println!("Fichier '{}' modifié avec succès!", filename),
                     Err(e) => println!("Erreur lors de la modification: {}", e),
                 }
+            } else {
+                println!("Modification annulée");
             }
-            
-            "4" => {
-                let filename = get_user_input("Nom du fichier à supprimer: ");
-                
-                let mut confirmation = String::new();
-                while confirmation != "oui" && confirmation != "non" {
-                    confirmation = get_user_input(&format!("Êtes-vous sûr de vouloir supprimer '{}'? (oui/non): ", filename));
+        }
+
+        4 => {
+            let filename = console.texte("Nom du fichier à supprimer: ")?;
+
+            if console.oui_non(&format!("Mettre '{}' à la corbeille ? (oui/non): ", filename))? {
+                match file_manager.delete_file(&filename) {
+                    Ok(_) => println!("Fichier '{}' placé dans la corbeille", filename),
+                    Err(e) => println!("Erreur lors de la suppression: {}", e),
                 }
-                
-                if confirmation == "oui" {
-                    match file_manager.delete_file(&filename) {
-                        Ok(()) => println!("Fichier '{}' supprimé avec succès!", filename),
-                        Err(e) => println!("Erreur lors de la suppression: {}", e),
-                    }
+            } else {
+                println!("Suppression annulée");
+            }
+        }
+
+        5 => match file_manager.list_files() {
+            Ok(files) => {
+                if files.is_empty() {
+                    println!("Aucun fichier trouvé dans le répertoire");
                 } else {
+                    println!("\nContenu du répertoire {}:", file_manager.pwd());
+                    for (index, file) in files.iter().enumerate() {
+                        println!("  {}. {}", index + 1, file);
+                    }
+                }
+            }
+            Err(e) => println!("Erreur lors du listage: {}", e),
+        },
+
+        6 => {
+            let name = console.texte("Répertoire (.. pour remonter): ")?;
+            match file_manager.change_directory(&name) {
+                Ok(()) => println!("Répertoire courant: {}", file_manager.pwd()),
+                Err(e) => println!("Erreur lors du changement de répertoire: {}", e),
+            }
+        }
+
+        7 => println!("Répertoire courant: {}", file_manager.pwd()),
+
+        8 => {
+            let name = console.texte("Nom du répertoire à créer: ")?;
+            match file_manager.make_directory(&name) {
+                Ok(()) => println!("Répertoire '{}' créé avec succès!", name),
+                Err(e) => println!("Erreur lors de la création: {}", e),
+            }
+        }
+
+        9 => {
+            let name = console.texte("Nom du répertoire à supprimer: ")?;
+            let mut resultat = file_manager.remove_directory(&name, false);
+            // Un répertoire non vide n'est supprimé avec son contenu qu'après confirmation
+            if let Err(FileManagerError::Io(e)) = &resultat
+                && e.kind() == io::ErrorKind::DirectoryNotEmpty
+            {
+                let question = format!("'{}' n'est pas vide, supprimer aussi tout son contenu ? (oui/non): ", name);
+                if !console.oui_non(&question)? {
                     println!("Suppression annulée");
+                    return Ok(Action::Continuer);
+                }
+                resultat = file_manager.remove_directory(&name, true);
+            }
+            match resultat {
+                Ok(()) => println!("Répertoire '{}' supprimé avec succès!", name),
+                Err(e) => println!("Erreur lors de la suppression: {}", e),
+            }
+        }
+
+        10 => {
+            let name = console.demander("Répertoire (vide pour le répertoire courant): ")?;
+            let name = if name.trim().is_empty() { "." } else { name.trim() };
+            let depth = console.entier("Profondeur maximale (0 pour illimitée): ", 0..=usize::MAX)?;
+            match file_manager.tree(name, (depth > 0).then_some(depth)) {
+                Ok(lines) => {
+                    for line in lines {
+                        println!("{}", line);
+                    }
                 }
+                Err(e) => println!("Erreur lors de l'affichage de l'arborescence: {}", e),
             }
-            
-            "5" => {
-                match file_manager.list_files() {
-                    Ok(files) => {
-                        if files.is_empty() {
-                            println!("Aucun fichier trouvé dans le répertoire");
-                        } else {
-                            println!("\nFichiers dans le répertoire:");
-                            for (index, file) in files.iter().enumerate() {
-                                println!("  {}. {}", index + 1, file);
-                            }
-                        }
+        }
+
+        11 => {
+            let options = ask_list_options(console)?;
+            match file_manager.list_details(&options) {
+                Ok(entries) if entries.is_empty() => println!("Aucun élément ne correspond"),
+                Ok(entries) => {
+                    println!("\n{}", listing::header());
+                    for entry in entries {
+                        println!("{}", entry.line());
                     }
-                    Err(e) => println!("Erreur lors du listage: {}", e),
                 }
+                Err(e) => println!("Erreur lors du listage: {}", e),
             }
+        }
 
-            "6" => {
-                println!("Au revoir !");
-                break;
+        12 | 13 => {
+            let copy = choice == 12;
+            let from = console.texte(if copy { "Élément à copier: " } else { "Élément à déplacer: " })?;
+            let to = console.texte("Destination (nouveau nom ou répertoire): ")?;
+            let policy = ask_overwrite_policy(console)?;
+            // Le contenu d'un répertoire est copié ou déplacé avec lui
+            let directory = file_manager.is_directory(&from).unwrap_or(false);
+            let mut ask = |path: &str| ask_conflict(console, path);
+            let resultat = match (copy, directory) {
+                (true, false) => file_manager.copy_file(&from, &to, policy, &mut ask),
+                (true, true) => file_manager.copy_directory(&from, &to, policy, &mut ask),
+                (false, false) => file_manager.move_file(&from, &to, policy, &mut ask),
+                (false, true) => file_manager.move_directory(&from, &to, policy, &mut ask),
+            };
+            match resultat {
+                Ok(Some(path)) if copy => println!("'{}' copié vers {}", from, path),
+                Ok(Some(path)) => println!("'{}' déplacé vers {}", from, path),
+                Ok(None) => println!("La destination existe déjà, '{}' est laissé tel quel", from),
+                Err(e) if copy => println!("Erreur lors de la copie: {}", e),
+                Err(e) => println!("Erreur lors du déplacement: {}", e),
+            }
+        }
+
+        14 => {
+            let name = console.texte("Élément à renommer: ")?;
+            let new_name = console.texte("Nouveau nom: ")?;
+            let policy = ask_overwrite_policy(console)?;
+            match file_manager.rename(&name, &new_name, policy, &mut |path| ask_conflict(console, path)) {
+                Ok(Some(path)) => println!("'{}' renommé en {}", name, path),
+                Ok(None) => println!("La destination existe déjà, '{}' est laissé tel quel", name),
+                Err(e) => println!("Erreur lors du renommage: {}", e),
+            }
+        }
+
+        15 => match file_manager.list_trash() {
+            Ok(items) if items.is_empty() => println!("La corbeille est vide"),
+            Ok(items) => {
+                println!("\nCorbeille (date de suppression, emplacement d'origine):");
+                for (index, item) in items.iter().enumerate() {
+                    println!("  {}. {}", index + 1, item.line());
+                }
+            }
+            Err(e) => println!("Erreur lors de la lecture de la corbeille: {}", e),
+        },
+
+        16 => {
+            let items = match file_manager.list_trash() {
+                Ok(items) if items.is_empty() => {
+                    println!("La corbeille est vide");
+                    return Ok(Action::Continuer);
+                }
+                Ok(items) => items,
+                Err(e) => {
+                    println!("Erreur lors de la lecture de la corbeille: {}", e);
+                    return Ok(Action::Continuer);
+                }
+            };
+            let lines: Vec<String> = items.iter().map(|item| item.line()).collect();
+            let item = &items[console.choisir("Élément à restaurer: ", &lines)?];
+            let policy = ask_overwrite_policy(console)?;
+            match file_manager.restore(&item.id, policy, &mut |path| ask_conflict(console, path)) {
+                Ok(Some(path)) => println!("'{}' restauré", path),
+                Ok(None) => println!("L'emplacement d'origine est occupé, '{}' reste dans la corbeille", item.original_path),
+                Err(e) => println!("Erreur lors de la restauration: {}", e),
+            }
+        }
+
+        17 => {
+            if console.oui_non("Supprimer définitivement tout le contenu de la corbeille ? (oui/non): ")? {
+                match file_manager.empty_trash() {
+                    Ok(count) => println!("Corbeille vidée ({} élément(s) supprimé(s))", count),
+                    Err(e) => println!("Erreur lors du vidage de la corbeille: {}", e),
+                }
+            } else {
+                println!("Vidage annulé");
+            }
+        }
+
+        18..=20 => {
+            let filename = console.texte("Nom du fichier: ")?;
+            let versions = match file_manager.list_versions(&filename) {
+                Ok(versions) if versions.is_empty() => {
+                    println!("Aucune ancienne version de '{}'", filename);
+                    return Ok(Action::Continuer);
+                }
+                Ok(versions) => versions,
+                Err(e) => {
+                    println!("Erreur lors de la lecture des versions: {}", e);
+                    return Ok(Action::Continuer);
+                }
+            };
+            match choice {
+                18 => {
+                    println!("\nVersions de '{}' (de la plus ancienne à la plus récente):", filename);
+                    for (index, version) in versions.iter().enumerate() {
+                        println!("  {}. {}  {}", index + 1, version.label(), listing::format_size(version.size));
+                    }
+                }
+                19 => {
+                    let old = ask_version(console, &versions, "Version de départ: ")?;
+                    let new = ask_version(console, &versions, "Version d'arrivée: ")?;
+                    match file_manager.diff_versions(&filename, old, new) {
+                        Ok(diff) if diff.is_empty() => println!("Aucune différence"),
+                        Ok(diff) => print!("{}", diff),
+                        Err(e) => println!("Erreur lors de la comparaison: {}", e),
+                    }
+                }
+                _ => {
+                    let labels: Vec<String> = versions.iter().map(|version| version.label()).collect();
+                    let version = &versions[console.choisir("Version à restaurer: ", &labels)?];
+                    match file_manager.restore_version(&filename, &version.id) {
+                        Ok(()) => println!("'{}' remis à la version du {}", filename, version.label()),
+                        Err(e) => println!("Erreur lors de la restauration: {}", e),
+                    }
+                }
             }
-            
-            _ => println!("Choix invalide, veuillez réessayer"),
         }
+
+        21 => {
+            let pattern = console.texte("Texte ou motif à rechercher: ")?;
+            let options = ask_search_options(console)?;
+            match file_manager.search(&pattern, &options) {
+                Ok(lines) if lines.is_empty() => println!("Aucune correspondance"),
+                Ok(lines) => {
+                    // Les couleurs ne sont utiles que dans un terminal
+                    for line in search::format_results(&lines, options.context, io::stdout().is_terminal()) {
+                        println!("{}", line);
+                    }
+                    let matches: Vec<_> = lines.iter().filter(|line| line.is_match()).collect();
+                    let mut files: Vec<&str> = matches.iter().map(|line| line.path.as_str()).collect();
+                    files.dedup();
+                    println!("{} ligne(s) trouvée(s) dans {} fichier(s)", matches.len(), files.len());
+                }
+                Err(e) => println!("Erreur lors de la recherche: {}", e),
+            }
+        }
+
+        _ => return Ok(Action::Quitter),
+    }
+    Ok(Action::Continuer)
+}
+
+// Questions de la liste détaillée ; une réponse vide garde le réglage par défaut
+fn ask_list_options(console: &mut Console<impl Lecteur, impl Write>) -> saisie::Resultat<ListOptions> {
+    let sort = match console.choisir("Trier par: ", &["nom", "taille", "date de modification"])? {
+        0 => SortKey::Name,
+        1 => SortKey::Size,
+        _ => SortKey::Modified,
+    };
+    let reverse = console.oui_non("Ordre décroissant ? (oui/non): ")?;
+    let pattern = console.valeur("Motif du nom (ex: *.txt, vide pour tous): ", "Motif invalide", |text| {
+        if text.is_empty() { Some(None) } else { glob::Pattern::new(text).ok().map(Some) }
+    })?;
+    let extension = console.demander("Extension (ex: rs, vide pour toutes): ")?;
+    let min_size = console.valeur("Taille minimale (ex: 10K, vide pour aucune): ", "Taille invalide", |text| {
+        if text.is_empty() { Some(None) } else { listing::parse_size(text).map(Some) }
+    })?;
+    let show_hidden = console.oui_non("Afficher les fichiers cachés ? (oui/non): ")?;
+    Ok(ListOptions {
+        sort,
+        reverse,
+        pattern,
+        extension: Some(extension.trim().to_string()).filter(|extension| !extension.is_empty()),
+        min_size,
+        show_hidden,
+    })
+}
+
+// Questions d'une modification de lignes (1 : insertion, 2 : suppression, 3 : remplacement) pour un
+// fichier de `count` lignes, au moins une pour une suppression ou un remplacement
+fn ask_line_edit(console: &mut Console<impl Lecteur, impl Write>, operation: usize, count: usize) -> saisie::Resultat<LineEdit> {
+    Ok(match operation {
+        1 => {
+            let line = console.entier(&format!("Insérer avant la ligne (1-{}): ", count + 1), 1..=count + 1)?;
+            LineEdit::Insert {
+                line,
+                text: console.demander("Ligne à insérer: ")?,
+            }
+        }
+        2 => {
+            let start = console.entier(&format!("Première ligne à supprimer (1-{}): ", count), 1..=count)?;
+            let end = console.entier(&format!("Dernière ligne à supprimer ({}-{}): ", start, count), start..=count)?;
+            LineEdit::Delete { lines: start..=end }
+        }
+        _ => {
+            let line = console.entier(&format!("Ligne à remplacer (1-{}): ", count), 1..=count)?;
+            LineEdit::Replace {
+                line,
+                text: console.demander("Nouveau contenu de la ligne: ")?,
+            }
+        }
+    })
+}
+
+// Questions de la recherche ; les motifs de noms sont séparés par des virgules
+fn ask_search_options(console: &mut Console<impl Lecteur, impl Write>) -> saisie::Resultat<SearchOptions> {
+    let regex = console.oui_non("Expression régulière ? (oui/non): ")?;
+    let ignore_case = console.oui_non("Ignorer la casse ? (oui/non): ")?;
+    let whole_word = console.oui_non("Mots entiers seulement ? (oui/non): ")?;
+    let recursive = console.oui_non("Chercher dans les sous-répertoires ? (oui/non): ")?;
+    let context = console.entier("Lignes de contexte (0 pour aucune): ", 0..=100)?;
+    let patterns = |text: &str| -> Option<Vec<glob::Pattern>> {
+        text.split(',')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| glob::Pattern::new(pattern).ok())
+            .collect()
+    };
+    let include = console.valeur("Fichiers à inclure (ex: *.rs,*.md, vide pour tous): ", "Motif invalide", patterns)?;
+    let exclude = console.valeur("Fichiers et répertoires à exclure (ex: target, vide pour aucun): ", "Motif invalide", patterns)?;
+    Ok(SearchOptions {
+        regex,
+        ignore_case,
+        whole_word,
+        recursive,
+        context,
+        include,
+        exclude,
+    })
+}
+
+fn ask_overwrite_policy(console: &mut Console<impl Lecteur, impl Write>) -> saisie::Resultat<OverwritePolicy> {
+    let options = ["demander", "ignorer", "écraser", "garder les deux"];
+    Ok(match console.choisir("Si la destination existe déjà: ", &options)? {
+        0 => OverwritePolicy::Ask,
+        1 => OverwritePolicy::Skip,
+        2 => OverwritePolicy::Overwrite,
+        _ => OverwritePolicy::KeepBoth,
+    })
+}
+
+// Version à comparer, ou None pour le contenu actuel (dernier choix de la liste)
+fn ask_version<'a>(
+    console: &mut Console<impl Lecteur, impl Write>,
+    versions: &'a [Version],
+    invite: &str,
+) -> saisie::Resultat<Option<&'a str>> {
+    let mut labels: Vec<String> = versions.iter().map(|version| version.label()).collect();
+    labels.push("fichier actuel".to_string());
+    let index = console.choisir(invite, &labels)?;
+    Ok(versions.get(index).map(|version| version.id.as_str()))
+}
+
+// Réponse à un conflit avec la politique « demander » ; sans réponse, l'existant est gardé
+fn ask_conflict(console: &mut Console<impl Lecteur, impl Write>, path: &str) -> OverwritePolicy {
+    let options = ["ignorer", "écraser", "garder les deux"];
+    match console.choisir(&format!("'{}' existe déjà: ", path), &options) {
+        Ok(1) => OverwritePolicy::Overwrite,
+        Ok(2) => OverwritePolicy::KeepBoth,
+        _ => OverwritePolicy::Skip,
     }
 }
@@ -0,0 +1,176 @@
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use regex::{Regex, RegexBuilder};
+
+// Recherche dans le contenu des fichiers, comme grep
+
+// Un fichier dont le début contient un octet nul est considéré comme binaire et ignoré
+const BINARY_CHECK_LENGTH: usize = 8000;
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    // Le motif est une expression régulière, et non un texte à trouver tel quel
+    pub regex: bool,
+    pub ignore_case: bool,
+    // Le motif ne correspond qu'à des mots entiers
+    pub whole_word: bool,
+    // Cherche aussi dans les sous-répertoires
+    pub recursive: bool,
+    // Nombre de lignes affichées avant et après chaque correspondance
+    pub context: usize,
+    // Seuls les fichiers dont le nom correspond à l'un de ces motifs sont lus (tous si la liste est vide)
+    pub include: Vec<glob::Pattern>,
+    // Fichiers et répertoires dont le nom correspond à l'un de ces motifs sont ignorés
+    pub exclude: Vec<glob::Pattern>,
+}
+
+// Ligne d'un résultat : une correspondance, ou une ligne de contexte (`matches` vide)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchLine {
+    // Chemin du fichier depuis le répertoire de la recherche : « src/main.rs »
+    pub path: String,
+    // Numéro de ligne, à partir de 1
+    pub number: usize,
+    pub text: String,
+    // Positions (en octets) des correspondances dans la ligne
+    pub matches: Vec<Range<usize>>,
+}
+
+impl SearchLine {
+    pub fn is_match(&self) -> bool {
+        !self.matches.is_empty()
+    }
+
+    // « fichier:ligne:texte » pour une correspondance, « fichier-ligne-texte » pour le contexte, comme grep.
+    // Avec `highlight`, les correspondances sont mises en valeur par des codes de couleur du terminal.
+    pub fn line(&self, highlight: bool) -> String {
+        let separator = if self.is_match() { ':' } else { '-' };
+        let mut text = String::new();
+        let mut end = 0;
+        for range in &self.matches {
+            text.push_str(&self.text[end..range.start]);
+            if highlight && !range.is_empty() {
+                text.push_str(&format!("\x1b[1;31m{}\x1b[0m", &self.text[range.clone()]));
+            } else {
+                text.push_str(&self.text[range.clone()]);
+            }
+            end = range.end;
+        }
+        text.push_str(&self.text[end..]);
+        format!("{}{}{}{}{}", self.path, separator, self.number, separator, text)
+    }
+}
+
+// Lignes à afficher, avec « -- » entre deux blocs qui ne se suivent pas quand il y a du contexte
+pub fn format_results(lines: &[SearchLine], context: usize, highlight: bool) -> Vec<String> {
+    let mut formatted = Vec::new();
+    let mut previous: Option<&SearchLine> = None;
+    for line in lines {
+        if let Some(previous) = previous
+            && context > 0
+            && (previous.path != line.path || previous.number + 1 != line.number)
+        {
+            formatted.push("--".to_string());
+        }
+        formatted.push(line.line(highlight));
+        previous = Some(line);
+    }
+    formatted
+}
+
+// Expression régulière d'un motif selon les options ; un motif invalide donne InvalidInput
+pub fn build_regex(pattern: &str, options: &SearchOptions) -> io::Result<Regex> {
+    let mut expression = if options.regex { pattern.to_string() } else { regex::escape(pattern) };
+    if options.whole_word {
+        expression = format!(r"\b(?:{})\b", expression);
+    }
+    RegexBuilder::new(&expression)
+        .case_insensitive(options.ignore_case)
+        .build()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, format!("motif invalide: {}", e)))
+}
+
+// Cherche dans les fichiers de `directory` (et de ses sous-répertoires avec `recursive`), dans l'ordre
+// des noms. Les liens symboliques ne sont pas suivis, les répertoires `hidden` sont ignorés, ainsi que
+// les fichiers binaires ou illisibles.
+pub(crate) fn search(
+    directory: &Path,
+    hidden: &[PathBuf],
+    regex: &Regex,
+    options: &SearchOptions,
+) -> io::Result<Vec<SearchLine>> {
+    let mut lines = Vec::new();
+    search_directory(directory, Path::new(""), hidden, regex, options, &mut lines)?;
+    Ok(lines)
+}
+
+fn search_directory(
+    directory: &Path,
+    relative: &Path,
+    hidden: &[PathBuf],
+    regex: &Regex,
+    options: &SearchOptions,
+    lines: &mut Vec<SearchLine>,
+) -> io::Result<()> {
+    let mut entries = fs::read_dir(directory)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let file_type = entry.file_type()?;
+        let excluded = options.exclude.iter().any(|pattern| pattern.matches(&name));
+        if excluded || file_type.is_symlink() || hidden.contains(&entry.path()) {
+            continue;
+        }
+        if file_type.is_dir() {
+            if options.recursive {
+                search_directory(&entry.path(), &relative.join(&name), hidden, regex, options, lines)?;
+            }
+        } else if options.include.is_empty() || options.include.iter().any(|pattern| pattern.matches(&name)) {
+            // Un fichier illisible n'arrête pas la recherche
+            if let Ok(content) = fs::read(entry.path()) {
+                search_file(&content, &relative.join(&name).to_string_lossy(), regex, options.context, lines);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn search_file(content: &[u8], path: &str, regex: &Regex, context: usize, lines: &mut Vec<SearchLine>) {
+    if content[..content.len().min(BINARY_CHECK_LENGTH)].contains(&0) {
+        return;
+    }
+    let content = String::from_utf8_lossy(content);
+    let texts: Vec<&str> = content.lines().collect();
+    let matching: Vec<usize> = (0..texts.len()).filter(|&index| regex.is_match(texts[index])).collect();
+
+    // Les lignes de contexte qui se chevauchent ne sont affichées qu'une fois
+    let mut next = 0;
+    for &index in &matching {
+        let start = index.saturating_sub(context).max(next);
+        let end = (index + context + 1).min(texts.len());
+        for (number, text) in texts.iter().enumerate().take(end).skip(start) {
+            let matches = if matching.binary_search(&number).is_ok() {
+                match_ranges(regex, text)
+            } else {
+                Vec::new()
+            };
+            lines.push(SearchLine {
+                path: path.to_string(),
+                number: number + 1,
+                text: text.to_string(),
+                matches,
+            });
+        }
+        next = next.max(end);
+    }
+}
+
+// Positions des correspondances dans une ligne. Un motif qui ne trouve que des chaînes vides (« ^ »)
+// garde une position vide, pour que la ligne reste une correspondance.
+fn match_ranges(regex: &Regex, text: &str) -> Vec<Range<usize>> {
+    let ranges: Vec<Range<usize>> = regex.find_iter(text).map(|found| found.range()).collect();
+    let non_empty: Vec<Range<usize>> = ranges.iter().filter(|range| !range.is_empty()).cloned().collect();
+    if non_empty.is_empty() { ranges.into_iter().take(1).collect() } else { non_empty }
+}
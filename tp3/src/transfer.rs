@@ -0,0 +1,71 @@
+use std::fs::{self, File, FileTimes};
+use std::io;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+
+// Que faire quand la destination d'une copie ou d'un déplacement existe déjà
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    // Demander pour chaque conflit
+    Ask,
+    Skip,
+    Overwrite,
+    // Garder l'existant et nommer la copie « nom (1).ext »
+    KeepBoth,
+}
+
+// Premier nom libre de la forme « rapport (1).txt », « rapport (2).txt »... à côté de `path`
+pub fn keep_both_name(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{} ({}){}", stem, n, extension)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .expect("un nom libre finit par être trouvé")
+}
+
+// Copie un fichier, un lien symbolique (recréé tel quel) ou un répertoire et tout son contenu, en gardant
+// les permissions et les dates d'accès et de modification
+pub fn copy_path(from: &Path, to: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(from)?;
+    if metadata.file_type().is_symlink() {
+        return symlink(fs::read_link(from)?, to);
+    }
+    if metadata.is_dir() {
+        fs::create_dir(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_path(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        // fs::copy copie aussi les permissions
+        fs::copy(from, to)?;
+    }
+    // Les dates du répertoire sont posées en dernier : y créer les copies les aurait modifiées
+    fs::set_permissions(to, metadata.permissions())?;
+    let times = FileTimes::new()
+        .set_accessed(metadata.accessed()?)
+        .set_modified(metadata.modified()?);
+    File::open(to)?.set_times(times)
+}
+
+// Déplace un fichier ou un répertoire. Entre deux systèmes de fichiers, où rename est impossible, la
+// source est copiée puis supprimée.
+pub fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            copy_path(from, to)?;
+            remove_path(from)
+        }
+        resultat => resultat,
+    }
+}
+
+// Supprime un fichier, un lien (pas sa cible) ou un répertoire et son contenu
+pub fn remove_path(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
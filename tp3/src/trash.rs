@@ -0,0 +1,145 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local, TimeDelta};
+use crate::atomic;
+use crate::file_manager::{Entry, EntryKind};
+use crate::transfer;
+
+// Corbeille à la manière de freedesktop : l'élément supprimé est rangé dans `files/<id>` et décrit par
+// `info/<id>.trashinfo` (chemin d'origine vu depuis la racine et date de suppression).
+
+pub const TRASH_DIRECTORY: &str = ".trash";
+const FILES: &str = "files";
+const INFO: &str = "info";
+const INFO_EXTENSION: &str = ".trashinfo";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashItem {
+    // Nom dans la corbeille, unique : « rapport.txt », puis « rapport (1).txt »...
+    pub id: String,
+    // Chemin d'origine vu depuis la racine : « /docs/rapport.txt »
+    pub original_path: String,
+    pub deleted_at: DateTime<Local>,
+    pub kind: EntryKind,
+}
+
+impl TrashItem {
+    // Ligne de la liste de la corbeille : date de suppression, puis chemin d'origine
+    pub fn line(&self) -> String {
+        let entry = Entry {
+            name: self.original_path.clone(),
+            kind: self.kind,
+        };
+        format!("{}  {}", self.deleted_at.format("%Y-%m-%d %H:%M"), entry)
+    }
+}
+
+pub(crate) fn files_path(trash: &Path, id: &str) -> PathBuf {
+    trash.join(FILES).join(id)
+}
+
+fn info_path(trash: &Path, id: &str) -> PathBuf {
+    trash.join(INFO).join(format!("{}{}", id, INFO_EXTENSION))
+}
+
+// Range `path` dans la corbeille. La description est écrite avant le déplacement : une coupure entre les
+// deux laisse une description sans élément, ignorée par `list`, et jamais un élément perdu.
+pub(crate) fn put(trash: &Path, path: &Path, original_path: &str) -> io::Result<TrashItem> {
+    fs::create_dir_all(trash.join(FILES))?;
+    fs::create_dir_all(trash.join(INFO))?;
+    let name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "nom de fichier invalide"))?;
+    let mut destination = trash.join(FILES).join(name);
+    while fs::symlink_metadata(&destination).is_ok() || fs::symlink_metadata(info_path(trash, &id_of(&destination))).is_ok() {
+        destination = transfer::keep_both_name(&destination);
+    }
+    let item = TrashItem {
+        id: id_of(&destination),
+        original_path: original_path.to_string(),
+        deleted_at: Local::now(),
+        kind: EntryKind::of(fs::symlink_metadata(path)?.file_type()),
+    };
+    let info = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        item.original_path,
+        item.deleted_at.to_rfc3339()
+    );
+    atomic::write(&info_path(trash, &item.id), info.as_bytes())?;
+    if let Err(e) = transfer::move_path(path, &destination) {
+        let _ = fs::remove_file(info_path(trash, &item.id));
+        return Err(e);
+    }
+    Ok(item)
+}
+
+fn id_of(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().into_owned()
+}
+
+// Éléments de la corbeille, du plus ancien au plus récent ; les descriptions illisibles ou sans élément
+// sont ignorées
+pub(crate) fn list(trash: &Path) -> io::Result<Vec<TrashItem>> {
+    let entries = match fs::read_dir(trash.join(INFO)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut items = Vec::new();
+    for entry in entries {
+        let file_name = entry?.file_name().to_string_lossy().into_owned();
+        let Some(id) = file_name.strip_suffix(INFO_EXTENSION) else {
+            continue;
+        };
+        if let Some(item) = read_item(trash, id) {
+            items.push(item);
+        }
+    }
+    items.sort_by(|a, b| a.deleted_at.cmp(&b.deleted_at).then_with(|| a.id.cmp(&b.id)));
+    Ok(items)
+}
+
+fn read_item(trash: &Path, id: &str) -> Option<TrashItem> {
+    let info = fs::read_to_string(info_path(trash, id)).ok()?;
+    let value = |key: &str| info.lines().find_map(|line| line.strip_prefix(key)?.strip_prefix('='));
+    let deleted_at = DateTime::parse_from_rfc3339(value("DeletionDate")?).ok()?;
+    let metadata = fs::symlink_metadata(files_path(trash, id)).ok()?;
+    Some(TrashItem {
+        id: id.to_string(),
+        original_path: value("Path")?.to_string(),
+        deleted_at: deleted_at.with_timezone(&Local),
+        kind: EntryKind::of(metadata.file_type()),
+    })
+}
+
+// Oublie la description d'un élément sorti de la corbeille
+pub(crate) fn forget(trash: &Path, id: &str) -> io::Result<()> {
+    fs::remove_file(info_path(trash, id))
+}
+
+// Supprime définitivement un élément et sa description
+pub(crate) fn remove(trash: &Path, id: &str) -> io::Result<()> {
+    match transfer::remove_path(&files_path(trash, id)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    forget(trash, id)
+}
+
+// Supprime les éléments supprimés depuis plus de `max_age`, ou tous sans limite ; retourne leur nombre.
+// Sans limite, les restes d'une opération interrompue (élément sans description...) partent aussi.
+pub(crate) fn purge(trash: &Path, max_age: Option<TimeDelta>) -> io::Result<usize> {
+    let items = list(trash)?;
+    let Some(max_age) = max_age else {
+        if fs::symlink_metadata(trash).is_ok() {
+            transfer::remove_path(trash)?;
+        }
+        return Ok(items.len());
+    };
+    let limit = Local::now() - max_age;
+    let mut purged = 0;
+    for item in items.iter().filter(|item| item.deleted_at < limit) {
+        remove(trash, &item.id)?;
+        purged += 1;
+    }
+    Ok(purged)
+}
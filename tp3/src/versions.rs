@@ -0,0 +1,97 @@
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta, Utc};
+use similar::TextDiff;
+use crate::atomic;
+
+// Anciennes versions des fichiers : le contenu de `docs/rapport.txt` avant chaque écriture est gardé dans
+// `.versions/docs/rapport.txt/<date>`, la date (UTC, à la microseconde) servant d'identifiant.
+
+pub const VERSIONS_DIRECTORY: &str = ".versions";
+const ID_FORMAT: &str = "%Y%m%dT%H%M%S%.6fZ";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    // Identifiant de la version, tiré de sa date : « 20261019T085312.123456Z »
+    pub id: String,
+    pub saved_at: DateTime<Local>,
+    pub size: u64,
+}
+
+impl Version {
+    // Date de la version pour les listes et les menus
+    pub fn label(&self) -> String {
+        self.saved_at.format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+}
+
+// Garde le contenu actuel de `path` comme nouvelle version, puis ne conserve que les `keep` plus récentes
+pub(crate) fn save(directory: &Path, path: &Path, keep: usize) -> io::Result<Version> {
+    fs::create_dir_all(directory)?;
+    let mut saved_at = Utc::now();
+    // Deux écritures dans la même microseconde : la seconde version prend la microseconde suivante
+    while fs::symlink_metadata(directory.join(saved_at.format(ID_FORMAT).to_string())).is_ok() {
+        saved_at += TimeDelta::microseconds(1);
+    }
+    let id = saved_at.format(ID_FORMAT).to_string();
+    atomic::write_with(&directory.join(&id), |file| io::copy(&mut File::open(path)?, file).map(|_| ()))?;
+
+    let versions = list(directory)?;
+    for old in &versions[..versions.len().saturating_sub(keep)] {
+        fs::remove_file(directory.join(&old.id))?;
+    }
+    Ok(Version {
+        id,
+        saved_at: saved_at.with_timezone(&Local),
+        size: fs::metadata(path)?.len(),
+    })
+}
+
+// Versions d'un fichier, de la plus ancienne à la plus récente
+pub(crate) fn list(directory: &Path) -> io::Result<Vec<Version>> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut versions = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let id = entry.file_name().to_string_lossy().into_owned();
+        // Les fichiers temporaires et les répertoires des versions de fichiers plus profonds sont ignorés
+        let Ok(saved_at) = NaiveDateTime::parse_from_str(&id, ID_FORMAT) else {
+            continue;
+        };
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            versions.push(Version {
+                id,
+                saved_at: saved_at.and_utc().with_timezone(&Local),
+                size: metadata.len(),
+            });
+        }
+    }
+    versions.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(versions)
+}
+
+// Chemin du contenu d'une version ; une version inconnue donne NotFound
+pub(crate) fn path(directory: &Path, id: &str) -> io::Result<PathBuf> {
+    if NaiveDateTime::parse_from_str(id, ID_FORMAT).is_err() || !directory.join(id).is_file() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("la version '{}' n'existe pas", id)));
+    }
+    Ok(directory.join(id))
+}
+
+// Différences ligne à ligne au format unifié (diff -u), vide si les deux contenus sont identiques
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_label, new_label)
+        .to_string()
+}
@@ -0,0 +1,112 @@
+// Écritures atomiques et ajouts : une écriture qui échoue laisse le fichier d'origine intact
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+use tp3::atomic;
+use tp3::file_manager::FileManager;
+
+// Noms présents dans le répertoire, fichiers temporaires compris
+fn contenu(dossier: &Path) -> Vec<String> {
+    let mut noms: Vec<String> = fs::read_dir(dossier)
+        .unwrap()
+        .map(|entree| entree.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    noms.sort();
+    noms
+}
+
+// Simule un disque plein au milieu de l'écriture
+fn disque_plein(fichier: &mut fs::File) -> io::Result<()> {
+    fichier.write_all(b"debut du nouveau contenu")?;
+    Err(io::Error::new(io::ErrorKind::StorageFull, "disque plein"))
+}
+
+#[test]
+fn ecriture_complete() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("notes.txt");
+    fs::write(&chemin, "ancien").unwrap();
+    fs::set_permissions(&chemin, fs::Permissions::from_mode(0o600)).unwrap();
+
+    atomic::write(&chemin, b"nouveau").unwrap();
+    assert_eq!(fs::read_to_string(&chemin).unwrap(), "nouveau");
+    assert_eq!(fs::metadata(&chemin).unwrap().permissions().mode() & 0o777, 0o600);
+    assert_eq!(contenu(dossier.path()), ["notes.txt"]);
+
+    atomic::write(&dossier.path().join("neuf.txt"), b"x").unwrap();
+    assert_eq!(contenu(dossier.path()), ["neuf.txt", "notes.txt"]);
+}
+
+#[test]
+fn une_ecriture_interrompue_garde_l_original() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("notes.txt");
+    fs::write(&chemin, "contenu précieux").unwrap();
+
+    let erreur = atomic::write_with(&chemin, disque_plein).unwrap_err();
+    assert_eq!(erreur.kind(), io::ErrorKind::StorageFull);
+    assert_eq!(fs::read_to_string(&chemin).unwrap(), "contenu précieux");
+    // Le fichier temporaire a été supprimé
+    assert_eq!(contenu(dossier.path()), ["notes.txt"]);
+
+    // Un fichier qui n'existait pas n'est pas créé
+    let absent = dossier.path().join("absent.txt");
+    assert!(atomic::write_with(&absent, disque_plein).is_err());
+    assert!(!absent.exists());
+    assert_eq!(contenu(dossier.path()), ["notes.txt"]);
+}
+
+#[test]
+fn un_renommage_impossible_ne_laisse_rien() {
+    let dossier = tempfile::tempdir().unwrap();
+    let cible = dossier.path().join("dossier");
+    fs::create_dir(&cible).unwrap();
+    fs::write(cible.join("dedans.txt"), "x").unwrap();
+
+    // Un fichier ne peut pas remplacer un répertoire non vide
+    assert!(atomic::write(&cible, b"contenu").is_err());
+    assert!(cible.join("dedans.txt").exists());
+    assert_eq!(contenu(dossier.path()), ["dossier"]);
+}
+
+#[test]
+fn ajout_sans_reecriture() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("journal.txt");
+
+    atomic::append(&chemin, "première").unwrap();
+    let inode = fs::metadata(&chemin).unwrap().ino();
+    atomic::append(&chemin, "deuxième\n").unwrap();
+    assert_eq!(fs::read_to_string(&chemin).unwrap(), "première\ndeuxième\n");
+
+    // Une dernière ligne sans retour à la ligne est terminée avant l'ajout
+    fs::OpenOptions::new().append(true).open(&chemin).unwrap().write_all(b"sans fin").unwrap();
+    atomic::append(&chemin, "troisième").unwrap();
+    assert_eq!(fs::read_to_string(&chemin).unwrap(), "première\ndeuxième\nsans fin\ntroisième\n");
+    // Le fichier est complété sur place, pas remplacé
+    assert_eq!(fs::metadata(&chemin).unwrap().ino(), inode);
+}
+
+#[test]
+fn un_ajout_interrompu_est_annule() {
+    let dossier = tempfile::tempdir().unwrap();
+    let chemin = dossier.path().join("journal.txt");
+    fs::write(&chemin, "ligne 1\nligne 2").unwrap();
+
+    assert!(atomic::append_with(&chemin, disque_plein).is_err());
+    assert_eq!(fs::read_to_string(&chemin).unwrap(), "ligne 1\nligne 2");
+}
+
+#[test]
+fn le_gestionnaire_ecrit_et_ajoute_de_facon_sure() {
+    let dossier = tempfile::tempdir().unwrap();
+    let file_manager = FileManager::new(dossier.path()).unwrap();
+    file_manager.write_file("a.txt", "un\n").unwrap();
+    file_manager.modify_file("a.txt", "deux").unwrap();
+    file_manager.modify_file("b.txt", "créé par l'ajout").unwrap();
+
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "un\ndeux\n");
+    assert_eq!(file_manager.read_file("b.txt").unwrap(), "créé par l'ajout\n");
+    assert_eq!(contenu(dossier.path()), ["a.txt", "b.txt"]);
+}
@@ -0,0 +1,26 @@
+// Aides partagées par les tests du gestionnaire de fichiers ; chaque fichier de test n'en utilise qu'une partie
+#![allow(dead_code)]
+
+use std::io::ErrorKind;
+use tp3::file_manager::{FileManager, FileManagerError};
+use tp3::transfer::OverwritePolicy;
+
+// Gestionnaire de fichiers sur un répertoire temporaire, supprimé à la fin du test
+pub fn racine() -> (tempfile::TempDir, FileManager) {
+    let dossier = tempfile::tempdir().unwrap();
+    let file_manager = FileManager::new(dossier.path()).unwrap();
+    (dossier, file_manager)
+}
+
+// Politique sans question : `ask` ne doit pas être appelé
+pub fn jamais(chemin: &str) -> OverwritePolicy {
+    panic!("question inattendue pour {}", chemin)
+}
+
+// Type de l'erreur d'entrée/sortie attendue
+pub fn kind<T: std::fmt::Debug>(resultat: tp3::file_manager::Result<T>) -> ErrorKind {
+    match resultat {
+        Err(FileManagerError::Io(e)) => e.kind(),
+        autre => panic!("erreur d'entrée/sortie attendue: {:?}", autre),
+    }
+}
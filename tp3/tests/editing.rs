@@ -0,0 +1,127 @@
+// Modifications de lignes et remplacements : aperçu, confirmation et écriture
+mod common;
+
+use std::io::ErrorKind;
+use tp3::editing::{LineEdit, apply_line_edit};
+use tp3::file_manager::FileManager;
+use tp3::search::SearchOptions;
+use common::kind;
+
+fn racine() -> (tempfile::TempDir, FileManager) {
+    let (dossier, file_manager) = common::racine();
+    file_manager.write_file("a.txt", "un\ndeux\ntrois\n").unwrap();
+    (dossier, file_manager)
+}
+
+fn inserer(line: usize, text: &str) -> LineEdit {
+    LineEdit::Insert {
+        line,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn modifications_de_lignes() {
+    let contenu = "un\ndeux\ntrois\n";
+    assert_eq!(apply_line_edit(contenu, &inserer(1, "zéro")).unwrap(), "zéro\nun\ndeux\ntrois\n");
+    assert_eq!(apply_line_edit(contenu, &inserer(4, "quatre")).unwrap(), "un\ndeux\ntrois\nquatre\n");
+    assert_eq!(apply_line_edit(contenu, &inserer(2, "")).unwrap(), "un\n\ndeux\ntrois\n");
+    assert_eq!(apply_line_edit(contenu, &inserer(2, "a\nb")).unwrap(), "un\na\nb\ndeux\ntrois\n");
+    assert_eq!(apply_line_edit(contenu, &LineEdit::Delete { lines: 1..=2 }).unwrap(), "trois\n");
+    assert_eq!(apply_line_edit(contenu, &LineEdit::Delete { lines: 1..=3 }).unwrap(), "");
+    let remplacer = LineEdit::Replace {
+        line: 3,
+        text: "3".to_string(),
+    };
+    assert_eq!(apply_line_edit(contenu, &remplacer).unwrap(), "un\ndeux\n3\n");
+
+    // Un fichier sans fin de ligne finale le reste ; un fichier vide accepte une insertion
+    assert_eq!(apply_line_edit("un\ndeux", &inserer(3, "trois")).unwrap(), "un\ndeux\ntrois");
+    assert_eq!(apply_line_edit("", &inserer(1, "seule")).unwrap(), "seule\n");
+
+    let invalides = [
+        inserer(5, "x"),
+        inserer(0, "x"),
+        LineEdit::Delete { lines: 2..=4 },
+        LineEdit::Delete {
+            lines: std::ops::RangeInclusive::new(3, 2),
+        },
+    ];
+    for edit in invalides {
+        let erreur = apply_line_edit(contenu, &edit).unwrap_err();
+        assert_eq!(erreur.kind(), ErrorKind::InvalidInput, "{:?}", edit);
+    }
+    assert_eq!(
+        apply_line_edit("", &LineEdit::Delete { lines: 1..=1 }).unwrap_err().to_string(),
+        "la ligne 1 n'existe pas, le fichier a 0 ligne(s)"
+    );
+}
+
+#[test]
+fn apercu_puis_application() {
+    let (_dossier, file_manager) = racine();
+    let preview = file_manager.edit_lines("a.txt", &inserer(2, "un et demi")).unwrap();
+    // Rien n'est écrit avant la confirmation
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "un\ndeux\ntrois\n");
+    assert_eq!(preview.diff(), "--- a.txt (avant)\n+++ a.txt (après)\n@@ -1,3 +1,4 @@\n un\n+un et demi\n deux\n trois\n");
+    assert_eq!(preview.replacements, 0);
+
+    file_manager.apply_edit(&preview).unwrap();
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "un\nun et demi\ndeux\ntrois\n");
+
+    // Un aperçu périmé n'écrase pas une modification faite entre-temps
+    let preview = file_manager.edit_lines("a.txt", &LineEdit::Delete { lines: 1..=1 }).unwrap();
+    file_manager.modify_file("a.txt", "quatre").unwrap();
+    assert_eq!(kind(file_manager.apply_edit(&preview)), ErrorKind::Other);
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "un\nun et demi\ndeux\ntrois\nquatre\n");
+
+    assert_eq!(kind(file_manager.edit_lines("a.txt", &inserer(10, "x"))), ErrorKind::InvalidInput);
+    assert_eq!(kind(file_manager.edit_lines("absent.txt", &inserer(1, "x"))), ErrorKind::NotFound);
+}
+
+#[test]
+fn rechercher_et_remplacer() {
+    let (_dossier, file_manager) = racine();
+    file_manager.write_file("a.txt", "Le chat dort.\nchatte et chat.\n").unwrap();
+
+    let mots = SearchOptions {
+        whole_word: true,
+        ignore_case: true,
+        ..SearchOptions::default()
+    };
+    let preview = file_manager.replace_text("a.txt", "CHAT", "chien", &mots).unwrap();
+    assert_eq!(preview.content, "Le chien dort.\nchatte et chien.\n");
+    assert_eq!(preview.replacements, 2);
+
+    // Texte tel quel : ni le motif, ni le remplacement ne sont interprétés
+    file_manager.write_file("prix.txt", "total: 10.5 (ttc)\n").unwrap();
+    let preview = file_manager.replace_text("prix.txt", "(ttc)", "$1 €", &SearchOptions::default()).unwrap();
+    assert_eq!(preview.content, "total: 10.5 $1 €\n");
+
+    // Expression régulière avec groupes capturés
+    let regex = SearchOptions {
+        regex: true,
+        ..SearchOptions::default()
+    };
+    let preview = file_manager.replace_text("prix.txt", r"(\d+)\.(\d+)", "${1},${2}", &regex).unwrap();
+    assert_eq!(preview.content, "total: 10,5 (ttc)\n");
+    file_manager.apply_edit(&preview).unwrap();
+    assert_eq!(file_manager.read_file("prix.txt").unwrap(), "total: 10,5 (ttc)\n");
+
+    let preview = file_manager.replace_text("prix.txt", "absent", "x", &SearchOptions::default()).unwrap();
+    assert!(preview.is_unchanged());
+    assert_eq!(preview.diff(), "");
+    assert_eq!(kind(file_manager.replace_text("prix.txt", "(", "x", &regex)), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn l_ancien_contenu_devient_une_version() {
+    let (_dossier, mut file_manager) = racine();
+    file_manager.set_max_versions(5);
+    let preview = file_manager.edit_lines("a.txt", &LineEdit::Delete { lines: 2..=3 }).unwrap();
+    file_manager.apply_edit(&preview).unwrap();
+    let versions = file_manager.list_versions("a.txt").unwrap();
+    assert_eq!(versions.len(), 1);
+    file_manager.restore_version("a.txt", &versions[0].id).unwrap();
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "un\ndeux\ntrois\n");
+}
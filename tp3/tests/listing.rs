@@ -0,0 +1,128 @@
+// Liste détaillée : tailles lisibles, permissions, tri et filtres
+use std::fs::{self, File};
+use std::os::unix::fs::PermissionsExt;
+use std::time::{Duration, SystemTime};
+use tp3::file_manager::FileManager;
+use tp3::listing::{ListOptions, SortKey, format_size, parse_size};
+
+// Répertoire de test : trois fichiers de tailles et de dates différentes, un répertoire et un fichier caché
+fn exemple() -> (tempfile::TempDir, FileManager) {
+    let dossier = tempfile::tempdir().unwrap();
+    let file_manager = FileManager::new(dossier.path()).unwrap();
+    let maintenant = SystemTime::now();
+    for (nom, taille, age_jours) in [("b.txt", 10, 1), ("a.rs", 3000, 3), ("c.TXT", 500, 2), (".cache", 1, 0)] {
+        file_manager.write_file(nom, &"x".repeat(taille)).unwrap();
+        let fichier = File::options().write(true).open(dossier.path().join(nom)).unwrap();
+        fichier.set_modified(maintenant - Duration::from_secs(age_jours * 86400)).unwrap();
+    }
+    file_manager.make_directory("dossier").unwrap();
+    (dossier, file_manager)
+}
+
+fn noms(file_manager: &FileManager, options: &ListOptions) -> Vec<String> {
+    file_manager
+        .list_details(options)
+        .unwrap()
+        .into_iter()
+        .map(|details| details.entry.to_string())
+        .collect()
+}
+
+#[test]
+fn tailles_lisibles() {
+    assert_eq!(format_size(0), "0 o");
+    assert_eq!(format_size(1023), "1023 o");
+    assert_eq!(format_size(1536), "1,5 Ko");
+    assert_eq!(format_size(20 * 1024 * 1024), "20 Mo");
+    assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3,0 Go");
+
+    assert_eq!(parse_size("2048"), Some(2048));
+    assert_eq!(parse_size("10K"), Some(10240));
+    assert_eq!(parse_size("1,5 Mo"), Some(1572864));
+    assert_eq!(parse_size("2g"), Some(2147483648));
+    assert_eq!(parse_size("beaucoup"), None);
+    assert_eq!(parse_size("-1"), None);
+}
+
+#[test]
+fn tri_par_nom_taille_ou_date() {
+    let (_dossier, file_manager) = exemple();
+    assert_eq!(noms(&file_manager, &ListOptions::default()), ["a.rs", "b.txt", "c.TXT", "dossier/"]);
+
+    let fichiers = |sort, reverse| {
+        let options = ListOptions {
+            sort,
+            reverse,
+            pattern: Some(glob::Pattern::new("?.*").unwrap()),
+            ..ListOptions::default()
+        };
+        noms(&file_manager, &options)
+    };
+    assert_eq!(fichiers(SortKey::Size, false), ["b.txt", "c.TXT", "a.rs"]);
+    assert_eq!(fichiers(SortKey::Size, true), ["a.rs", "c.TXT", "b.txt"]);
+    assert_eq!(fichiers(SortKey::Modified, false), ["a.rs", "c.TXT", "b.txt"]);
+    assert_eq!(fichiers(SortKey::Name, true), ["c.TXT", "b.txt", "a.rs"]);
+}
+
+#[test]
+fn filtres() {
+    let (_dossier, file_manager) = exemple();
+    let motif = ListOptions {
+        pattern: Some(glob::Pattern::new("*.txt").unwrap()),
+        ..ListOptions::default()
+    };
+    assert_eq!(noms(&file_manager, &motif), ["b.txt"]);
+
+    // L'extension ne tient pas compte de la casse, avec ou sans le point
+    for extension in ["txt", ".TXT"] {
+        let options = ListOptions {
+            extension: Some(extension.to_string()),
+            ..ListOptions::default()
+        };
+        assert_eq!(noms(&file_manager, &options), ["b.txt", "c.TXT"]);
+    }
+
+    let taille = ListOptions {
+        min_size: Some(500),
+        sort: SortKey::Size,
+        ..ListOptions::default()
+    };
+    assert_eq!(noms(&file_manager, &taille), ["c.TXT", "a.rs", "dossier/"]);
+}
+
+#[test]
+fn fichiers_caches_sur_demande() {
+    let (_dossier, mut file_manager) = exemple();
+    assert!(!noms(&file_manager, &ListOptions::default()).contains(&".cache".to_string()));
+    let options = ListOptions {
+        show_hidden: true,
+        ..ListOptions::default()
+    };
+    assert_eq!(noms(&file_manager, &options)[0], ".cache");
+
+    // Les répertoires du gestionnaire restent cachés
+    file_manager.set_max_versions(1);
+    file_manager.write_file("b.txt", "nouveau").unwrap();
+    file_manager.delete_file("c.TXT").unwrap();
+    assert_eq!(noms(&file_manager, &options), [".cache", "a.rs", "b.txt", "dossier/"]);
+}
+
+#[test]
+fn colonnes_de_la_ligne() {
+    let (dossier, file_manager) = exemple();
+    fs::set_permissions(dossier.path().join("a.rs"), fs::Permissions::from_mode(0o750)).unwrap();
+    let details = file_manager.list_details(&ListOptions::default()).unwrap();
+
+    let fichier = &details[0];
+    assert_eq!(fichier.permissions(), "-rwxr-x---");
+    assert_eq!(fichier.kind_name(), "fichier");
+    assert_eq!(fichier.size, 3000);
+    let date = fichier.modified.unwrap().format("%Y-%m-%d %H:%M").to_string();
+    let ligne = fichier.line();
+    assert!(ligne.starts_with("-rwxr-x---  fichier"), "{}", ligne);
+    assert!(ligne.contains(&format!("2,9 Ko  {}", date)), "{}", ligne);
+    assert!(ligne.ends_with("  a.rs"), "{}", ligne);
+
+    assert!(details[3].permissions().starts_with('d'));
+    assert_eq!(details[3].kind_name(), "répertoire");
+}
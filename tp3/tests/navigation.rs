@@ -0,0 +1,97 @@
+// Navigation dans les sous-répertoires : cd, pwd, mkdir, rmdir, tree et listage avec les types
+mod common;
+
+use std::io::ErrorKind;
+use std::os::unix::fs::symlink;
+use tp3::file_manager::{Entry, EntryKind, FileManagerError};
+use common::{kind, racine};
+
+#[test]
+fn cd_et_pwd() {
+    let (_dossier, mut file_manager) = racine();
+    assert_eq!(file_manager.pwd(), "/");
+    file_manager.make_directory("projets/rust").unwrap();
+
+    file_manager.change_directory("projets/rust").unwrap();
+    assert_eq!(file_manager.pwd(), "/projets/rust");
+    // Les noms de fichiers sont relatifs au répertoire courant
+    file_manager.write_file("main.rs", "fn main() {}").unwrap();
+    assert!(file_manager.root().join("projets/rust/main.rs").exists());
+
+    file_manager.change_directory("..").unwrap();
+    assert_eq!(file_manager.pwd(), "/projets");
+    assert_eq!(file_manager.read_file("rust/main.rs").unwrap(), "fn main() {}");
+
+    // Pas plus haut que la racine, ni dans un fichier
+    file_manager.change_directory("..").unwrap();
+    assert!(matches!(file_manager.change_directory(".."), Err(FileManagerError::OutsideRoot(_))));
+    assert_eq!(kind(file_manager.change_directory("projets/rust/main.rs")), ErrorKind::NotADirectory);
+    assert_eq!(kind(file_manager.change_directory("absent")), ErrorKind::NotFound);
+    assert_eq!(file_manager.pwd(), "/");
+}
+
+#[test]
+fn le_listage_montre_les_repertoires_et_les_liens() {
+    let (_dossier, file_manager) = racine();
+    file_manager.write_file("b.txt", "").unwrap();
+    file_manager.make_directory("a").unwrap();
+    symlink(file_manager.root().join("b.txt"), file_manager.root().join("c")).unwrap();
+
+    let entries = file_manager.list_files().unwrap();
+    let affichage: Vec<String> = entries.iter().map(Entry::to_string).collect();
+    assert_eq!(affichage, ["a/", "b.txt", "c@"]);
+    assert_eq!(entries[0].kind, EntryKind::Directory);
+    assert_eq!(entries[1].kind, EntryKind::File);
+    assert_eq!(entries[2].kind, EntryKind::Symlink);
+}
+
+#[test]
+fn rmdir_ne_vide_un_repertoire_que_sur_demande() {
+    let (_dossier, mut file_manager) = racine();
+    file_manager.make_directory("vide").unwrap();
+    file_manager.make_directory("plein/sous").unwrap();
+    file_manager.write_file("plein/sous/f.txt", "x").unwrap();
+
+    file_manager.remove_directory("vide", false).unwrap();
+    assert_eq!(kind(file_manager.remove_directory("plein", false)), ErrorKind::DirectoryNotEmpty);
+    assert!(file_manager.root().join("plein/sous/f.txt").exists());
+    assert_eq!(kind(file_manager.remove_directory("plein/sous/f.txt", true)), ErrorKind::NotADirectory);
+
+    // Le répertoire courant et ses parents sont protégés
+    file_manager.change_directory("plein/sous").unwrap();
+    assert_eq!(kind(file_manager.remove_directory("..", true)), ErrorKind::InvalidInput);
+    assert_eq!(kind(file_manager.remove_directory(".", true)), ErrorKind::InvalidInput);
+    file_manager.change_directory("../..").unwrap();
+
+    file_manager.remove_directory("plein", true).unwrap();
+    assert!(file_manager.list_files().unwrap().is_empty());
+}
+
+#[test]
+fn rmdir_sur_un_lien_ne_touche_pas_a_sa_cible() {
+    let (_dossier, file_manager) = racine();
+    file_manager.make_directory("donnees").unwrap();
+    file_manager.write_file("donnees/f.txt", "x").unwrap();
+    symlink(file_manager.root().join("donnees"), file_manager.root().join("lien")).unwrap();
+
+    assert_eq!(kind(file_manager.remove_directory("lien", true)), ErrorKind::NotADirectory);
+    file_manager.delete_file("lien").unwrap();
+    assert_eq!(file_manager.read_file("donnees/f.txt").unwrap(), "x");
+}
+
+#[test]
+fn arborescence_avec_profondeur() {
+    let (_dossier, file_manager) = racine();
+    file_manager.make_directory("src/bin").unwrap();
+    file_manager.write_file("src/bin/outil.rs", "").unwrap();
+    file_manager.write_file("src/lib.rs", "").unwrap();
+    file_manager.write_file("Cargo.toml", "").unwrap();
+
+    assert_eq!(
+        file_manager.tree(".", None).unwrap(),
+        ["./", "├── Cargo.toml", "└── src/", "    ├── bin/", "    │   └── outil.rs", "    └── lib.rs"]
+    );
+    assert_eq!(file_manager.tree("src", Some(1)).unwrap(), ["src/", "├── bin/", "└── lib.rs"]);
+    assert_eq!(kind(file_manager.tree("Cargo.toml", None)), ErrorKind::NotADirectory);
+    assert!(matches!(file_manager.tree("..", None), Err(FileManagerError::OutsideRoot(_))));
+}
@@ -0,0 +1,92 @@
+// Les chemins du gestionnaire de fichiers restent dans son répertoire racine
+use std::fs;
+use std::os::unix::fs::symlink;
+use tp3::file_manager::{FileManager, FileManagerError};
+
+// Racine de test dans un dossier temporaire, à côté d'un dossier « dehors » qui ne doit pas être atteint
+fn sandbox() -> (tempfile::TempDir, FileManager) {
+    let dossier = tempfile::tempdir().unwrap();
+    fs::create_dir(dossier.path().join("racine")).unwrap();
+    fs::create_dir(dossier.path().join("dehors")).unwrap();
+    fs::write(dossier.path().join("dehors/secret.txt"), "secret").unwrap();
+    let file_manager = FileManager::new(dossier.path().join("racine")).unwrap();
+    (dossier, file_manager)
+}
+
+fn refuse<T: std::fmt::Debug>(resultat: tp3::file_manager::Result<T>) -> bool {
+    matches!(resultat, Err(FileManagerError::OutsideRoot(_)))
+}
+
+#[test]
+fn les_noms_simples_restent_dans_la_racine() {
+    let (_dossier, file_manager) = sandbox();
+    file_manager.write_file("note.txt", "bonjour").unwrap();
+    fs::create_dir(file_manager.root().join("sous")).unwrap();
+    file_manager.write_file("sous/../sous/./autre.txt", "salut").unwrap();
+
+    assert_eq!(file_manager.read_file("./note.txt").unwrap(), "bonjour");
+    assert_eq!(file_manager.read_file("sous/autre.txt").unwrap(), "salut");
+    assert_eq!(file_manager.resolve("nouveau.txt").unwrap(), file_manager.root().join("nouveau.txt"));
+}
+
+#[test]
+fn remonter_au_dessus_de_la_racine_est_refuse() {
+    let (_dossier, file_manager) = sandbox();
+    assert!(refuse(file_manager.read_file("../dehors/secret.txt")));
+    assert!(refuse(file_manager.read_file("../../../../etc/passwd")));
+    assert!(refuse(file_manager.write_file("a/../../dehors/pirate.txt", "x")));
+    assert!(refuse(file_manager.delete_file("..")));
+    assert!(!file_manager.root().parent().unwrap().join("dehors/pirate.txt").exists());
+}
+
+#[test]
+fn les_chemins_absolus_sont_refuses() {
+    let (dossier, file_manager) = sandbox();
+    let secret = dossier.path().join("dehors/secret.txt");
+    assert!(refuse(file_manager.read_file(secret.to_str().unwrap())));
+    assert!(refuse(file_manager.read_file("/etc/passwd")));
+    // Même un chemin absolu qui désigne un fichier de la racine
+    let interne = file_manager.root().join("note.txt");
+    assert!(refuse(file_manager.write_file(interne.to_str().unwrap(), "x")));
+}
+
+#[test]
+fn les_liens_symboliques_vers_l_exterieur_sont_refuses() {
+    let (dossier, file_manager) = sandbox();
+    let racine = file_manager.root().to_path_buf();
+    symlink(dossier.path().join("dehors/secret.txt"), racine.join("lien.txt")).unwrap();
+    symlink(dossier.path().join("dehors"), racine.join("porte")).unwrap();
+    symlink(dossier.path().join("dehors/absent.txt"), racine.join("casse.txt")).unwrap();
+
+    assert!(refuse(file_manager.read_file("lien.txt")));
+    assert!(refuse(file_manager.write_file("lien.txt", "écrasé")));
+    assert!(refuse(file_manager.read_file("porte/secret.txt")));
+    assert!(refuse(file_manager.write_file("porte/nouveau.txt", "x")));
+    assert!(refuse(file_manager.delete_file("porte/secret.txt")));
+    // Écrire à travers un lien cassé créerait sa cible hors de la racine
+    assert!(refuse(file_manager.write_file("casse.txt", "x")));
+
+    assert_eq!(fs::read_to_string(dossier.path().join("dehors/secret.txt")).unwrap(), "secret");
+    assert!(!dossier.path().join("dehors/nouveau.txt").exists());
+    assert!(!dossier.path().join("dehors/absent.txt").exists());
+}
+
+#[test]
+fn les_liens_symboliques_internes_sont_suivis() {
+    let (_dossier, file_manager) = sandbox();
+    let racine = file_manager.root().to_path_buf();
+    fs::create_dir(racine.join("donnees")).unwrap();
+    fs::write(racine.join("donnees/vrai.txt"), "contenu").unwrap();
+    symlink(racine.join("donnees"), racine.join("raccourci")).unwrap();
+
+    assert_eq!(file_manager.read_file("raccourci/vrai.txt").unwrap(), "contenu");
+    file_manager.write_file("raccourci/cree.txt", "x").unwrap();
+    assert!(racine.join("donnees/cree.txt").exists());
+}
+
+#[test]
+fn message_de_l_erreur() {
+    let (_dossier, file_manager) = sandbox();
+    let erreur = file_manager.read_file("../dehors/secret.txt").unwrap_err();
+    assert_eq!(erreur.to_string(), "accès refusé, '../dehors/secret.txt' est hors du répertoire racine");
+}
@@ -0,0 +1,134 @@
+// Recherche dans le contenu des fichiers : motifs, options, contexte, fichiers binaires et filtres
+use std::fs;
+use std::io::ErrorKind;
+use std::os::unix::fs::symlink;
+use tp3::file_manager::{FileManager, FileManagerError};
+use tp3::search::{SearchOptions, format_results};
+
+// Racine de test : deux fichiers texte, un fichier binaire et un sous-répertoire
+fn exemple() -> (tempfile::TempDir, FileManager) {
+    let dossier = tempfile::tempdir().unwrap();
+    let file_manager = FileManager::new(dossier.path()).unwrap();
+    file_manager.write_file("notes.txt", "Rust est rapide\nrustique\nfin\n").unwrap();
+    file_manager.write_file("main.rs", "fn main() {\n    // rust\n}\n").unwrap();
+    fs::write(dossier.path().join("image.bin"), b"rust\0\x01\x02").unwrap();
+    file_manager.make_directory("src").unwrap();
+    file_manager.write_file("src/lib.rs", "pub fn rust() {}\n").unwrap();
+    (dossier, file_manager)
+}
+
+fn resultats(file_manager: &FileManager, motif: &str, options: &SearchOptions) -> Vec<String> {
+    let lines = file_manager.search(motif, options).unwrap();
+    format_results(&lines, options.context, false)
+}
+
+fn motifs(motifs: &[&str]) -> Vec<glob::Pattern> {
+    motifs.iter().map(|motif| glob::Pattern::new(motif).unwrap()).collect()
+}
+
+#[test]
+fn texte_simple_et_casse() {
+    let (_dossier, file_manager) = exemple();
+    assert_eq!(resultats(&file_manager, "rust", &SearchOptions::default()), ["main.rs:2:    // rust", "notes.txt:2:rustique"]);
+
+    let options = SearchOptions {
+        ignore_case: true,
+        ..SearchOptions::default()
+    };
+    assert_eq!(
+        resultats(&file_manager, "RUST", &options),
+        ["main.rs:2:    // rust", "notes.txt:1:Rust est rapide", "notes.txt:2:rustique"]
+    );
+    // Sans expression régulière, les caractères spéciaux sont cherchés tels quels
+    assert_eq!(resultats(&file_manager, "main()", &SearchOptions::default()), ["main.rs:1:fn main() {"]);
+}
+
+#[test]
+fn expression_reguliere_et_mots_entiers() {
+    let (_dossier, file_manager) = exemple();
+    let regex = SearchOptions {
+        regex: true,
+        ..SearchOptions::default()
+    };
+    assert_eq!(resultats(&file_manager, r"^\w+ est", &regex), ["notes.txt:1:Rust est rapide"]);
+
+    let mots = SearchOptions {
+        whole_word: true,
+        ignore_case: true,
+        ..SearchOptions::default()
+    };
+    assert_eq!(resultats(&file_manager, "rust", &mots), ["main.rs:2:    // rust", "notes.txt:1:Rust est rapide"]);
+
+    let erreur = file_manager.search("(", &regex).unwrap_err();
+    assert!(matches!(&erreur, FileManagerError::Io(e) if e.kind() == ErrorKind::InvalidInput), "{}", erreur);
+}
+
+#[test]
+fn recursif_avec_filtres() {
+    let (dossier, file_manager) = exemple();
+    symlink(dossier.path().join("src"), dossier.path().join("lien")).unwrap();
+    let recursif = SearchOptions {
+        recursive: true,
+        ..SearchOptions::default()
+    };
+    // Les liens ne sont pas suivis : src/lib.rs n'est trouvé qu'une fois
+    assert_eq!(
+        resultats(&file_manager, "rust", &recursif),
+        ["main.rs:2:    // rust", "notes.txt:2:rustique", "src/lib.rs:1:pub fn rust() {}"]
+    );
+
+    let inclus = SearchOptions {
+        include: motifs(&["*.rs"]),
+        ..recursif.clone()
+    };
+    assert_eq!(resultats(&file_manager, "rust", &inclus), ["main.rs:2:    // rust", "src/lib.rs:1:pub fn rust() {}"]);
+    let exclus = SearchOptions {
+        exclude: motifs(&["src", "notes.*"]),
+        ..recursif
+    };
+    assert_eq!(resultats(&file_manager, "rust", &exclus), ["main.rs:2:    // rust"]);
+}
+
+#[test]
+fn lignes_de_contexte() {
+    let (_dossier, file_manager) = exemple();
+    let contenu: String = (1..=10).map(|n| format!("ligne {}\n", n)).collect();
+    file_manager.write_file("long.txt", &contenu).unwrap();
+    let options = SearchOptions {
+        regex: true,
+        context: 1,
+        include: motifs(&["long.txt"]),
+        ..SearchOptions::default()
+    };
+    assert_eq!(
+        resultats(&file_manager, "ligne (2|3|8)$", &options),
+        [
+            "long.txt-1-ligne 1",
+            "long.txt:2:ligne 2",
+            "long.txt:3:ligne 3",
+            "long.txt-4-ligne 4",
+            "--",
+            "long.txt-7-ligne 7",
+            "long.txt:8:ligne 8",
+            "long.txt-9-ligne 9",
+        ]
+    );
+}
+
+#[test]
+fn mise_en_valeur_et_fichiers_internes() {
+    let (_dossier, mut file_manager) = exemple();
+    file_manager.set_max_versions(5);
+    file_manager.write_file("notes.txt", "nouveau\n").unwrap();
+    file_manager.delete_file("main.rs").unwrap();
+
+    // Ni la corbeille, ni les versions ne sont fouillées
+    let options = SearchOptions {
+        recursive: true,
+        ..SearchOptions::default()
+    };
+    let lines = file_manager.search("rust", &options).unwrap();
+    assert_eq!(format_results(&lines, 0, false), ["src/lib.rs:1:pub fn rust() {}"]);
+    assert_eq!(lines[0].matches, vec![std::ops::Range { start: 7, end: 11 }]);
+    assert_eq!(lines[0].line(true), "src/lib.rs:1:pub fn \x1b[1;31mrust\x1b[0m() {}");
+}
@@ -0,0 +1,193 @@
+// Copie, déplacement et renommage : conflits, contenu des répertoires, dates et permissions conservées
+mod common;
+
+use std::fs::{self, File, FileTimes};
+use std::io::ErrorKind;
+use std::os::unix::fs::{MetadataExt, PermissionsExt, symlink};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tp3::file_manager::FileManagerError;
+use tp3::transfer::{self, OverwritePolicy};
+use common::{jamais, kind, racine};
+
+// Fichier daté d'il y a une semaine, en lecture seule pour le groupe et les autres
+fn fichier_ancien(chemin: &Path) -> SystemTime {
+    fs::write(chemin, "contenu").unwrap();
+    fs::set_permissions(chemin, fs::Permissions::from_mode(0o640)).unwrap();
+    let date = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    File::options()
+        .write(true)
+        .open(chemin)
+        .unwrap()
+        .set_times(FileTimes::new().set_modified(date).set_accessed(date))
+        .unwrap();
+    date
+}
+
+#[test]
+fn copie_d_un_fichier_avec_ses_dates_et_permissions() {
+    let (dossier, file_manager) = racine();
+    let date = fichier_ancien(&dossier.path().join("a.txt"));
+
+    let copie = file_manager.copy_file("a.txt", "b.txt", OverwritePolicy::Skip, &mut jamais).unwrap();
+    assert_eq!(copie.as_deref(), Some("/b.txt"));
+    let metadata = fs::metadata(dossier.path().join("b.txt")).unwrap();
+    assert_eq!(metadata.modified().unwrap(), date);
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o640);
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "contenu");
+
+    // Vers un répertoire existant, la copie garde son nom
+    file_manager.make_directory("archives").unwrap();
+    let copie = file_manager.copy_file("a.txt", "archives", OverwritePolicy::Skip, &mut jamais).unwrap();
+    assert_eq!(copie.as_deref(), Some("/archives/a.txt"));
+}
+
+#[test]
+fn politiques_en_cas_de_conflit() {
+    let (_dossier, file_manager) = racine();
+    file_manager.write_file("source.txt", "nouveau").unwrap();
+    file_manager.write_file("cible.txt", "ancien").unwrap();
+
+    let copier = |policy, ask: &mut dyn FnMut(&str) -> OverwritePolicy| {
+        file_manager.copy_file("source.txt", "cible.txt", policy, ask).unwrap()
+    };
+    assert_eq!(copier(OverwritePolicy::Skip, &mut jamais), None);
+    assert_eq!(file_manager.read_file("cible.txt").unwrap(), "ancien");
+
+    assert_eq!(copier(OverwritePolicy::KeepBoth, &mut jamais).as_deref(), Some("/cible (1).txt"));
+    assert_eq!(copier(OverwritePolicy::KeepBoth, &mut jamais).as_deref(), Some("/cible (2).txt"));
+    assert_eq!(file_manager.read_file("cible (2).txt").unwrap(), "nouveau");
+
+    // Avec Ask, la question porte sur le chemin existant
+    let mut questions = Vec::new();
+    let mut ask = |chemin: &str| {
+        questions.push(chemin.to_string());
+        OverwritePolicy::Overwrite
+    };
+    assert_eq!(copier(OverwritePolicy::Ask, &mut ask).as_deref(), Some("/cible.txt"));
+    assert_eq!(questions, ["/cible.txt"]);
+    assert_eq!(file_manager.read_file("cible.txt").unwrap(), "nouveau");
+
+    // Une réponse Ask à la question compte comme Skip
+    file_manager.write_file("cible.txt", "ancien").unwrap();
+    assert_eq!(copier(OverwritePolicy::Ask, &mut |_| OverwritePolicy::Ask), None);
+    assert_eq!(file_manager.read_file("cible.txt").unwrap(), "ancien");
+}
+
+#[test]
+fn un_fichier_ne_remplace_pas_un_repertoire() {
+    let (_dossier, file_manager) = racine();
+    file_manager.write_file("a.txt", "").unwrap();
+    file_manager.make_directory("d/a.txt").unwrap();
+    let resultat = file_manager.copy_file("a.txt", "d", OverwritePolicy::Overwrite, &mut jamais);
+    assert_eq!(kind(resultat), ErrorKind::AlreadyExists);
+    assert!(file_manager.is_directory("d/a.txt").unwrap());
+}
+
+#[test]
+fn copie_recursive_d_un_repertoire() {
+    let (dossier, file_manager) = racine();
+    file_manager.make_directory("projet/src").unwrap();
+    let date = fichier_ancien(&dossier.path().join("projet/src/main.rs"));
+    symlink("src/main.rs", dossier.path().join("projet/lien")).unwrap();
+    File::open(dossier.path().join("projet"))
+        .unwrap()
+        .set_times(FileTimes::new().set_modified(date))
+        .unwrap();
+
+    assert_eq!(kind(file_manager.copy_file("projet", "copie", OverwritePolicy::Skip, &mut jamais)), ErrorKind::IsADirectory);
+    let copie = file_manager.copy_directory("projet", "copie", OverwritePolicy::Skip, &mut jamais).unwrap();
+    assert_eq!(copie.as_deref(), Some("/copie"));
+
+    assert_eq!(file_manager.read_file("copie/src/main.rs").unwrap(), "contenu");
+    assert_eq!(fs::metadata(dossier.path().join("copie/src/main.rs")).unwrap().modified().unwrap(), date);
+    assert_eq!(fs::metadata(dossier.path().join("copie")).unwrap().modified().unwrap(), date);
+    // Le lien est recopié comme lien
+    assert_eq!(fs::read_link(dossier.path().join("copie/lien")).unwrap(), Path::new("src/main.rs"));
+    assert_eq!(file_manager.read_file("copie/lien").unwrap(), "contenu");
+
+    // Ni dans lui-même, ni hors de la racine
+    let resultat = file_manager.copy_directory("projet", "projet/src", OverwritePolicy::Skip, &mut jamais);
+    assert_eq!(kind(resultat), ErrorKind::InvalidInput);
+    let resultat = file_manager.copy_directory("projet", "../evasion", OverwritePolicy::Skip, &mut jamais);
+    assert!(matches!(resultat, Err(FileManagerError::OutsideRoot(_))));
+}
+
+#[test]
+fn deplacements() {
+    let (dossier, mut file_manager) = racine();
+    let date = fichier_ancien(&dossier.path().join("note.txt"));
+    file_manager.make_directory("a/b").unwrap();
+
+    let deplace = file_manager.move_file("note.txt", "a", OverwritePolicy::Skip, &mut jamais).unwrap();
+    assert_eq!(deplace.as_deref(), Some("/a/note.txt"));
+    assert!(!dossier.path().join("note.txt").exists());
+    assert_eq!(fs::metadata(dossier.path().join("a/note.txt")).unwrap().modified().unwrap(), date);
+
+    assert_eq!(kind(file_manager.move_file("a", "c", OverwritePolicy::Skip, &mut jamais)), ErrorKind::IsADirectory);
+    let deplace = file_manager.move_directory("a", "c", OverwritePolicy::Skip, &mut jamais).unwrap();
+    assert_eq!(deplace.as_deref(), Some("/c"));
+    assert_eq!(file_manager.read_file("c/note.txt").unwrap(), "contenu");
+
+    // Ni dans lui-même, ni le répertoire courant
+    let resultat = file_manager.move_directory("c", "c/b", OverwritePolicy::Skip, &mut jamais);
+    assert_eq!(kind(resultat), ErrorKind::InvalidInput);
+    file_manager.change_directory("c/b").unwrap();
+    let resultat = file_manager.move_directory("..", "../../d", OverwritePolicy::Skip, &mut jamais);
+    assert_eq!(kind(resultat), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn un_repertoire_ne_remplace_pas_son_propre_parent() {
+    let (_dossier, file_manager) = racine();
+    file_manager.make_directory("a/a").unwrap();
+    file_manager.write_file("a/a/f.txt", "x").unwrap();
+    let resultat = file_manager.move_directory("a/a", ".", OverwritePolicy::Overwrite, &mut jamais);
+    assert_eq!(kind(resultat), ErrorKind::InvalidInput);
+    assert_eq!(file_manager.read_file("a/a/f.txt").unwrap(), "x");
+}
+
+#[test]
+fn renommage() {
+    let (_dossier, file_manager) = racine();
+    file_manager.make_directory("docs").unwrap();
+    file_manager.write_file("docs/brouillon.md", "texte").unwrap();
+    file_manager.write_file("docs/final.md", "ancien").unwrap();
+
+    let renomme = file_manager.rename("docs/brouillon.md", "final.md", OverwritePolicy::KeepBoth, &mut jamais).unwrap();
+    assert_eq!(renomme.as_deref(), Some("/docs/final (1).md"));
+    let renomme = file_manager.rename("docs", "documents", OverwritePolicy::Skip, &mut jamais).unwrap();
+    assert_eq!(renomme.as_deref(), Some("/documents"));
+    assert_eq!(file_manager.read_file("documents/final (1).md").unwrap(), "texte");
+
+    for nom in ["autre/final.md", "..", "/tmp/x", ""] {
+        let resultat = file_manager.rename("documents/final.md", nom, OverwritePolicy::Skip, &mut jamais);
+        assert_eq!(kind(resultat), ErrorKind::InvalidInput, "{}", nom);
+    }
+    let resultat = file_manager.rename("absent", "x", OverwritePolicy::Skip, &mut jamais);
+    assert_eq!(kind(resultat), ErrorKind::NotFound);
+}
+
+#[test]
+fn deplacement_entre_deux_systemes_de_fichiers() {
+    // Nécessite un second système de fichiers (tmpfs) ; le test ne fait rien sinon
+    let autre = Path::new("/dev/shm");
+    let dossier = tempfile::tempdir().unwrap();
+    let Ok(cible) = tempfile::tempdir_in(autre) else {
+        return;
+    };
+    if fs::metadata(dossier.path()).unwrap().dev() == fs::metadata(cible.path()).unwrap().dev() {
+        return;
+    }
+
+    let source = dossier.path().join("arbre");
+    fs::create_dir_all(source.join("sous")).unwrap();
+    let date = fichier_ancien(&source.join("sous/f.txt"));
+    transfer::move_path(&source, &cible.path().join("arbre")).unwrap();
+
+    assert!(!source.exists());
+    let copie = cible.path().join("arbre/sous/f.txt");
+    assert_eq!(fs::read_to_string(&copie).unwrap(), "contenu");
+    assert_eq!(fs::metadata(&copie).unwrap().modified().unwrap(), date);
+    assert_eq!(fs::metadata(&copie).unwrap().permissions().mode() & 0o777, 0o640);
+}
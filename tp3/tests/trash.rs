@@ -0,0 +1,147 @@
+// Corbeille : suppression réversible, restauration avec conflits, vidage et purge automatique
+mod common;
+
+use std::fs;
+use std::io::ErrorKind;
+use std::os::unix::fs::symlink;
+use chrono::{Local, TimeDelta};
+use tp3::file_manager::{EntryKind, FileManager};
+use tp3::transfer::OverwritePolicy;
+use common::{jamais, kind, racine};
+
+// Fait comme si l'élément avait été supprimé il y a `jours` jours
+fn vieillir(file_manager: &FileManager, id: &str, jours: i64) {
+    let chemin = file_manager.trash_directory().join(format!("info/{}.trashinfo", id));
+    let info = fs::read_to_string(&chemin).unwrap();
+    let date = (Local::now() - TimeDelta::days(jours)).to_rfc3339();
+    let info: Vec<String> = info
+        .lines()
+        .map(|ligne| if ligne.starts_with("DeletionDate=") { format!("DeletionDate={}", date) } else { ligne.to_string() })
+        .collect();
+    fs::write(chemin, info.join("\n")).unwrap();
+}
+
+#[test]
+fn supprimer_puis_restaurer() {
+    let (_dossier, mut file_manager) = racine();
+    file_manager.make_directory("docs").unwrap();
+    file_manager.write_file("docs/rapport.txt", "contenu").unwrap();
+    file_manager.change_directory("docs").unwrap();
+
+    let avant = Local::now();
+    let item = file_manager.delete_file("rapport.txt").unwrap();
+    assert_eq!(item.id, "rapport.txt");
+    assert_eq!(item.original_path, "/docs/rapport.txt");
+    assert_eq!(item.kind, EntryKind::File);
+    assert!(item.deleted_at >= avant - TimeDelta::seconds(1));
+    assert!(file_manager.list_files().unwrap().is_empty());
+    file_manager.change_directory("..").unwrap();
+    // La corbeille n'apparaît ni dans la liste, ni dans l'arborescence
+    assert_eq!(file_manager.tree(".", None).unwrap(), ["./", "└── docs/"]);
+    file_manager.change_directory("docs").unwrap();
+    assert_eq!(file_manager.list_trash().unwrap(), std::slice::from_ref(&item));
+    assert!(item.line().ends_with("  /docs/rapport.txt"), "{}", item.line());
+
+    // Le répertoire d'origine est recréé s'il a disparu entre-temps
+    file_manager.change_directory("..").unwrap();
+    file_manager.remove_directory("docs", false).unwrap();
+    let restaure = file_manager.restore(&item.id, OverwritePolicy::Skip, &mut jamais).unwrap();
+    assert_eq!(restaure.as_deref(), Some("/docs/rapport.txt"));
+    assert_eq!(file_manager.read_file("docs/rapport.txt").unwrap(), "contenu");
+    assert!(file_manager.list_trash().unwrap().is_empty());
+    assert_eq!(kind(file_manager.restore(&item.id, OverwritePolicy::Skip, &mut jamais)), ErrorKind::NotFound);
+}
+
+#[test]
+fn deux_fichiers_de_meme_nom() {
+    let (_dossier, file_manager) = racine();
+    file_manager.make_directory("a").unwrap();
+    file_manager.write_file("a/note.txt", "a").unwrap();
+    file_manager.write_file("note.txt", "racine").unwrap();
+
+    let premier = file_manager.delete_file("a/note.txt").unwrap();
+    let second = file_manager.delete_file("note.txt").unwrap();
+    assert_eq!(premier.id, "note.txt");
+    assert_eq!(second.id, "note (1).txt");
+    let chemins: Vec<String> = file_manager.list_trash().unwrap().into_iter().map(|item| item.original_path).collect();
+    assert_eq!(chemins, ["/a/note.txt", "/note.txt"]);
+
+    file_manager.restore(&second.id, OverwritePolicy::Skip, &mut jamais).unwrap();
+    assert_eq!(file_manager.read_file("note.txt").unwrap(), "racine");
+}
+
+#[test]
+fn restaurer_sur_un_emplacement_occupe() {
+    let (_dossier, file_manager) = racine();
+    file_manager.write_file("a.txt", "ancien").unwrap();
+    let item = file_manager.delete_file("a.txt").unwrap();
+    file_manager.write_file("a.txt", "nouveau").unwrap();
+
+    // Laissé : l'élément reste dans la corbeille
+    assert_eq!(file_manager.restore(&item.id, OverwritePolicy::Ask, &mut |_| OverwritePolicy::Skip).unwrap(), None);
+    assert_eq!(file_manager.list_trash().unwrap().len(), 1);
+
+    let restaure = file_manager.restore(&item.id, OverwritePolicy::KeepBoth, &mut jamais).unwrap();
+    assert_eq!(restaure.as_deref(), Some("/a (1).txt"));
+    assert_eq!(file_manager.read_file("a (1).txt").unwrap(), "ancien");
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "nouveau");
+
+    let item = file_manager.delete_file("a (1).txt").unwrap();
+    file_manager.write_file("a (1).txt", "autre").unwrap();
+    file_manager.restore(&item.id, OverwritePolicy::Overwrite, &mut jamais).unwrap();
+    assert_eq!(file_manager.read_file("a (1).txt").unwrap(), "ancien");
+}
+
+#[test]
+fn ce_qui_ne_va_pas_a_la_corbeille() {
+    let (_dossier, file_manager) = racine();
+    file_manager.make_directory("dossier").unwrap();
+    file_manager.write_file("cible.txt", "x").unwrap();
+    symlink(file_manager.root().join("cible.txt"), file_manager.root().join("lien")).unwrap();
+
+    assert_eq!(kind(file_manager.delete_file("dossier")), ErrorKind::IsADirectory);
+    assert_eq!(kind(file_manager.delete_file("absent")), ErrorKind::NotFound);
+    // Un lien part seul, sans sa cible
+    let item = file_manager.delete_file("lien").unwrap();
+    assert_eq!(item.kind, EntryKind::Symlink);
+    assert_eq!(file_manager.read_file("cible.txt").unwrap(), "x");
+    // Le contenu de la corbeille ne se supprime qu'en la vidant
+    assert_eq!(kind(file_manager.delete_file(".trash/files/lien")), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn vider_la_corbeille() {
+    let (_dossier, file_manager) = racine();
+    for nom in ["a", "b", "c"] {
+        file_manager.write_file(nom, "").unwrap();
+        file_manager.delete_file(nom).unwrap();
+    }
+    assert_eq!(file_manager.empty_trash().unwrap(), 3);
+    assert!(file_manager.list_trash().unwrap().is_empty());
+    assert!(!file_manager.trash_directory().exists());
+    assert_eq!(file_manager.empty_trash().unwrap(), 0);
+}
+
+#[test]
+fn purge_des_elements_trop_anciens() {
+    let (_dossier, mut file_manager) = racine();
+    for nom in ["vieux", "recent"] {
+        file_manager.write_file(nom, "").unwrap();
+        file_manager.delete_file(nom).unwrap();
+    }
+    vieillir(&file_manager, "vieux", 40);
+
+    // Sans durée de conservation, rien n'est purgé
+    assert_eq!(file_manager.purge_trash().unwrap(), 0);
+    file_manager.set_trash_retention(Some(TimeDelta::days(30)));
+    assert_eq!(file_manager.purge_trash().unwrap(), 1);
+    let restants: Vec<String> = file_manager.list_trash().unwrap().into_iter().map(|item| item.id).collect();
+    assert_eq!(restants, ["recent"]);
+
+    // Une suppression purge aussi au passage
+    vieillir(&file_manager, "recent", 31);
+    file_manager.write_file("nouveau", "").unwrap();
+    file_manager.delete_file("nouveau").unwrap();
+    let restants: Vec<String> = file_manager.list_trash().unwrap().into_iter().map(|item| item.id).collect();
+    assert_eq!(restants, ["nouveau"]);
+}
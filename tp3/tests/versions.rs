@@ -0,0 +1,108 @@
+// Versions des fichiers : enregistrement à chaque écriture, limite, diff et restauration
+mod common;
+
+use std::io::ErrorKind;
+use tp3::file_manager::FileManager;
+use tp3::versions::unified_diff;
+use common::kind;
+
+fn racine(max_versions: usize) -> (tempfile::TempDir, FileManager) {
+    let (dossier, mut file_manager) = common::racine();
+    file_manager.set_max_versions(max_versions);
+    (dossier, file_manager)
+}
+
+fn tailles(file_manager: &FileManager, nom: &str) -> Vec<u64> {
+    file_manager.list_versions(nom).unwrap().iter().map(|version| version.size).collect()
+}
+
+#[test]
+fn chaque_ecriture_garde_l_ancien_contenu() {
+    let (_dossier, file_manager) = racine(10);
+    file_manager.write_file("a.txt", "un\n").unwrap();
+    // La création n'a pas d'ancien contenu
+    assert!(file_manager.list_versions("a.txt").unwrap().is_empty());
+
+    file_manager.write_file("a.txt", "deux\n").unwrap();
+    file_manager.modify_file("a.txt", "trois").unwrap();
+    let versions = file_manager.list_versions("a.txt").unwrap();
+    assert_eq!(tailles(&file_manager, "a.txt"), [3, 5]);
+    assert!(versions[0].saved_at <= versions[1].saved_at);
+    assert!(versions[0].id < versions[1].id);
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "deux\ntrois\n");
+
+    // Les versions ne se mélangent pas entre fichiers et ne sont pas listées
+    file_manager.make_directory("docs").unwrap();
+    file_manager.write_file("docs/a.txt", "x").unwrap();
+    file_manager.write_file("docs/a.txt", "y").unwrap();
+    assert_eq!(tailles(&file_manager, "docs/a.txt"), [1]);
+    let noms: Vec<String> = file_manager.list_files().unwrap().iter().map(ToString::to_string).collect();
+    assert_eq!(noms, ["a.txt", "docs/"]);
+}
+
+#[test]
+fn seules_les_dernieres_versions_sont_gardees() {
+    let (_dossier, file_manager) = racine(3);
+    for taille in 1..=6 {
+        file_manager.write_file("a.txt", &"x".repeat(taille)).unwrap();
+    }
+    assert_eq!(tailles(&file_manager, "a.txt"), [3, 4, 5]);
+
+    // Sans limite positive, aucune version n'est gardée
+    let (_dossier, file_manager) = racine(0);
+    file_manager.write_file("b.txt", "1").unwrap();
+    file_manager.write_file("b.txt", "2").unwrap();
+    assert!(file_manager.list_versions("b.txt").unwrap().is_empty());
+    assert!(!file_manager.versions_directory().exists());
+}
+
+#[test]
+fn diff_entre_versions() {
+    let (_dossier, file_manager) = racine(10);
+    file_manager.write_file("a.txt", "un\ndeux\ntrois\n").unwrap();
+    file_manager.write_file("a.txt", "un\n2\ntrois\n").unwrap();
+    file_manager.modify_file("a.txt", "quatre").unwrap();
+    let versions = file_manager.list_versions("a.txt").unwrap();
+    let (premiere, seconde) = (versions[0].id.as_str(), versions[1].id.as_str());
+
+    let diff = file_manager.diff_versions("a.txt", Some(premiere), Some(seconde)).unwrap();
+    let attendu = format!(
+        "--- a.txt@{}\n+++ a.txt@{}\n@@ -1,3 +1,3 @@\n un\n-deux\n+2\n trois\n",
+        premiere, seconde
+    );
+    assert_eq!(diff, attendu);
+
+    // Contre le fichier actuel
+    let diff = file_manager.diff_versions("a.txt", Some(seconde), None).unwrap();
+    assert!(diff.starts_with(&format!("--- a.txt@{}\n+++ a.txt\n", seconde)), "{}", diff);
+    assert!(diff.ends_with(" trois\n+quatre\n"), "{}", diff);
+    assert_eq!(file_manager.diff_versions("a.txt", None, None).unwrap(), "");
+
+    assert_eq!(kind(file_manager.diff_versions("a.txt", Some("inconnue"), None)), ErrorKind::NotFound);
+    assert_eq!(kind(file_manager.diff_versions("a.txt", Some("../../a.txt"), None)), ErrorKind::NotFound);
+}
+
+#[test]
+fn restaurer_une_version() {
+    let (_dossier, file_manager) = racine(2);
+    file_manager.write_file("a.txt", "v1").unwrap();
+    file_manager.write_file("a.txt", "v2").unwrap();
+    file_manager.write_file("a.txt", "v3").unwrap();
+    let versions = file_manager.list_versions("a.txt").unwrap();
+    assert_eq!(versions.len(), 2);
+
+    // La plus ancienne version reste restaurable même si l'enregistrement de v3 la fait disparaître
+    file_manager.restore_version("a.txt", &versions[0].id).unwrap();
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "v1");
+    // Le contenu remplacé est devenu une version : la restauration peut être annulée
+    let versions = file_manager.list_versions("a.txt").unwrap();
+    assert_eq!(tailles(&file_manager, "a.txt"), [2, 2]);
+    file_manager.restore_version("a.txt", &versions[1].id).unwrap();
+    assert_eq!(file_manager.read_file("a.txt").unwrap(), "v3");
+}
+
+#[test]
+fn format_unifie() {
+    assert_eq!(unified_diff("a\n", "a\n", "avant", "après"), "");
+    assert_eq!(unified_diff("", "a\n", "avant", "après"), "--- avant\n+++ après\n@@ -0,0 +1 @@\n+a\n");
+}